@@ -1,16 +1,23 @@
-use crate::{First, Main, MainSchedulePlugin, Plugin, Plugins, Startup, StateTransition, Update};
+use crate::{
+    First, Last, Main, MainSchedulePlugin, Plugin, Plugins, Startup, StateTransition, Update,
+};
 pub use bevy_derive::AppLabel;
 use bevy_ecs::{
+    event::EventRetention,
+    index::{init_index, refresh_index, ComponentIndex},
     prelude::*,
     schedule::{
-        apply_state_transition, common_conditions::run_once as run_once_condition,
-        run_enter_schedule, BoxedScheduleLabel, IntoSystemConfigs, IntoSystemSetConfigs,
-        ScheduleLabel,
+        apply_next_state, compute_state, enter_state, exit_state, transition_state,
+        update_sub_state, BoxedScheduleLabel, IntoSystemConfigs, IntoSystemSetConfigs,
+        ScheduleLabel, StateSet, StateTransitionSet, StateTransitionSteps,
     },
+    system::{DefaultErrorHandler, ErrorHandler},
+    world::{compact_world, CompactionPolicy},
 };
-use bevy_utils::{tracing::debug, HashMap, HashSet};
+use bevy_utils::{tracing::debug, Duration, HashMap, HashSet, Instant};
 use std::{
     fmt::Debug,
+    hash::Hash,
     panic::{catch_unwind, resume_unwind, AssertUnwindSafe},
 };
 
@@ -138,6 +145,27 @@ pub struct SubApp {
     /// A function that allows access to both the main [`App`] [`World`] and the [`SubApp`]. This is
     /// useful for moving data between the sub app and the main app.
     extract: Box<dyn Fn(&mut World, &mut App) + Send>,
+
+    cadence: UpdateCadence,
+    skipped_updates: u32,
+    last_update: Option<Instant>,
+}
+
+/// How often a [`SubApp`] is extracted and run by [`App::update`].
+///
+/// A sub app can always be updated right away with [`App::update_sub_app`], whatever its cadence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum UpdateCadence {
+    /// The sub app is updated on every update of the main app. This is the default.
+    #[default]
+    EveryUpdate,
+    /// The sub app is updated once every `n` updates of the main app.
+    EveryNthUpdate(u32),
+    /// The sub app is updated on the first update of the main app after at least this much time
+    /// has elapsed since its last update.
+    Interval(Duration),
+    /// The sub app is only updated by [`App::update_sub_app`].
+    Manual,
 }
 
 impl SubApp {
@@ -151,6 +179,51 @@ impl SubApp {
         Self {
             app,
             extract: Box::new(extract),
+            cadence: UpdateCadence::EveryUpdate,
+            skipped_updates: 0,
+            last_update: None,
+        }
+    }
+
+    /// Sets how often this sub app is updated by [`App::update`].
+    pub fn with_cadence(mut self, cadence: UpdateCadence) -> Self {
+        self.set_cadence(cadence);
+        self
+    }
+
+    /// Sets how often this sub app is updated by [`App::update`].
+    pub fn set_cadence(&mut self, cadence: UpdateCadence) {
+        self.cadence = cadence;
+        self.skipped_updates = 0;
+    }
+
+    /// Returns how often this sub app is updated by [`App::update`].
+    pub fn cadence(&self) -> UpdateCadence {
+        self.cadence
+    }
+
+    /// Extracts data from the main world to this sub app, then runs its default schedule.
+    pub fn update(&mut self, main_world: &mut World) {
+        self.extract(main_world);
+        self.run();
+        self.skipped_updates = 0;
+        self.last_update = Some(Instant::now());
+    }
+
+    /// Returns `true` if this sub app should be updated during the current update of the main app,
+    /// according to its [`UpdateCadence`].
+    fn is_due(&mut self) -> bool {
+        match self.cadence {
+            UpdateCadence::EveryUpdate => true,
+            UpdateCadence::EveryNthUpdate(n) => {
+                self.skipped_updates += 1;
+                self.skipped_updates >= n
+            }
+            UpdateCadence::Interval(interval) => match self.last_update {
+                Some(last_update) => last_update.elapsed() >= interval,
+                None => true,
+            },
+            UpdateCadence::Manual => false,
         }
     }
 
@@ -226,7 +299,7 @@ impl App {
 
     /// Advances the execution of the [`Schedule`] by one cycle.
     ///
-    /// This method also updates sub apps.
+    /// This method also updates sub apps, according to their [`UpdateCadence`].
     /// See [`insert_sub_app`](Self::insert_sub_app) for more details.
     ///
     /// The schedule run by this method is determined by the [`main_schedule_label`](App) field.
@@ -244,10 +317,12 @@ impl App {
             self.world.run_schedule(&*self.main_schedule_label);
         }
         for (_label, sub_app) in self.sub_apps.iter_mut() {
+            if !sub_app.is_due() {
+                continue;
+            }
             #[cfg(feature = "trace")]
             let _sub_app_span = info_span!("sub app", name = ?_label).entered();
-            sub_app.extract(&mut self.world);
-            sub_app.run();
+            sub_app.update(&mut self.world);
         }
 
         self.world.clear_trackers();
@@ -326,35 +401,92 @@ impl App {
         self.plugin_registry = plugin_registry;
     }
 
-    /// Adds [`State<S>`] and [`NextState<S>`] resources, [`OnEnter`] and [`OnExit`] schedules
-    /// for each state variant (if they don't already exist), an instance of [`apply_state_transition::<S>`] in
-    /// [`StateTransition`] so that transitions happen before [`Update`] and
-    /// a instance of [`run_enter_schedule::<S>`] in [`StateTransition`] with a
-    /// [`run_once`](`run_once_condition`) condition to run the on enter schedule of the
-    /// initial state.
+    /// Adds [`State<S>`], [`NextState<S>`] and [`StateStack<S>`] resources, and the systems applying
+    /// the transitions of `S` in [`StateTransition`] so that transitions happen before [`Update`].
+    /// The [`OnEnter`] schedule of the initial state runs the first time [`StateTransition`] runs,
+    /// even if another state is already queued in [`NextState<S>`], which is entered right after.
+    ///
+    /// States can be pushed onto and popped from the [`StateStack<S>`], which runs the [`OnPause`]
+    /// and [`OnResume`] schedules of the covered state instead of its [`OnExit`] and [`OnEnter`] schedules.
     ///
     /// If you would like to control how other systems run based on the current state,
-    /// you can emulate this behavior using the [`in_state`] [`Condition`](bevy_ecs::schedule::Condition).
+    /// you can emulate this behavior using the [`in_state`] [`Condition`](bevy_ecs::schedule::Condition),
+    /// or [`in_state_stack`] to include the covered states.
     ///
     /// Note that you can also apply state transitions at other points in the schedule
-    /// by adding the [`apply_state_transition`] system manually.
+    /// by running the [`StateTransition`] schedule, which applies the queued transitions of all states.
     pub fn add_state<S: States>(&mut self) -> &mut Self {
         self.init_resource::<State<S>>()
             .init_resource::<NextState<S>>()
-            .add_systems(
-                StateTransition,
-                (
-                    run_enter_schedule::<S>.run_if(run_once_condition()),
-                    apply_state_transition::<S>,
-                )
-                    .chain(),
-            );
+            .init_resource::<StateStack<S>>()
+            .add_state_transition_systems::<S, _>(apply_next_state::<S>)
+    }
+
+    /// Adds the [`ComputedStates`] `S`, recomputed in [`StateTransition`] whenever one of its
+    /// source states changes.
+    ///
+    /// The source states must be added to the app as well. Within a single pass of [`StateTransition`],
+    /// the [`OnExit`] schedules of `S` run before those of its sources, and its [`OnTransition`] and
+    /// [`OnEnter`] schedules run after those of its sources.
+    ///
+    /// As [`State<S>`] doesn't exist while `S` isn't computed, use
+    /// [`state_exists_and_equals`](bevy_ecs::schedule::common_conditions::state_exists_and_equals)
+    /// rather than [`in_state`] for systems running outside of the computed state.
+    pub fn add_computed_state<S: ComputedStates>(&mut self) -> &mut Self {
+        self.add_state_transition_systems::<S, _>(compute_state::<S>)
+            .edit_schedule(StateTransition, |schedule| {
+                S::SourceStates::configure_dependent::<S>(schedule);
+            })
+    }
+
+    /// Adds the [`SubStates`] `S`, which only exists while its source states have given values.
+    ///
+    /// The source states must be added to the app as well, and transitions are ordered as for
+    /// [`App::add_computed_state`]. While it exists, `S` is changed with [`NextState<S>`].
+    pub fn add_sub_state<S: SubStates>(&mut self) -> &mut Self {
+        self.init_resource::<NextState<S>>()
+            .add_state_transition_systems::<S, _>(update_sub_state::<S>)
+            .edit_schedule(StateTransition, |schedule| {
+                S::SourceStates::configure_dependent::<S>(schedule);
+            })
+    }
+
+    fn add_state_transition_systems<S: States, M>(
+        &mut self,
+        update_state: impl IntoSystemConfigs<M>,
+    ) -> &mut Self {
+        use StateTransitionSteps::*;
 
         // The OnEnter, OnExit, and OnTransition schedules are lazily initialized
         // (i.e. when the first system is added to them), and World::try_run_schedule is used to fail
         // gracefully if they aren't present.
-
-        self
+        self.configure_sets(
+            StateTransition,
+            (
+                UpdateStates,
+                ExitSchedules,
+                TransitionSchedules,
+                EnterSchedules,
+            )
+                .chain(),
+        )
+        .add_systems(
+            StateTransition,
+            (
+                update_state
+                    .in_set(UpdateStates)
+                    .in_set(StateTransitionSet::<S>::new(UpdateStates)),
+                exit_state::<S>
+                    .in_set(ExitSchedules)
+                    .in_set(StateTransitionSet::<S>::new(ExitSchedules)),
+                transition_state::<S>
+                    .in_set(TransitionSchedules)
+                    .in_set(StateTransitionSet::<S>::new(TransitionSchedules)),
+                enter_state::<S>
+                    .in_set(EnterSchedules)
+                    .in_set(StateTransitionSet::<S>::new(EnterSchedules)),
+            ),
+        )
     }
 
     /// Adds a system to the default system set and schedule of the app's [`Schedules`].
@@ -533,6 +665,65 @@ impl App {
         self
     }
 
+    /// Setup the application to manage events of type `T`, kept according to the given
+    /// [`EventRetention`] policy instead of the default two updates.
+    ///
+    /// See [`App::add_event`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy_app::prelude::*;
+    /// # use bevy_ecs::{event::EventRetention, prelude::*};
+    /// #
+    /// # #[derive(Event)]
+    /// # struct MyEvent;
+    /// # let mut app = App::new();
+    /// #
+    /// // `MyEvent`s are kept until every reader has read them
+    /// app.add_event_with_retention::<MyEvent>(EventRetention::UntilConsumed);
+    /// ```
+    pub fn add_event_with_retention<T>(&mut self, retention: EventRetention) -> &mut Self
+    where
+        T: Event,
+    {
+        self.add_event::<T>();
+        self.world
+            .resource_mut::<Events<T>>()
+            .set_retention(retention);
+        self
+    }
+
+    /// Setup the application to index the entities by the value of their component `C`,
+    /// so they can be looked up with the [`Index<C>`](bevy_ecs::index::Index) system parameter.
+    ///
+    /// This is done by adding a [`Resource`] of type [`ComponentIndex::<C>`] kept up to date by the
+    /// hooks of `C`, with [`init_index`], and inserting a [`refresh_index`] system into [`Last`]
+    /// to pick up the components changed in place.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy_app::prelude::*;
+    /// # use bevy_ecs::prelude::*;
+    /// #
+    /// # #[derive(Component, PartialEq, Eq, Hash, Clone)]
+    /// # struct NetworkId(u64);
+    /// # let mut app = App::new();
+    /// #
+    /// app.add_index::<NetworkId>();
+    /// ```
+    pub fn add_index<C>(&mut self) -> &mut Self
+    where
+        C: Component + Eq + Hash + Clone,
+    {
+        if !self.world.contains_resource::<ComponentIndex<C>>() {
+            init_index::<C>(&mut self.world);
+            self.add_systems(Last, refresh_index::<C>);
+        }
+        self
+    }
+
     /// Inserts a [`Resource`] to the current [`App`] and overwrites any [`Resource`] previously added of the same type.
     ///
     /// A [`Resource`] in Bevy represents globally unique data. [`Resource`]s must be added to Bevy apps
@@ -884,6 +1075,38 @@ impl App {
         }
     }
 
+    /// Sets how often the `SubApp` with the given label is updated by [`App::update`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `SubApp` doesn't exist.
+    pub fn set_sub_app_cadence(
+        &mut self,
+        label: impl AppLabel,
+        cadence: UpdateCadence,
+    ) -> &mut Self {
+        let label = label.as_label();
+        match self.sub_apps.get_mut(&label) {
+            Some(sub_app) => sub_app.set_cadence(cadence),
+            None => panic!("Sub-App with label '{:?}' does not exist", label.as_str()),
+        }
+        self
+    }
+
+    /// Extracts data from the main world to the `SubApp` with the given label and runs it right away,
+    /// whatever its [`UpdateCadence`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `SubApp` doesn't exist.
+    pub fn update_sub_app(&mut self, label: impl AppLabel) {
+        let label = label.as_label();
+        match self.sub_apps.get_mut(&label) {
+            Some(sub_app) => sub_app.update(&mut self.world),
+            None => panic!("Sub-App with label '{:?}' does not exist", label.as_str()),
+        }
+    }
+
     /// Inserts an existing sub app into the app
     pub fn insert_sub_app(&mut self, label: impl AppLabel, sub_app: SubApp) {
         self.sub_apps.insert(label.as_label(), sub_app);
@@ -960,6 +1183,65 @@ impl App {
 
         self
     }
+
+    /// Compacts the [`World`] at the end of every `policy.interval` updates, releasing the memory
+    /// its storages keep for despawned entities, and reclaiming its empty archetypes if requested.
+    ///
+    /// This is done by inserting the [`CompactionPolicy`] resource, and a [`compact_world`] system into [`Last`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy_app::prelude::*;
+    /// # use bevy_ecs::world::CompactionPolicy;
+    /// #
+    /// App::new().set_compaction_policy(CompactionPolicy::every(600).reclaiming_empty_archetypes());
+    /// ```
+    pub fn set_compaction_policy(&mut self, policy: CompactionPolicy) -> &mut Self {
+        if !self.world.contains_resource::<CompactionPolicy>() {
+            self.add_systems(Last, compact_world);
+        }
+        self.insert_resource(policy)
+    }
+
+    /// Sets the handler of the errors returned by fallible systems, for the schedules
+    /// that don't have their own. By default, errors panic.
+    ///
+    /// See [`error_handlers`](bevy_ecs::system::error_handlers) for the built-in handlers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy_app::prelude::*;
+    /// # use bevy_ecs::system::{error_handlers, SystemError};
+    /// #
+    /// fn fallible_system() -> Result<(), SystemError> {
+    ///     Err("something went wrong".into())
+    /// }
+    ///
+    /// App::new()
+    ///     .set_error_handler(error_handlers::log)
+    ///     .add_systems(Update, fallible_system)
+    ///     .update();
+    /// ```
+    pub fn set_error_handler(&mut self, error_handler: ErrorHandler) -> &mut Self {
+        self.world
+            .insert_resource(DefaultErrorHandler(error_handler));
+        self
+    }
+
+    /// Sets the handler of the errors returned by the fallible systems of the [`Schedule`] associated with `label`.
+    ///
+    /// **Note:** This will create the schedule if it does not already exist.
+    pub fn set_schedule_error_handler(
+        &mut self,
+        label: impl ScheduleLabel,
+        error_handler: ErrorHandler,
+    ) -> &mut Self {
+        self.edit_schedule(label, |schedule| {
+            schedule.set_error_handler(error_handler);
+        })
+    }
 }
 
 fn run_once(mut app: App) {
@@ -989,11 +1271,14 @@ pub struct AppExit;
 #[cfg(test)]
 mod tests {
     use bevy_ecs::{
-        schedule::{OnEnter, States},
-        system::Commands,
+        schedule::{
+            common_conditions::in_state_stack, ComputedStates, IntoSystemConfigs, NextState,
+            OnEnter, OnExit, OnPause, OnResume, OnTransition, State, StateStack, States, SubStates,
+        },
+        system::{Commands, ResMut, Resource, SystemError},
     };
 
-    use crate::{App, Plugin};
+    use crate::{App, Plugin, PreUpdate, Update};
 
     struct PluginA;
     impl Plugin for PluginA {
@@ -1084,4 +1369,307 @@ mod tests {
         app.world.run_schedule(OnEnter(AppState::MainMenu));
         assert_eq!(app.world.entities().len(), 2);
     }
+
+    #[derive(States, PartialEq, Eq, Debug, Default, Hash, Clone, Copy)]
+    enum GameState {
+        #[default]
+        Menu,
+        Playing,
+        Paused,
+    }
+
+    #[derive(States, PartialEq, Eq, Debug, Default, Hash, Clone, Copy)]
+    enum InGame {
+        #[default]
+        Running,
+    }
+
+    impl ComputedStates for InGame {
+        type SourceStates = GameState;
+
+        fn compute(state: GameState) -> Option<Self> {
+            (state != GameState::Menu).then_some(InGame::Running)
+        }
+    }
+
+    #[derive(States, PartialEq, Eq, Debug, Default, Hash, Clone, Copy)]
+    enum MenuScreen {
+        #[default]
+        Main,
+        Settings,
+    }
+
+    impl SubStates for MenuScreen {
+        type SourceStates = GameState;
+
+        fn should_exist(state: GameState) -> bool {
+            state == GameState::Menu
+        }
+    }
+
+    #[derive(Resource, Default)]
+    struct Transitions(Vec<&'static str>);
+
+    fn log_transition(transition: &'static str) -> impl FnMut(ResMut<Transitions>) {
+        move |mut transitions: ResMut<Transitions>| transitions.0.push(transition)
+    }
+
+    fn take_transitions(app: &mut App) -> Vec<&'static str> {
+        std::mem::take(&mut app.world.resource_mut::<Transitions>().0)
+    }
+
+    fn state_app() -> App {
+        let mut app = App::new();
+        app.init_resource::<Transitions>()
+            .add_state::<GameState>()
+            .add_systems(OnEnter(GameState::Menu), log_transition("enter menu"))
+            .add_systems(OnExit(GameState::Menu), log_transition("exit menu"))
+            .add_systems(OnEnter(GameState::Playing), log_transition("enter playing"))
+            .add_systems(OnExit(GameState::Playing), log_transition("exit playing"))
+            .add_systems(OnEnter(GameState::Paused), log_transition("enter paused"))
+            .add_systems(OnExit(GameState::Paused), log_transition("exit paused"));
+        app
+    }
+
+    fn set_state<S: States>(app: &mut App, state: S) {
+        app.world.resource_mut::<NextState<S>>().set(state);
+        app.update();
+    }
+
+    #[test]
+    fn computed_state() {
+        let mut app = state_app();
+        app.add_computed_state::<InGame>()
+            .add_systems(OnEnter(InGame::Running), log_transition("enter in game"))
+            .add_systems(OnExit(InGame::Running), log_transition("exit in game"))
+            .add_systems(
+                OnTransition {
+                    from: GameState::Menu,
+                    to: GameState::Playing,
+                },
+                log_transition("menu to playing"),
+            );
+
+        app.update();
+        assert_eq!(take_transitions(&mut app), vec!["enter menu"]);
+        assert!(!app.world.contains_resource::<State<InGame>>());
+
+        set_state(&mut app, GameState::Playing);
+        assert_eq!(
+            take_transitions(&mut app),
+            vec![
+                "exit menu",
+                "menu to playing",
+                "enter playing",
+                "enter in game"
+            ]
+        );
+        assert_eq!(*app.world.resource::<State<InGame>>(), InGame::Running);
+
+        set_state(&mut app, GameState::Paused);
+        assert_eq!(
+            take_transitions(&mut app),
+            vec!["exit playing", "enter paused"]
+        );
+
+        set_state(&mut app, GameState::Menu);
+        assert_eq!(
+            take_transitions(&mut app),
+            vec!["exit in game", "exit paused", "enter menu"]
+        );
+        assert!(!app.world.contains_resource::<State<InGame>>());
+    }
+
+    #[test]
+    fn initial_state_entered_before_queued_state() {
+        let mut app = state_app();
+
+        set_state(&mut app, GameState::Playing);
+        assert_eq!(
+            take_transitions(&mut app),
+            vec!["enter menu", "exit menu", "enter playing"]
+        );
+        assert_eq!(
+            *app.world.resource::<State<GameState>>(),
+            GameState::Playing
+        );
+    }
+
+    #[test]
+    fn sub_state() {
+        let mut app = state_app();
+        app.add_sub_state::<MenuScreen>()
+            .add_systems(OnEnter(MenuScreen::Main), log_transition("enter main"))
+            .add_systems(OnExit(MenuScreen::Main), log_transition("exit main"))
+            .add_systems(
+                OnEnter(MenuScreen::Settings),
+                log_transition("enter settings"),
+            )
+            .add_systems(
+                OnExit(MenuScreen::Settings),
+                log_transition("exit settings"),
+            );
+
+        app.update();
+        assert_eq!(take_transitions(&mut app), vec!["enter menu", "enter main"]);
+
+        set_state(&mut app, MenuScreen::Settings);
+        assert_eq!(
+            take_transitions(&mut app),
+            vec!["exit main", "enter settings"]
+        );
+
+        set_state(&mut app, GameState::Playing);
+        assert_eq!(
+            take_transitions(&mut app),
+            vec!["exit settings", "exit menu", "enter playing"]
+        );
+        assert!(!app.world.contains_resource::<State<MenuScreen>>());
+
+        // queued while the sub-state doesn't exist
+        set_state(&mut app, MenuScreen::Settings);
+        assert!(take_transitions(&mut app).is_empty());
+
+        set_state(&mut app, GameState::Menu);
+        assert_eq!(
+            take_transitions(&mut app),
+            vec!["exit playing", "enter menu", "enter main"]
+        );
+        assert_eq!(*app.world.resource::<State<MenuScreen>>(), MenuScreen::Main);
+    }
+
+    #[test]
+    fn state_stack() {
+        #[derive(Resource, Default)]
+        struct PlayingTicks(usize);
+
+        let mut app = state_app();
+        app.init_resource::<PlayingTicks>()
+            .add_systems(OnPause(GameState::Playing), log_transition("pause playing"))
+            .add_systems(
+                OnResume(GameState::Playing),
+                log_transition("resume playing"),
+            )
+            .add_systems(
+                Update,
+                (|mut ticks: ResMut<PlayingTicks>| ticks.0 += 1)
+                    .run_if(in_state_stack(GameState::Playing)),
+            );
+
+        app.update();
+        set_state(&mut app, GameState::Playing);
+        take_transitions(&mut app);
+
+        app.world
+            .resource_mut::<StateStack<GameState>>()
+            .push(GameState::Paused);
+        app.update();
+        assert_eq!(
+            take_transitions(&mut app),
+            vec!["pause playing", "enter paused"]
+        );
+        assert_eq!(*app.world.resource::<State<GameState>>(), GameState::Paused);
+        assert_eq!(
+            app.world.resource::<StateStack<GameState>>().covered(),
+            &[GameState::Playing]
+        );
+        assert_eq!(app.world.resource::<PlayingTicks>().0, 2);
+
+        app.world.resource_mut::<StateStack<GameState>>().pop();
+        app.update();
+        assert_eq!(
+            take_transitions(&mut app),
+            vec!["exit paused", "resume playing"]
+        );
+        assert_eq!(
+            *app.world.resource::<State<GameState>>(),
+            GameState::Playing
+        );
+        assert!(app
+            .world
+            .resource::<StateStack<GameState>>()
+            .covered()
+            .is_empty());
+
+        // nothing to return to
+        app.world.resource_mut::<StateStack<GameState>>().pop();
+        app.update();
+        assert!(take_transitions(&mut app).is_empty());
+        assert_eq!(
+            *app.world.resource::<State<GameState>>(),
+            GameState::Playing
+        );
+
+        // operations are applied in order, one per update, and then the state queued in `NextState`
+        let mut stack = app.world.resource_mut::<StateStack<GameState>>();
+        stack.push(GameState::Paused);
+        stack.push(GameState::Menu);
+        set_state(&mut app, GameState::Playing);
+        assert_eq!(
+            take_transitions(&mut app),
+            vec!["pause playing", "enter paused"]
+        );
+        app.update();
+        // the paused state is covered, not exited
+        assert_eq!(take_transitions(&mut app), vec!["enter menu"]);
+        assert_eq!(
+            app.world.resource::<StateStack<GameState>>().covered(),
+            &[GameState::Playing, GameState::Paused]
+        );
+        app.update();
+        assert_eq!(
+            take_transitions(&mut app),
+            vec!["exit menu", "enter playing"]
+        );
+        assert_eq!(
+            app.world.resource::<StateStack<GameState>>().covered(),
+            &[GameState::Playing, GameState::Paused]
+        );
+    }
+
+    #[derive(Resource, Default)]
+    struct Errors(Vec<&'static str>);
+
+    fn fail() -> Result<(), SystemError> {
+        Err("failed".into())
+    }
+
+    #[test]
+    fn error_handlers() {
+        let mut app = App::new();
+        app.init_resource::<Errors>()
+            .set_error_handler(|_, context| context.world.resource_mut::<Errors>().0.push("app"))
+            .set_schedule_error_handler(Update, |_, context| {
+                context.world.resource_mut::<Errors>().0.push("update");
+            })
+            .add_systems(PreUpdate, fail)
+            .add_systems(Update, fail);
+
+        app.update();
+        assert_eq!(app.world.resource::<Errors>().0, vec!["app", "update"]);
+    }
+
+    #[test]
+    fn add_index() {
+        use bevy_ecs::{component::Component, index::ComponentIndex};
+
+        #[derive(Component, PartialEq, Eq, Hash, Clone)]
+        struct NetworkId(u64);
+
+        let mut app = App::new();
+        app.add_index::<NetworkId>();
+        let entity = app.world.spawn(NetworkId(1)).id();
+        app.update();
+        assert_eq!(
+            app.world
+                .resource::<ComponentIndex<NetworkId>>()
+                .value(entity)
+                .map(|id| id.0),
+            Some(1)
+        );
+
+        app.world.despawn(entity);
+        app.update();
+        assert!(app.world.resource::<ComponentIndex<NetworkId>>().is_empty());
+    }
 }
@@ -0,0 +1,253 @@
+use bevy_ecs::system::Resource;
+use crossbeam_channel::{Receiver, Sender};
+
+use crate::{App, AppLabel};
+
+/// A [`Resource`] sending values of type `T` from the world of an [`App`] to another app,
+/// where they are received by its [`AppReceiver<T>`].
+///
+/// Channels between the main app and its sub apps are added with [`App::add_channel_to_sub_app`]
+/// and [`App::add_channel_from_sub_app`]. They are unbounded and don't depend on the update of
+/// the apps, so a sub app updated at a different [`UpdateCadence`](crate::UpdateCadence), or
+/// removed with [`App::remove_sub_app`] to be updated on another thread, keeps communicating
+/// with the main app.
+///
+/// ```
+/// # use bevy_app::prelude::*;
+/// # use bevy_app::{AppReceiver, AppSender};
+/// # use bevy_ecs::prelude::*;
+/// struct Readback(Vec<u8>);
+///
+/// fn send_readback(sender: Res<AppSender<Readback>>) {
+///     sender.send(Readback(vec![0; 4]));
+/// }
+///
+/// fn receive_readback(receiver: Res<AppReceiver<Readback>>) {
+///     for Readback(bytes) in receiver.try_iter() {
+///         println!("Received {} bytes", bytes.len());
+///     }
+/// }
+/// # bevy_ecs::system::assert_is_system(send_readback);
+/// # bevy_ecs::system::assert_is_system(receive_readback);
+/// ```
+#[derive(Resource)]
+pub struct AppSender<T: Send + 'static>(Sender<T>);
+
+impl<T: Send + 'static> AppSender<T> {
+    /// Sends `value` to the receiving app. The value is dropped if the [`AppReceiver<T>`] was dropped.
+    pub fn send(&self, value: T) {
+        // the receiving app may have been dropped, in which case nobody expects the value anymore
+        let _ = self.0.send(value);
+    }
+}
+
+impl<T: Send + 'static> Clone for AppSender<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+/// A [`Resource`] receiving the values of type `T` sent by the [`AppSender<T>`]s of other apps.
+///
+/// See [`AppSender`] for more details.
+#[derive(Resource)]
+pub struct AppReceiver<T: Send + 'static> {
+    receiver: Receiver<T>,
+    // kept to connect more apps to this receiver
+    sender: Sender<T>,
+}
+
+impl<T: Send + 'static> AppReceiver<T> {
+    /// Returns the next value sent to this app, or `None` if there isn't any.
+    pub fn try_recv(&self) -> Option<T> {
+        self.receiver.try_recv().ok()
+    }
+
+    /// Returns an iterator over the values sent to this app, which stops when there isn't any left.
+    pub fn try_iter(&self) -> impl Iterator<Item = T> + '_ {
+        self.receiver.try_iter()
+    }
+
+    /// Returns the number of values waiting to be received.
+    pub fn len(&self) -> usize {
+        self.receiver.len()
+    }
+
+    /// Returns `true` if no value is waiting to be received.
+    pub fn is_empty(&self) -> bool {
+        self.receiver.is_empty()
+    }
+
+    /// Returns a new [`AppSender<T>`] sending values to this receiver.
+    pub fn sender(&self) -> AppSender<T> {
+        AppSender(self.sender.clone())
+    }
+}
+
+/// Creates a channel, returning its sending and receiving halves.
+pub fn app_channel<T: Send + 'static>() -> (AppSender<T>, AppReceiver<T>) {
+    let (sender, receiver) = crossbeam_channel::unbounded();
+    (AppSender(sender.clone()), AppReceiver { receiver, sender })
+}
+
+impl App {
+    /// Adds a channel sending values of type `T` from this app to the sub app with the given label,
+    /// by inserting an [`AppSender<T>`] resource in this app and an [`AppReceiver<T>`] resource in
+    /// the sub app.
+    ///
+    /// If the sub app already has an [`AppReceiver<T>`], this app sends to it. Otherwise, any
+    /// [`AppSender<T>`] of this app is replaced.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the sub app doesn't exist.
+    pub fn add_channel_to_sub_app<T: Send + 'static>(&mut self, label: impl AppLabel) -> &mut Self {
+        let sender = connect::<T>(self.sub_app_mut(label));
+        self.insert_resource(sender)
+    }
+
+    /// Adds a channel sending values of type `T` from the sub app with the given label to this app,
+    /// by inserting an [`AppSender<T>`] resource in the sub app and an [`AppReceiver<T>`] resource
+    /// in this app.
+    ///
+    /// All the sub apps sending values of type `T` to this app share its [`AppReceiver<T>`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the sub app doesn't exist.
+    pub fn add_channel_from_sub_app<T: Send + 'static>(
+        &mut self,
+        label: impl AppLabel,
+    ) -> &mut Self {
+        let sender = connect::<T>(self);
+        self.sub_app_mut(label).insert_resource(sender);
+        self
+    }
+}
+
+/// Returns a sender to the [`AppReceiver<T>`] of `receiving_app`, which is inserted if it doesn't exist.
+fn connect<T: Send + 'static>(receiving_app: &mut App) -> AppSender<T> {
+    if let Some(receiver) = receiving_app.world.get_resource::<AppReceiver<T>>() {
+        return receiver.sender();
+    }
+    let (sender, receiver) = app_channel::<T>();
+    receiving_app.insert_resource(receiver);
+    sender
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::prelude::*;
+
+    use crate::{
+        self as bevy_app, App, AppLabel, AppReceiver, AppSender, Main, SubApp, UpdateCadence,
+    };
+
+    #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, AppLabel)]
+    struct Simulation;
+
+    #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, AppLabel)]
+    struct Readback;
+
+    struct Input(u32);
+
+    #[derive(Debug, PartialEq)]
+    struct Output(u32);
+
+    fn simulate(input: Res<AppReceiver<Input>>, output: Res<AppSender<Output>>) {
+        for Input(value) in input.try_iter() {
+            output.send(Output(value * 2));
+        }
+    }
+
+    fn sub_app(cadence: UpdateCadence) -> SubApp {
+        let mut sub_app = App::empty();
+        sub_app.add_systems(Main, simulate);
+        SubApp::new(sub_app, |_, _| {}).with_cadence(cadence)
+    }
+
+    fn main_app() -> App {
+        let mut app = App::empty();
+        app.init_schedule(Main);
+        app
+    }
+
+    fn received(app: &App) -> Vec<Output> {
+        app.world
+            .resource::<AppReceiver<Output>>()
+            .try_iter()
+            .collect()
+    }
+
+    #[test]
+    fn channels_between_apps() {
+        let mut app = main_app();
+        app.insert_sub_app(Simulation, sub_app(UpdateCadence::EveryUpdate));
+        let mut readback = App::empty();
+        readback.init_schedule(Main);
+        app.insert_sub_app(Readback, SubApp::new(readback, |_, _| {}));
+        app.add_channel_to_sub_app::<Input>(Simulation)
+            .add_channel_from_sub_app::<Output>(Simulation)
+            .add_channel_from_sub_app::<Output>(Readback);
+
+        app.world.resource::<AppSender<Input>>().send(Input(1));
+        app.world.resource::<AppSender<Input>>().send(Input(2));
+        app.update();
+        assert_eq!(received(&app), vec![Output(2), Output(4)]);
+
+        // both sub apps send to the same receiver of the main app
+        app.sub_app(Readback)
+            .world
+            .resource::<AppSender<Output>>()
+            .send(Output(0));
+        assert_eq!(received(&app), vec![Output(0)]);
+    }
+
+    #[test]
+    fn channels_across_threads() {
+        let mut app = main_app();
+        app.insert_sub_app(Simulation, sub_app(UpdateCadence::Manual));
+        app.add_channel_to_sub_app::<Input>(Simulation)
+            .add_channel_from_sub_app::<Output>(Simulation);
+
+        let mut sub_app = app.remove_sub_app(Simulation).unwrap();
+        app.world.resource::<AppSender<Input>>().send(Input(3));
+        std::thread::spawn(move || sub_app.run()).join().unwrap();
+
+        assert_eq!(received(&app), vec![Output(6)]);
+    }
+
+    #[test]
+    fn update_cadence() {
+        #[derive(Resource, Default)]
+        struct Runs(u32);
+
+        fn runs(app: &App, label: impl AppLabel) -> u32 {
+            app.sub_app(label).world.resource::<Runs>().0
+        }
+
+        fn counting(cadence: UpdateCadence) -> SubApp {
+            let mut sub_app = App::empty();
+            sub_app
+                .init_resource::<Runs>()
+                .add_systems(Main, |mut runs: ResMut<Runs>| runs.0 += 1);
+            SubApp::new(sub_app, |_, _| {}).with_cadence(cadence)
+        }
+
+        let mut app = main_app();
+        app.insert_sub_app(Simulation, counting(UpdateCadence::EveryNthUpdate(3)));
+        app.insert_sub_app(Readback, counting(UpdateCadence::Manual));
+
+        for _ in 0..7 {
+            app.update();
+        }
+        assert_eq!(runs(&app, Simulation), 2);
+        assert_eq!(runs(&app, Readback), 0);
+
+        app.update_sub_app(Readback);
+        app.set_sub_app_cadence(Simulation, UpdateCadence::EveryUpdate);
+        app.update();
+        assert_eq!(runs(&app, Simulation), 3);
+        assert_eq!(runs(&app, Readback), 1);
+    }
+}
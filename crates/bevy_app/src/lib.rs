@@ -4,6 +4,7 @@
 #![allow(clippy::type_complexity)]
 
 mod app;
+mod channel;
 mod main_schedule;
 mod plugin;
 mod plugin_group;
@@ -11,9 +12,11 @@ mod schedule_runner;
 
 #[cfg(feature = "bevy_ci_testing")]
 pub mod ci_testing;
+pub mod testing;
 
 pub use app::*;
 pub use bevy_derive::DynamicPlugin;
+pub use channel::*;
 pub use main_schedule::*;
 pub use plugin::*;
 pub use plugin_group::*;
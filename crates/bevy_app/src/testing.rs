@@ -0,0 +1,342 @@
+//! A harness for integration tests, running an [`App`] frame by frame without a runner.
+
+use std::{
+    fmt::{Debug, Write},
+    ops::{Deref, DerefMut},
+};
+
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    event::Event,
+    system::Resource,
+    world::{Mut, World},
+};
+
+use crate::App;
+
+/// An [`App`] driven by a test, one frame at a time.
+///
+/// The app is never passed to its [runner](App::set_runner), so it runs headless as long as
+/// no windowing plugin is added. Events, such as input events, can be injected with
+/// [`TestApp::send_event`] before advancing frames, and the assertions of this type report the
+/// differences between the expected and actual values when they fail.
+///
+/// [`TestApp`] dereferences to [`App`], to configure the app further.
+///
+/// ```
+/// # use bevy_app::{prelude::*, testing::TestApp};
+/// # use bevy_ecs::prelude::*;
+/// #[derive(Resource, Default, Debug, PartialEq)]
+/// struct Score(u32);
+///
+/// #[derive(Event)]
+/// struct Goal;
+///
+/// fn count_goals(mut goals: EventReader<Goal>, mut score: ResMut<Score>) {
+///     score.0 += goals.iter().count() as u32;
+/// }
+///
+/// let mut app = App::new();
+/// app.init_resource::<Score>()
+///     .add_event::<Goal>()
+///     .add_systems(Update, count_goals);
+///
+/// let mut app = TestApp::new(app);
+/// app.send_event(Goal).send_event(Goal).update();
+/// app.assert_resource_eq(&Score(2));
+/// ```
+pub struct TestApp {
+    app: App,
+    frame: u32,
+}
+
+impl TestApp {
+    /// Creates a test harness for `app`, once all its plugins are [ready](App::ready) and finished.
+    pub fn new(mut app: App) -> Self {
+        while !app.ready() {
+            #[cfg(not(target_arch = "wasm32"))]
+            bevy_tasks::tick_global_task_pools_on_main_thread();
+        }
+        app.finish();
+        app.cleanup();
+        Self { app, frame: 0 }
+    }
+
+    /// Returns the number of frames the app advanced since the harness was created.
+    pub fn frame(&self) -> u32 {
+        self.frame
+    }
+
+    /// Advances the app by one frame.
+    pub fn update(&mut self) -> &mut Self {
+        self.app.update();
+        self.frame += 1;
+        self
+    }
+
+    /// Advances the app by `frames` frames.
+    pub fn update_frames(&mut self, frames: u32) -> &mut Self {
+        for _ in 0..frames {
+            self.update();
+        }
+        self
+    }
+
+    /// Advances the app one frame at a time until `condition` returns `true` after a frame,
+    /// and returns the number of frames it took.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `condition` still returns `false` after `max_frames` frames.
+    #[track_caller]
+    pub fn update_until(
+        &mut self,
+        max_frames: u32,
+        mut condition: impl FnMut(&mut World) -> bool,
+    ) -> u32 {
+        for frames in 1..=max_frames {
+            self.update();
+            if condition(&mut self.app.world) {
+                return frames;
+            }
+        }
+        panic!("condition still doesn't hold after {max_frames} frames");
+    }
+
+    /// Sends `event` to the app, to be read during the next frames.
+    ///
+    /// The event must have been added to the app with [`App::add_event`].
+    pub fn send_event<E: Event>(&mut self, event: E) -> &mut Self {
+        self.app.world.send_event(event);
+        self
+    }
+
+    /// Gives mutable access to the resource `R`, for example to set up the next frame.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the resource doesn't exist.
+    #[track_caller]
+    pub fn resource_mut<R: Resource>(&mut self) -> Mut<'_, R> {
+        self.app.world.resource_mut::<R>()
+    }
+
+    /// Asserts that the resource `R` is equal to `expected`.
+    ///
+    /// # Panics
+    ///
+    /// Panics with the differences between both values if they aren't equal, or if the resource doesn't exist.
+    #[track_caller]
+    pub fn assert_resource_eq<R: Resource + PartialEq + Debug>(&self, expected: &R) {
+        let Some(actual) = self.app.world.get_resource::<R>() else {
+            panic!("resource `{}` doesn't exist", std::any::type_name::<R>());
+        };
+        assert_eq_diff(
+            &format!("resource `{}`", std::any::type_name::<R>()),
+            expected,
+            actual,
+        );
+    }
+
+    /// Asserts that the component `C` of `entity` is equal to `expected`.
+    ///
+    /// # Panics
+    ///
+    /// Panics with the differences between both values if they aren't equal, or if the entity
+    /// doesn't have the component.
+    #[track_caller]
+    pub fn assert_component_eq<C: Component + PartialEq + Debug>(
+        &self,
+        entity: Entity,
+        expected: &C,
+    ) {
+        let Some(actual) = self.app.world.get::<C>(entity) else {
+            panic!(
+                "entity {entity:?} doesn't have a component `{}`",
+                std::any::type_name::<C>()
+            );
+        };
+        assert_eq_diff(
+            &format!("component `{}` of {entity:?}", std::any::type_name::<C>()),
+            expected,
+            actual,
+        );
+    }
+
+    /// Asserts that the components `C` of all entities, in the order of the entities'
+    /// [index](Entity::index), are equal to `expected`.
+    ///
+    /// # Panics
+    ///
+    /// Panics with the differences between both lists if they aren't equal.
+    #[track_caller]
+    pub fn assert_components_eq<C: Component + PartialEq + Debug>(&self, expected: &[C]) {
+        let actual: Vec<&C> = self
+            .app
+            .world
+            .iter_entities_ordered()
+            .filter_map(|entity| entity.get::<C>())
+            .collect();
+        let expected: Vec<&C> = expected.iter().collect();
+        assert_eq_diff(
+            &format!("components `{}`", std::any::type_name::<C>()),
+            &expected,
+            &actual,
+        );
+    }
+
+    /// Returns the app, to run it outside of the harness.
+    pub fn into_app(self) -> App {
+        self.app
+    }
+}
+
+impl Deref for TestApp {
+    type Target = App;
+
+    fn deref(&self) -> &Self::Target {
+        &self.app
+    }
+}
+
+impl DerefMut for TestApp {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.app
+    }
+}
+
+/// Asserts that `actual` is equal to `expected`, and panics with a line by line diff of their
+/// pretty-printed [`Debug`] representations if they aren't.
+///
+/// `what` describes the compared values in the panic message.
+#[track_caller]
+pub fn assert_eq_diff<T: PartialEq + Debug + ?Sized>(what: &str, expected: &T, actual: &T) {
+    if expected != actual {
+        panic!(
+            "{what} differs from the expected value (- expected, + actual):\n{}",
+            diff(&format!("{expected:#?}"), &format!("{actual:#?}"))
+        );
+    }
+}
+
+/// Returns a line by line diff from `expected` to `actual`, based on their longest common
+/// subsequence of lines.
+fn diff(expected: &str, actual: &str) -> String {
+    let expected: Vec<&str> = expected.lines().collect();
+    let actual: Vec<&str> = actual.lines().collect();
+
+    // common[i][j] is the length of the longest common subsequence of expected[i..] and actual[j..]
+    let mut common = vec![vec![0usize; actual.len() + 1]; expected.len() + 1];
+    for i in (0..expected.len()).rev() {
+        for j in (0..actual.len()).rev() {
+            common[i][j] = if expected[i] == actual[j] {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+
+    let mut diff = String::new();
+    let (mut i, mut j) = (0, 0);
+    while i < expected.len() || j < actual.len() {
+        let (sign, line) = if i < expected.len() && j < actual.len() && expected[i] == actual[j] {
+            i += 1;
+            j += 1;
+            (' ', expected[i - 1])
+        } else if i < expected.len() && (j == actual.len() || common[i + 1][j] >= common[i][j + 1])
+        {
+            i += 1;
+            ('-', expected[i - 1])
+        } else {
+            j += 1;
+            ('+', actual[j - 1])
+        };
+        let _ = writeln!(diff, "{sign} {line}");
+    }
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::prelude::*;
+
+    use super::{diff, TestApp};
+    use crate::{App, Update};
+
+    #[derive(Resource, Default, Debug, PartialEq)]
+    struct Counter(u32);
+
+    #[derive(Component, Debug, PartialEq)]
+    struct Health(u32);
+
+    #[derive(Event)]
+    struct Hit(Entity);
+
+    fn test_app() -> TestApp {
+        let mut app = App::new();
+        app.init_resource::<Counter>()
+            .add_event::<Hit>()
+            .add_systems(
+                Update,
+                |mut counter: ResMut<Counter>,
+                 mut hits: EventReader<Hit>,
+                 mut health: Query<&mut Health>| {
+                    counter.0 += 1;
+                    for Hit(entity) in hits.iter() {
+                        health.get_mut(*entity).unwrap().0 -= 10;
+                    }
+                },
+            );
+        TestApp::new(app)
+    }
+
+    #[test]
+    fn step_frames() {
+        let mut app = test_app();
+        app.update_frames(3);
+        assert_eq!(app.frame(), 3);
+        app.assert_resource_eq(&Counter(3));
+
+        let frames = app.update_until(10, |world| world.resource::<Counter>().0 == 5);
+        assert_eq!(frames, 2);
+        assert_eq!(app.frame(), 5);
+    }
+
+    #[test]
+    #[should_panic(expected = "condition still doesn't hold after 2 frames")]
+    fn update_until_timeout() {
+        test_app().update_until(2, |_| false);
+    }
+
+    #[test]
+    fn inject_events() {
+        let mut app = test_app();
+        let a = app.world.spawn(Health(100)).id();
+        let b = app.world.spawn(Health(100)).id();
+
+        app.send_event(Hit(a)).send_event(Hit(b)).send_event(Hit(a));
+        app.update();
+
+        app.assert_component_eq(a, &Health(80));
+        app.assert_components_eq(&[Health(80), Health(90)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "-         100,\n+         90,\n")]
+    fn assertion_diff() {
+        let mut app = test_app();
+        let a = app.world.spawn(Health(100)).id();
+        app.world.spawn(Health(90));
+        app.send_event(Hit(a)).update();
+
+        app.assert_components_eq(&[Health(100), Health(90)]);
+    }
+
+    #[test]
+    fn line_diff() {
+        assert_eq!(diff("a\nb\nc", "a\nc\nd"), "  a\n- b\n  c\n+ d\n");
+        assert_eq!(diff("", "a"), "+ a\n");
+    }
+}
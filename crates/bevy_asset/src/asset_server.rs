@@ -1,17 +1,24 @@
 use crate::{
+    embedded::embedded_path,
+    meta::{meta_path, Meta},
     path::{AssetPath, AssetPathId, SourcePathId},
     Asset, AssetIo, AssetIoError, AssetLifecycle, AssetLifecycleChannel, AssetLifecycleEvent,
-    AssetLoader, Assets, Handle, HandleId, HandleUntyped, LabelId, LoadContext, LoadState,
-    RefChange, RefChangeChannel, SourceInfo, SourceMeta,
+    AssetLoader, AssetProcessor, Assets, Handle, HandleId, HandleUntyped, LabelId, LoadContext,
+    LoadState, MemoryAssetIo, RefChange, RefChangeChannel, SaveRequest, SourceInfo, SourceMeta,
+    EMBEDDED_SOURCE,
 };
 use anyhow::Result;
 use bevy_ecs::system::{Res, ResMut, Resource};
 use bevy_log::warn;
 use bevy_tasks::IoTaskPool;
-use bevy_utils::{Entry, HashMap, Uuid};
+use bevy_utils::{Entry, HashMap, Instant, Uuid};
 use crossbeam_channel::TryRecvError;
 use parking_lot::{Mutex, RwLock};
-use std::{path::Path, sync::Arc};
+use std::{
+    any::Any,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 use thiserror::Error;
 
 /// Errors that occur while loading assets with an [`AssetServer`].
@@ -30,6 +37,28 @@ pub enum AssetServerError {
         extensions: Vec<String>,
     },
 
+    /// No asset loader with the name given by the `.meta` file of the asset was found.
+    #[error("no `AssetLoader` named `{0}`")]
+    MissingNamedAssetLoader(String),
+
+    /// The `.meta` file of the asset is not valid.
+    #[error("invalid meta file {path}: {error}")]
+    InvalidMetaFile {
+        /// The path of the `.meta` file.
+        path: PathBuf,
+        /// The error encountered while deserializing the `.meta` file.
+        error: ron::error::SpannedError,
+    },
+
+    /// No asset saver was found for the type of the asset and the specified extensions.
+    #[error("no `AssetSaver` found{}", format_missing_asset_ext(.extensions))]
+    MissingAssetSaver {
+        /// The list of extensions detected on the path the asset is saved to.
+        ///
+        /// The list may be empty if the path is invalid or doesn't have an extension.
+        extensions: Vec<String>,
+    },
+
     /// The handle type does not match the type of the loaded asset.
     #[error("the given type does not match the type of the loaded asset")]
     IncorrectHandleType,
@@ -38,9 +67,21 @@ pub enum AssetServerError {
     #[error("encountered an error while loading an asset: {0}")]
     AssetLoaderError(anyhow::Error),
 
+    /// Encountered an error while saving an asset with an [`AssetSaver`](crate::AssetSaver).
+    #[error("encountered an error while saving an asset: {0}")]
+    AssetSaverError(anyhow::Error),
+
     /// Encountered an error while reading an asset from disk.
     #[error("encountered an error while reading an asset: {0}")]
     AssetIoError(#[from] AssetIoError),
+
+    /// Encountered an error while processing an asset with an [`AssetProcessor`](crate::AssetProcessor).
+    #[error("encountered an error while processing an asset: {0}")]
+    AssetProcessorError(anyhow::Error),
+
+    /// Assets can't be processed before setting the processed asset I/O.
+    #[error("no processed asset io was set")]
+    MissingProcessedAssetIo,
 }
 
 fn format_missing_asset_ext(exts: &[String]) -> String {
@@ -67,12 +108,20 @@ pub(crate) struct AssetRefCounter {
 /// [`AssetServer`] is the public API for interacting with the asset server.
 pub struct AssetServerInternal {
     pub(crate) asset_io: Box<dyn AssetIo>,
+    pub(crate) embedded_assets: MemoryAssetIo,
     pub(crate) asset_ref_counter: AssetRefCounter,
     pub(crate) asset_sources: Arc<RwLock<HashMap<SourcePathId, SourceInfo>>>,
     pub(crate) asset_lifecycles: Arc<RwLock<HashMap<Uuid, Box<dyn AssetLifecycle>>>>,
     loaders: RwLock<Vec<Arc<dyn AssetLoader>>>,
     extension_to_loader_index: RwLock<HashMap<String, usize>>,
-    handle_to_path: Arc<RwLock<HashMap<HandleId, AssetPath<'static>>>>,
+    name_to_loader_index: RwLock<HashMap<&'static str, usize>>,
+    pub(crate) settings_overrides: RwLock<HashMap<SourcePathId, Arc<dyn Any + Send + Sync>>>,
+    pub(crate) savers: RwLock<HashMap<(Uuid, String), Box<dyn Any + Send + Sync>>>,
+    pub(crate) save_requests: Mutex<HashMap<Uuid, Vec<SaveRequest>>>,
+    pub(crate) saved_paths: Mutex<HashMap<PathBuf, Instant>>,
+    pub(crate) processors: RwLock<HashMap<String, Arc<dyn AssetProcessor>>>,
+    pub(crate) processed_asset_io: RwLock<Option<Arc<dyn AssetIo>>>,
+    pub(crate) handle_to_path: Arc<RwLock<HashMap<HandleId, AssetPath<'static>>>>,
 }
 
 /// Loads assets from the filesystem in the background.
@@ -129,6 +178,14 @@ impl AssetServer {
             server: Arc::new(AssetServerInternal {
                 loaders: Default::default(),
                 extension_to_loader_index: Default::default(),
+                name_to_loader_index: Default::default(),
+                settings_overrides: Default::default(),
+                savers: Default::default(),
+                save_requests: Default::default(),
+                saved_paths: Default::default(),
+                processors: Default::default(),
+                processed_asset_io: Default::default(),
+                embedded_assets: Default::default(),
                 asset_sources: Default::default(),
                 asset_ref_counter: Default::default(),
                 handle_to_path: Default::default(),
@@ -161,12 +218,19 @@ impl AssetServer {
     ///
     /// If `loader` has one or more supported extensions in conflict with loaders that came before
     /// it, it will replace them.
+    ///
+    /// The `.meta` file of an asset can choose the loader by its type name, for example
+    /// `bevy_render::texture::image_texture_loader::ImageTextureLoader`.
     pub fn add_loader<T>(&self, loader: T)
     where
         T: AssetLoader,
     {
         let mut loaders = self.server.loaders.write();
         let loader_index = loaders.len();
+        self.server
+            .name_to_loader_index
+            .write()
+            .insert(std::any::type_name::<T>(), loader_index);
         for extension in loader.extensions() {
             self.server
                 .extension_to_loader_index
@@ -201,6 +265,13 @@ impl AssetServer {
             })
     }
 
+    fn get_named_asset_loader(&self, name: &str) -> Result<Arc<dyn AssetLoader>, AssetServerError> {
+        let index = self.server.name_to_loader_index.read().get(name).copied();
+        index
+            .map(|index| self.server.loaders.read()[index].clone())
+            .ok_or_else(|| AssetServerError::MissingNamedAssetLoader(name.to_string()))
+    }
+
     fn get_path_asset_loader<P: AsRef<Path>>(
         &self,
         path: P,
@@ -305,7 +376,29 @@ impl AssetServer {
         self.load_untyped(path).typed()
     }
 
-    async fn load_async(
+    /// Queues the [`Asset`] at the provided path for loading with the given loader settings, and
+    /// returns a handle to it.
+    ///
+    /// The settings replace those of the `.meta` file of the asset for this load only, and are read
+    /// by the loader with [`LoadContext::settings`]. Later reloads use the `.meta` file again. The
+    /// asset is reloaded if it was already loaded, so this should only be called when the settings
+    /// change.
+    #[must_use = "not using the returned strong handle may result in the unexpected release of the asset"]
+    pub fn load_with_settings<'a, T: Asset, P: Into<AssetPath<'a>>, S: Send + Sync + 'static>(
+        &self,
+        path: P,
+        settings: S,
+    ) -> Handle<T> {
+        let asset_path = path.into();
+        self.server
+            .settings_overrides
+            .write()
+            .insert(asset_path.get_id().source_path_id(), Arc::new(settings));
+        let handle_id = self.load_untracked(asset_path, true);
+        self.get_handle(handle_id)
+    }
+
+    pub(crate) async fn load_async(
         &self,
         asset_path: AssetPath<'_>,
         force: bool,
@@ -345,6 +438,13 @@ impl AssetServer {
             source_info.version
         };
 
+        // the settings override only applies to this load, so that it doesn't outlive it
+        let settings_override = self
+            .server
+            .settings_overrides
+            .write()
+            .remove(&asset_path_id.source_path_id());
+
         let set_asset_failed = || {
             let mut asset_sources = self.server.asset_sources.write();
             let source_info = asset_sources
@@ -353,8 +453,34 @@ impl AssetServer {
             source_info.load_state = LoadState::Failed;
         };
 
+        let (source_asset_io, source_path) = self.source_asset_io(asset_path.path());
+
+        // prefer the processed artifact of the asset, which is loaded by the loader of its own
+        // extension
+        let processed = self.load_processed(asset_path.path()).await;
+
+        // the `.meta` file of the source may choose its loader and carry the loader settings
+        let meta = match processed {
+            Some(_) => None,
+            None => match self.load_meta(asset_path.path()).await {
+                Ok(meta) => meta,
+                Err(err) => {
+                    set_asset_failed();
+                    return Err(err);
+                }
+            },
+        };
+
         // get the according asset loader
-        let asset_loader = match self.get_path_asset_loader(asset_path.path()) {
+        let asset_loader = match (
+            &processed,
+            meta.as_ref().and_then(|meta| meta.loader.as_ref()),
+        ) {
+            (Some((processed_path, _)), _) => self.get_path_asset_loader(processed_path),
+            (None, Some(name)) => self.get_named_asset_loader(name),
+            (None, None) => self.get_path_asset_loader(asset_path.path()),
+        };
+        let asset_loader = match asset_loader {
             Ok(loader) => loader,
             Err(err) => {
                 set_asset_failed();
@@ -363,21 +489,22 @@ impl AssetServer {
         };
 
         // load the asset bytes
-        let bytes = match self.asset_io().load_path(asset_path.path()).await {
-            Ok(bytes) => bytes,
-            Err(err) => {
-                set_asset_failed();
-                return Err(AssetServerError::AssetIoError(err));
-            }
+        let bytes = match processed {
+            Some((_, bytes)) => bytes,
+            None => match source_asset_io.load_path(source_path).await {
+                Ok(bytes) => bytes,
+                Err(err) => {
+                    set_asset_failed();
+                    return Err(AssetServerError::AssetIoError(err));
+                }
+            },
         };
 
         // load the asset source using the corresponding AssetLoader
-        let mut load_context = LoadContext::new(
-            asset_path.path(),
-            &self.server.asset_ref_counter.channel,
-            self.asset_io(),
-            version,
-        );
+        let mut load_context = LoadContext::new(asset_path.path(), self, source_asset_io, version);
+        let has_meta = meta.is_some();
+        load_context.meta = meta;
+        load_context.settings_override = settings_override;
 
         if let Err(err) = asset_loader
             .load(&bytes, &mut load_context)
@@ -422,13 +549,51 @@ impl AssetServer {
             }
         }
 
-        self.asset_io()
-            .watch_path_for_changes(asset_path.path(), None)
+        source_asset_io
+            .watch_path_for_changes(source_path, Some(asset_path.path().to_owned()))
             .unwrap();
+        if has_meta {
+            source_asset_io
+                .watch_path_for_changes(&meta_path(source_path), Some(asset_path.path().to_owned()))
+                .unwrap();
+        }
         self.create_assets_in_load_context(&mut load_context);
         Ok(asset_path_id)
     }
 
+    /// Reads the `.meta` file of the asset at `path`, if it has one.
+    async fn load_meta(&self, path: &Path) -> Result<Option<Meta>, AssetServerError> {
+        let Some(bytes) = self.load_meta_bytes(path).await? else {
+            return Ok(None);
+        };
+        let meta_path = meta_path(self.source_asset_io(path).1);
+        Meta::parse(bytes)
+            .map(Some)
+            .map_err(|error| AssetServerError::InvalidMetaFile {
+                path: meta_path,
+                error,
+            })
+    }
+
+    /// Reads the contents of the `.meta` file of the asset at `path`, if it has one.
+    pub(crate) async fn load_meta_bytes(
+        &self,
+        path: &Path,
+    ) -> Result<Option<Vec<u8>>, AssetIoError> {
+        let (source_asset_io, source_path) = self.source_asset_io(path);
+        let meta_path = meta_path(source_path);
+        // some asset I/Os, such as web servers, answer reads of missing files with an error page,
+        // so the `.meta` file must exist before it is read
+        if let Err(AssetIoError::NotFound(_)) = source_asset_io.get_metadata(&meta_path) {
+            return Ok(None);
+        }
+        match source_asset_io.load_path(&meta_path).await {
+            Ok(bytes) => Ok(Some(bytes)),
+            Err(AssetIoError::NotFound(_)) => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
     /// Queues the [`Asset`] at the provided path for loading and returns an untyped handle.
     ///
     /// See [`load`](AssetServer::load).
@@ -481,15 +646,21 @@ impl AssetServer {
         path: P,
     ) -> Result<Vec<HandleUntyped>, AssetServerError> {
         let path = path.as_ref();
-        if !self.asset_io().is_dir(path) {
+        let (source_asset_io, source_path) = self.source_asset_io(path);
+        if !source_asset_io.is_dir(source_path) {
             return Err(AssetServerError::AssetFolderNotADirectory(
                 path.to_str().unwrap().to_string(),
             ));
         }
 
         let mut handles = Vec::new();
-        for child_path in self.asset_io().read_directory(path.as_ref())? {
-            if self.asset_io().is_dir(&child_path) {
+        for source_child_path in source_asset_io.read_directory(source_path)? {
+            // the children of embedded folders are embedded assets too
+            let child_path = match embedded_path(path) {
+                Some(_) => Path::new(EMBEDDED_SOURCE).join(&source_child_path),
+                None => source_child_path.clone(),
+            };
+            if source_asset_io.is_dir(&source_child_path) {
                 handles.extend(self.load_folder(&child_path)?);
             } else {
                 if self.get_path_asset_loader(&child_path).is_err() {
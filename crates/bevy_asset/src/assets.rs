@@ -1,6 +1,7 @@
 use crate::{
-    update_asset_storage_system, Asset, AssetEvents, AssetLoader, AssetServer, Handle, HandleId,
-    LoadAssets, RefChange, ReflectAsset, ReflectHandle,
+    save_assets_system, update_asset_storage_system, Asset, AssetEvents, AssetLoader,
+    AssetProcessor, AssetSaver, AssetServer, Handle, HandleId, LoadAssets, RefChange, ReflectAsset,
+    ReflectHandle,
 };
 use bevy_app::App;
 use bevy_ecs::prelude::*;
@@ -317,6 +318,23 @@ pub trait AddAsset {
     fn add_asset_loader<T>(&mut self, loader: T) -> &mut Self
     where
         T: AssetLoader;
+
+    /// Adds the provided asset processor to the application.
+    fn add_asset_processor<T>(&mut self, processor: T) -> &mut Self
+    where
+        T: AssetProcessor;
+
+    /// Adds an asset saver `T` using default values.
+    ///
+    /// The default values may come from the [`World`] or from `T::default()`.
+    fn init_asset_saver<T>(&mut self) -> &mut Self
+    where
+        T: AssetSaver + FromWorld;
+
+    /// Adds the provided asset saver to the application.
+    fn add_asset_saver<T>(&mut self, saver: T) -> &mut Self
+    where
+        T: AssetSaver;
 }
 
 impl AddAsset for App {
@@ -334,7 +352,10 @@ impl AddAsset for App {
 
         self.insert_resource(assets)
             .add_systems(LoadAssets, update_asset_storage_system::<T>)
-            .add_systems(AssetEvents, Assets::<T>::asset_event_system)
+            .add_systems(
+                AssetEvents,
+                (Assets::<T>::asset_event_system, save_assets_system::<T>),
+            )
             .register_type::<Handle<T>>()
             .add_event::<AssetEvent<T>>()
     }
@@ -404,6 +425,32 @@ impl AddAsset for App {
         self.world.resource_mut::<AssetServer>().add_loader(loader);
         self
     }
+
+    fn add_asset_processor<T>(&mut self, processor: T) -> &mut Self
+    where
+        T: AssetProcessor,
+    {
+        self.world
+            .resource::<AssetServer>()
+            .add_processor(processor);
+        self
+    }
+
+    fn init_asset_saver<T>(&mut self) -> &mut Self
+    where
+        T: AssetSaver + FromWorld,
+    {
+        let result = T::from_world(&mut self.world);
+        self.add_asset_saver(result)
+    }
+
+    fn add_asset_saver<T>(&mut self, saver: T) -> &mut Self
+    where
+        T: AssetSaver,
+    {
+        self.world.resource::<AssetServer>().add_saver(saver);
+        self
+    }
 }
 
 /// Loads an internal asset from a project source file.
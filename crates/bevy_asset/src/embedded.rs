@@ -0,0 +1,235 @@
+use crate::{AssetIo, AssetServer, MemoryAssetIo};
+use std::path::Path;
+
+/// The prefix of the paths of the [embedded assets](AssetServer::embedded_assets).
+pub const EMBEDDED_SOURCE: &str = "embedded://";
+
+/// Returns the path of `path` in the embedded assets, if it's the path of an embedded asset.
+pub(crate) fn embedded_path(path: &Path) -> Option<&Path> {
+    // `embedded://a/b` has the components `embedded:`, `a` and `b`
+    path.strip_prefix(EMBEDDED_SOURCE.trim_end_matches('/'))
+        .ok()
+}
+
+impl AssetServer {
+    /// Returns the assets embedded in the application, loaded from the paths starting with
+    /// [`EMBEDDED_SOURCE`].
+    ///
+    /// The file at `a/b.png` in the embedded assets is the asset at `embedded://a/b.png`. Files are
+    /// usually embedded with [`embedded_asset!`](crate::embedded_asset), to ship single binaries.
+    pub fn embedded_assets(&self) -> &MemoryAssetIo {
+        &self.server.embedded_assets
+    }
+
+    /// Returns the asset I/O the file at `path` is read from, and its path in this asset I/O.
+    pub(crate) fn source_asset_io<'a>(&'a self, path: &'a Path) -> (&'a dyn AssetIo, &'a Path) {
+        match embedded_path(path) {
+            Some(path) => (self.embedded_assets(), path),
+            None => (self.asset_io(), path),
+        }
+    }
+}
+
+/// Embeds the file at a path relative to the current file in the binary, as an embedded asset of
+/// the [`AssetServer`] of the app.
+///
+/// The file is loaded from `embedded://<crate>/<path>`, where `<crate>` is the name of the crate
+/// calling the macro.
+///
+/// ```
+/// # use bevy_app::App;
+/// # use bevy_asset::{embedded_asset, AssetIo, AssetServer, MemoryAssetIo};
+/// # use std::path::Path;
+/// # let mut app = App::new();
+/// # app.insert_resource(AssetServer::new(MemoryAssetIo::default()));
+/// # let my_crate = module_path!();
+/// // embeds the file next to this one, which is loaded from `embedded://my_crate/embedded.rs`
+/// embedded_asset!(app, "embedded.rs");
+///
+/// let asset_server = app.world.resource::<AssetServer>();
+/// assert!(asset_server
+///     .embedded_assets()
+///     .is_file(&Path::new(my_crate).join("embedded.rs")));
+/// ```
+#[macro_export]
+macro_rules! embedded_asset {
+    ($app: ident, $path_str: expr) => {{
+        let embedded_path =
+            std::path::Path::new(module_path!().split("::").next().unwrap()).join($path_str);
+        $app.world
+            .resource::<$crate::AssetServer>()
+            .embedded_assets()
+            .insert(embedded_path, include_bytes!($path_str).as_slice());
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        memory_asset_io_system, update_asset_storage_system, AssetIo, AssetLoader, AssetServer,
+        Assets, ChangeWatcher, Handle, LoadContext, LoadState, LoadedAsset, MemoryAssetIo,
+    };
+    use anyhow::Error;
+    use bevy_app::{App, Update};
+    use bevy_reflect::{TypePath, TypeUuid};
+    use bevy_tasks::IoTaskPool;
+    use bevy_utils::BoxedFuture;
+    use std::path::Path;
+
+    #[derive(Debug, TypeUuid, TypePath)]
+    #[uuid = "6a1b3c9e-2f47-4d8a-9b0e-7c5d2e4f8a13"]
+    struct Text(String);
+
+    struct TextLoader;
+
+    impl AssetLoader for TextLoader {
+        fn load<'a>(
+            &'a self,
+            bytes: &'a [u8],
+            load_context: &'a mut LoadContext,
+        ) -> BoxedFuture<'a, Result<(), Error>> {
+            Box::pin(async move {
+                let text = String::from_utf8(bytes.to_vec())?;
+                load_context.set_default_asset(LoadedAsset::new(Text(text)));
+                Ok(())
+            })
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["txt"]
+        }
+    }
+
+    /// Loads the text of the file at the path written in the `.link` file.
+    struct LinkLoader;
+
+    impl AssetLoader for LinkLoader {
+        fn load<'a>(
+            &'a self,
+            bytes: &'a [u8],
+            load_context: &'a mut LoadContext,
+        ) -> BoxedFuture<'a, Result<(), Error>> {
+            Box::pin(async move {
+                let path = std::str::from_utf8(bytes)?;
+                let bytes = load_context.read_asset_bytes(path).await?;
+                let text = String::from_utf8(bytes)?;
+                load_context.set_default_asset(LoadedAsset::new(Text(text)));
+                Ok(())
+            })
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["link"]
+        }
+    }
+
+    fn setup(asset_io: MemoryAssetIo) -> App {
+        IoTaskPool::init(Default::default);
+        let asset_server = AssetServer::new(asset_io);
+        asset_server.add_loader(TextLoader);
+        asset_server.add_loader(LinkLoader);
+
+        let mut app = App::new();
+        app.insert_resource(asset_server.register_asset_type::<Text>())
+            .insert_resource(asset_server)
+            .add_systems(
+                Update,
+                (memory_asset_io_system, update_asset_storage_system::<Text>),
+            );
+        app
+    }
+
+    fn text(app: &App, handle: &Handle<Text>) -> String {
+        app.world
+            .resource::<Assets<Text>>()
+            .get(handle)
+            .unwrap()
+            .0
+            .clone()
+    }
+
+    fn wait_loaded(app: &mut App, handle: &Handle<Text>) -> String {
+        let asset_server = app.world.resource::<AssetServer>().clone();
+        while asset_server.get_load_state(handle) != LoadState::Loaded {
+            std::thread::sleep(std::time::Duration::from_millis(1));
+            app.update();
+        }
+        text(app, handle)
+    }
+
+    #[test]
+    fn load_embedded() {
+        let mut app = setup(MemoryAssetIo::default().with_file("a.txt", b"file".as_slice()));
+        let asset_server = app.world.resource::<AssetServer>().clone();
+        asset_server
+            .embedded_assets()
+            .insert("my_crate/a.txt", b"embedded".as_slice());
+
+        let file: Handle<Text> = asset_server.load("a.txt");
+        let embedded: Handle<Text> = asset_server.load("embedded://my_crate/a.txt");
+        assert_eq!(wait_loaded(&mut app, &file), "file");
+        assert_eq!(wait_loaded(&mut app, &embedded), "embedded");
+
+        let folder = asset_server.load_folder("embedded://my_crate").unwrap();
+        assert_eq!(folder.len(), 1);
+        assert_eq!(folder[0].id(), embedded.id());
+    }
+
+    #[test]
+    fn read_embedded_bytes() {
+        let mut app = setup(
+            MemoryAssetIo::default()
+                .with_file("a.txt", b"file".as_slice())
+                .with_file("a.link", b"embedded://my_crate/a.txt".as_slice()),
+        );
+        let asset_server = app.world.resource::<AssetServer>().clone();
+        asset_server
+            .embedded_assets()
+            .insert("my_crate/a.txt", b"embedded".as_slice());
+        asset_server
+            .embedded_assets()
+            .insert("my_crate/a.link", b"a.txt".as_slice());
+
+        // the files read by loaders are found the same way as the files of the assets
+        let file: Handle<Text> = asset_server.load("a.link");
+        let embedded: Handle<Text> = asset_server.load("embedded://my_crate/a.link");
+        assert_eq!(wait_loaded(&mut app, &file), "embedded");
+        assert_eq!(wait_loaded(&mut app, &embedded), "file");
+    }
+
+    #[test]
+    fn embed_file() {
+        let app = setup(MemoryAssetIo::default());
+        embedded_asset!(app, "embedded.rs");
+
+        let asset_server = app.world.resource::<AssetServer>();
+        assert!(asset_server
+            .embedded_assets()
+            .is_file(Path::new("bevy_asset/embedded.rs")));
+    }
+
+    #[test]
+    fn reload_memory_files() {
+        let asset_io = MemoryAssetIo::default().with_file("a.txt", b"a".as_slice());
+        asset_io
+            .watch_for_changes(&ChangeWatcher::with_delay(Default::default()).unwrap())
+            .unwrap();
+        let mut app = setup(asset_io);
+        let asset_server = app.world.resource::<AssetServer>().clone();
+
+        let handle: Handle<Text> = asset_server.load("a.txt");
+        assert_eq!(wait_loaded(&mut app, &handle), "a");
+
+        asset_server
+            .asset_io()
+            .downcast_ref::<MemoryAssetIo>()
+            .unwrap()
+            .insert("a.txt", b"changed".as_slice());
+        // the asset is reloaded as soon as the change is noticed
+        while text(&app, &handle) == "a" {
+            std::thread::sleep(std::time::Duration::from_millis(1));
+            app.update();
+        }
+        assert_eq!(text(&app, &handle), "changed");
+    }
+}
@@ -1,5 +1,5 @@
 #[cfg(feature = "filesystem_watcher")]
-use crate::{filesystem_watcher::FilesystemWatcher, AssetServer};
+use crate::{filesystem_watcher::FilesystemWatcher, AssetServer, OverlayAssetIo};
 use crate::{AssetIo, AssetIoError, ChangeWatcher, Metadata};
 use anyhow::Result;
 #[cfg(feature = "filesystem_watcher")]
@@ -154,6 +154,62 @@ impl AssetIo for FileAssetIo {
         Ok(())
     }
 
+    fn write_path<'a>(
+        &'a self,
+        path: &'a Path,
+        bytes: &'a [u8],
+    ) -> BoxedFuture<'a, Result<(), AssetIoError>> {
+        Box::pin(async move {
+            let full_path = self.root_path.join(path);
+            if let Some(parent) = full_path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(full_path, bytes)?;
+            Ok(())
+        })
+    }
+
+    fn rename_path<'a>(
+        &'a self,
+        from: &'a Path,
+        to: &'a Path,
+    ) -> BoxedFuture<'a, Result<(), AssetIoError>> {
+        Box::pin(async move {
+            let full_from = self.root_path.join(from);
+            if !full_from.exists() {
+                return Err(AssetIoError::NotFound(full_from));
+            }
+            let full_to = self.root_path.join(to);
+            if let Some(parent) = full_to.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::rename(full_from, full_to)?;
+            Ok(())
+        })
+    }
+
+    fn remove_path<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<(), AssetIoError>> {
+        Box::pin(async move {
+            let full_path = self.root_path.join(path);
+            match full_path.metadata() {
+                Ok(metadata) if metadata.is_dir() => fs::remove_dir_all(full_path)?,
+                Ok(_) => fs::remove_file(full_path)?,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                    return Err(AssetIoError::NotFound(full_path))
+                }
+                Err(e) => return Err(e.into()),
+            }
+            Ok(())
+        })
+    }
+
+    fn create_directory<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<(), AssetIoError>> {
+        Box::pin(async move {
+            fs::create_dir_all(self.root_path.join(path))?;
+            Ok(())
+        })
+    }
+
     fn get_metadata(&self, path: &Path) -> Result<Metadata, AssetIoError> {
         let full_path = self.root_path.join(path);
         full_path
@@ -169,6 +225,25 @@ impl AssetIo for FileAssetIo {
     }
 }
 
+/// Returns `true` if the files of the asset I/O of the [`AssetServer`] are watched by
+/// [`filesystem_watcher_system`], in which case writing a file is echoed back as a change.
+#[cfg(all(
+    feature = "filesystem_watcher",
+    all(not(target_arch = "wasm32"), not(target_os = "android"))
+))]
+pub(crate) fn is_watching_files(asset_server: &AssetServer) -> bool {
+    let is_watching = |asset_io: &dyn AssetIo| {
+        asset_io
+            .downcast_ref::<FileAssetIo>()
+            .is_some_and(|asset_io| asset_io.filesystem_watcher.read().is_some())
+    };
+    let asset_io = &*asset_server.server.asset_io;
+    match asset_io.downcast_ref::<OverlayAssetIo>() {
+        Some(asset_io) => asset_io.layers().iter().any(|layer| is_watching(&**layer)),
+        None => is_watching(asset_io),
+    }
+}
+
 /// Watches for file changes in the local file system.
 #[cfg(all(
     feature = "filesystem_watcher",
@@ -178,12 +253,25 @@ pub fn filesystem_watcher_system(
     asset_server: Res<AssetServer>,
     mut changed: Local<HashMap<PathBuf, Instant>>,
 ) {
-    let asset_io =
-        if let Some(asset_io) = asset_server.server.asset_io.downcast_ref::<FileAssetIo>() {
-            asset_io
-        } else {
-            return;
-        };
+    let asset_io = &*asset_server.server.asset_io;
+    if let Some(asset_io) = asset_io.downcast_ref::<FileAssetIo>() {
+        reload_changed_files(&asset_server, asset_io, &mut changed);
+    } else if let Some(asset_io) = asset_io.downcast_ref::<OverlayAssetIo>() {
+        // each directory layer has its own watcher
+        for layer in asset_io.layers() {
+            if let Some(layer) = layer.downcast_ref::<FileAssetIo>() {
+                reload_changed_files(&asset_server, layer, &mut changed);
+            }
+        }
+    }
+}
+
+#[cfg(feature = "filesystem_watcher")]
+fn reload_changed_files(
+    asset_server: &AssetServer,
+    asset_io: &FileAssetIo,
+    changed: &mut HashMap<PathBuf, Instant>,
+) {
     let watcher = asset_io.filesystem_watcher.read();
 
     if let Some(ref watcher) = *watcher {
@@ -215,10 +303,44 @@ pub fn filesystem_watcher_system(
         // When changing and then saving a shader, several modification events are sent in short succession.
         // Unless we wait until we are sure the shader is finished being modified (and that there will be no more events coming),
         // we will sometimes get a crash when trying to reload a partially-modified shader.
-        for (to_reload, _) in
+        for (to_reload, last_modified) in
             changed.extract_if(|_, last_modified| last_modified.elapsed() >= watcher.delay)
         {
+            // the asset in memory is already up to date with the files it saved
+            if asset_server.is_save_echo(&to_reload, last_modified, watcher.delay) {
+                continue;
+            }
             let _ = asset_server.load_untracked(to_reload.as_path().into(), true);
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_lite::future::block_on;
+
+    #[test]
+    fn write_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let asset_io = FileAssetIo::new(dir.path(), &None);
+
+        block_on(asset_io.write_path(Path::new("a/b.txt"), b"b")).unwrap();
+        block_on(asset_io.create_directory(Path::new("c/d"))).unwrap();
+        assert!(asset_io.is_dir(Path::new("c/d")));
+
+        block_on(asset_io.rename_path(Path::new("a/b.txt"), Path::new("c/d/e.txt"))).unwrap();
+        assert!(!asset_io.is_file(Path::new("a/b.txt")));
+        assert_eq!(
+            block_on(asset_io.load_path(Path::new("c/d/e.txt"))).unwrap(),
+            b"b"
+        );
+
+        block_on(asset_io.remove_path(Path::new("c"))).unwrap();
+        assert!(!asset_io.is_dir(Path::new("c")));
+        assert!(matches!(
+            block_on(asset_io.remove_path(Path::new("c"))),
+            Err(AssetIoError::NotFound(_))
+        ));
+    }
+}
@@ -0,0 +1,315 @@
+use crate::{
+    AssetIo, AssetIoError, AssetServer, ChangeWatcher, FileType, Metadata, OverlayAssetIo,
+};
+use anyhow::Result;
+use bevy_ecs::system::Res;
+use bevy_utils::{BoxedFuture, HashMap, HashSet};
+use parking_lot::{Mutex, RwLock};
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, BTreeSet},
+    path::{Path, PathBuf},
+};
+
+/// I/O implementation serving assets from memory.
+///
+/// Files are added with [`MemoryAssetIo::insert`], either from owned bytes or from static bytes,
+/// such as the bytes of [`include_bytes!`]. Directories aren't stored: a directory exists as long
+/// as it contains files.
+///
+/// Once [`AssetIo::watch_for_changes`] is called, inserting a file reloads the assets depending
+/// on it during the next [`LoadAssets`](crate::LoadAssets) schedule, without waiting for a delay,
+/// which makes hot reloading deterministic in tests.
+///
+/// ```
+/// # use bevy_asset::{AssetServer, MemoryAssetIo};
+/// let asset_io = MemoryAssetIo::default().with_file("levels/a.ron", b"(enemies: 3)".as_slice());
+/// let asset_server = AssetServer::new(asset_io);
+/// ```
+#[derive(Default)]
+pub struct MemoryAssetIo {
+    files: RwLock<BTreeMap<PathBuf, Cow<'static, [u8]>>>,
+    watcher: Mutex<Option<MemoryWatcher>>,
+}
+
+#[derive(Default)]
+struct MemoryWatcher {
+    path_map: HashMap<PathBuf, HashSet<PathBuf>>,
+    changed: Vec<PathBuf>,
+}
+
+impl MemoryAssetIo {
+    /// Adds the file at `path` with the provided contents, and returns the asset I/O.
+    pub fn with_file<P: Into<PathBuf>, B: Into<Cow<'static, [u8]>>>(
+        self,
+        path: P,
+        bytes: B,
+    ) -> Self {
+        self.insert(path, bytes);
+        self
+    }
+
+    /// Adds the file at `path` with the provided contents, replacing the file if it exists.
+    ///
+    /// If changes are watched, the assets depending on the file are reloaded.
+    pub fn insert<P: Into<PathBuf>, B: Into<Cow<'static, [u8]>>>(&self, path: P, bytes: B) {
+        let path = path.into();
+        self.files.write().insert(path.clone(), bytes.into());
+        self.notify_changed(&path);
+    }
+
+    /// Removes the file at `path`, returning `true` if it existed.
+    ///
+    /// If changes are watched, the assets depending on the file are reloaded.
+    pub fn remove(&self, path: &Path) -> bool {
+        let removed = self.files.write().remove(path).is_some();
+        if removed {
+            self.notify_changed(path);
+        }
+        removed
+    }
+
+    fn notify_changed(&self, path: &Path) {
+        if let Some(watcher) = &mut *self.watcher.lock() {
+            if let Some(to_reload) = watcher.path_map.get(path) {
+                watcher.changed.extend(to_reload.iter().cloned());
+            }
+        }
+    }
+
+    /// Takes the paths of the assets to reload since the last call.
+    pub(crate) fn take_changed(&self) -> Vec<PathBuf> {
+        match &mut *self.watcher.lock() {
+            Some(watcher) => std::mem::take(&mut watcher.changed),
+            None => Vec::new(),
+        }
+    }
+
+    fn is_directory(files: &BTreeMap<PathBuf, Cow<'static, [u8]>>, path: &Path) -> bool {
+        path.as_os_str().is_empty()
+            || files
+                .keys()
+                .any(|file| file != path && file.starts_with(path))
+    }
+}
+
+impl AssetIo for MemoryAssetIo {
+    fn load_path<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<Vec<u8>, AssetIoError>> {
+        Box::pin(async move {
+            self.files
+                .read()
+                .get(path)
+                .map(|bytes| bytes.to_vec())
+                .ok_or_else(|| AssetIoError::NotFound(path.to_owned()))
+        })
+    }
+
+    fn read_directory(
+        &self,
+        path: &Path,
+    ) -> Result<Box<dyn Iterator<Item = PathBuf>>, AssetIoError> {
+        let files = self.files.read();
+        if !Self::is_directory(&files, path) {
+            return Err(AssetIoError::NotFound(path.to_owned()));
+        }
+        let children: BTreeSet<PathBuf> = files
+            .keys()
+            .filter_map(|file| file.strip_prefix(path).ok()?.components().next())
+            .map(|child| path.join(child))
+            .collect();
+        Ok(Box::new(children.into_iter()))
+    }
+
+    fn get_metadata(&self, path: &Path) -> Result<Metadata, AssetIoError> {
+        let files = self.files.read();
+        if files.contains_key(path) {
+            Ok(Metadata::new(FileType::File))
+        } else if Self::is_directory(&files, path) {
+            Ok(Metadata::new(FileType::Directory))
+        } else {
+            Err(AssetIoError::NotFound(path.to_owned()))
+        }
+    }
+
+    fn watch_path_for_changes(
+        &self,
+        to_watch: &Path,
+        to_reload: Option<PathBuf>,
+    ) -> Result<(), AssetIoError> {
+        if let Some(watcher) = &mut *self.watcher.lock() {
+            let to_reload = to_reload.unwrap_or_else(|| to_watch.to_owned());
+            watcher
+                .path_map
+                .entry(to_watch.to_owned())
+                .or_default()
+                .insert(to_reload);
+        }
+        Ok(())
+    }
+
+    fn watch_for_changes(&self, _configuration: &ChangeWatcher) -> Result<(), AssetIoError> {
+        self.watcher.lock().get_or_insert_with(Default::default);
+        Ok(())
+    }
+
+    fn write_path<'a>(
+        &'a self,
+        path: &'a Path,
+        bytes: &'a [u8],
+    ) -> BoxedFuture<'a, Result<(), AssetIoError>> {
+        Box::pin(async move {
+            self.insert(path, bytes.to_vec());
+            Ok(())
+        })
+    }
+
+    fn rename_path<'a>(
+        &'a self,
+        from: &'a Path,
+        to: &'a Path,
+    ) -> BoxedFuture<'a, Result<(), AssetIoError>> {
+        Box::pin(async move {
+            let moved: Vec<_> = {
+                let mut files = self.files.write();
+                let paths: Vec<PathBuf> = files
+                    .keys()
+                    .filter(|file| file.starts_with(from))
+                    .cloned()
+                    .collect();
+                paths
+                    .into_iter()
+                    .map(|path| {
+                        let bytes = files.remove(&path).unwrap();
+                        (to.join(path.strip_prefix(from).unwrap()), bytes)
+                    })
+                    .collect()
+            };
+            if moved.is_empty() {
+                return Err(AssetIoError::NotFound(from.to_owned()));
+            }
+            for (path, bytes) in moved {
+                self.insert(path, bytes);
+            }
+            Ok(())
+        })
+    }
+
+    fn remove_path<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<(), AssetIoError>> {
+        Box::pin(async move {
+            let mut files = self.files.write();
+            let count = files.len();
+            files.retain(|file, _| !file.starts_with(path));
+            if files.len() == count {
+                return Err(AssetIoError::NotFound(path.to_owned()));
+            }
+            Ok(())
+        })
+    }
+
+    fn create_directory<'a>(
+        &'a self,
+        _path: &'a Path,
+    ) -> BoxedFuture<'a, Result<(), AssetIoError>> {
+        // directories only exist through the files they contain
+        Box::pin(async move { Ok(()) })
+    }
+}
+
+/// Reloads the assets whose files changed in the [`MemoryAssetIo`]s of the [`AssetServer`]:
+/// its asset I/O, the layers of an [`OverlayAssetIo`], and its
+/// [embedded assets](AssetServer::embedded_assets).
+pub fn memory_asset_io_system(asset_server: Res<AssetServer>) {
+    let asset_io = asset_server.asset_io();
+    let mut memory_asset_ios = vec![asset_server.embedded_assets()];
+    if let Some(asset_io) = asset_io.downcast_ref::<MemoryAssetIo>() {
+        memory_asset_ios.push(asset_io);
+    } else if let Some(asset_io) = asset_io.downcast_ref::<OverlayAssetIo>() {
+        memory_asset_ios.extend(
+            asset_io
+                .layers()
+                .iter()
+                .filter_map(|layer| layer.downcast_ref::<MemoryAssetIo>()),
+        );
+    }
+
+    for memory_asset_io in memory_asset_ios {
+        let mut reloaded = HashSet::default();
+        for to_reload in memory_asset_io.take_changed() {
+            if reloaded.insert(to_reload.clone()) {
+                asset_server.reload_asset(to_reload.as_path());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_lite::future::block_on;
+
+    #[test]
+    fn memory_files() {
+        let asset_io = MemoryAssetIo::default()
+            .with_file("a.txt", b"a".as_slice())
+            .with_file("levels/b.txt", b"b".to_vec())
+            .with_file("levels/c/d.txt", b"d".as_slice());
+
+        assert_eq!(
+            block_on(asset_io.load_path(Path::new("levels/b.txt"))).unwrap(),
+            b"b"
+        );
+        assert!(asset_io.is_dir(Path::new("levels/c")));
+        assert!(asset_io.is_file(Path::new("a.txt")));
+        assert!(!asset_io.is_dir(Path::new("a.txt")));
+
+        let read_directory = |path: &str| -> Vec<PathBuf> {
+            asset_io.read_directory(Path::new(path)).unwrap().collect()
+        };
+        assert_eq!(read_directory(""), ["a.txt", "levels"].map(PathBuf::from));
+        assert_eq!(
+            read_directory("levels"),
+            ["levels/b.txt", "levels/c"].map(PathBuf::from)
+        );
+
+        block_on(asset_io.rename_path(Path::new("levels"), Path::new("old"))).unwrap();
+        assert!(!asset_io.is_dir(Path::new("levels")));
+        assert!(asset_io.is_file(Path::new("old/c/d.txt")));
+
+        block_on(asset_io.remove_path(Path::new("old"))).unwrap();
+        assert_eq!(read_directory(""), [PathBuf::from("a.txt")]);
+        assert!(matches!(
+            block_on(asset_io.remove_path(Path::new("old"))),
+            Err(AssetIoError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn change_notifications() {
+        let asset_io = MemoryAssetIo::default().with_file("a.txt", b"a".as_slice());
+
+        // changes aren't tracked until watching for changes
+        asset_io
+            .watch_path_for_changes(Path::new("a.txt"), None)
+            .unwrap();
+        asset_io.insert("a.txt", b"a".as_slice());
+        assert!(asset_io.take_changed().is_empty());
+
+        asset_io
+            .watch_for_changes(&ChangeWatcher::with_delay(Default::default()).unwrap())
+            .unwrap();
+        asset_io
+            .watch_path_for_changes(Path::new("a.txt"), None)
+            .unwrap();
+        asset_io
+            .watch_path_for_changes(Path::new("a.txt.meta"), Some(PathBuf::from("a.txt")))
+            .unwrap();
+        asset_io.insert("a.txt.meta", b"()".as_slice());
+        asset_io.insert("b.txt", b"b".as_slice());
+        assert_eq!(asset_io.take_changed(), [PathBuf::from("a.txt")]);
+        assert!(asset_io.take_changed().is_empty());
+
+        assert!(asset_io.remove(Path::new("a.txt.meta")));
+        assert!(!asset_io.remove(Path::new("a.txt.meta")));
+        assert_eq!(asset_io.take_changed(), [PathBuf::from("a.txt")]);
+    }
+}
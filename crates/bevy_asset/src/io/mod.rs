@@ -5,7 +5,11 @@ mod file_asset_io;
 #[cfg(target_arch = "wasm32")]
 mod wasm_asset_io;
 
+mod memory_asset_io;
 mod metadata;
+mod overlay_asset_io;
+#[cfg(feature = "zip")]
+mod zip_asset_io;
 
 #[cfg(target_os = "android")]
 pub use android_asset_io::*;
@@ -14,7 +18,11 @@ pub use file_asset_io::*;
 #[cfg(target_arch = "wasm32")]
 pub use wasm_asset_io::*;
 
+pub use memory_asset_io::*;
 pub use metadata::*;
+pub use overlay_asset_io::*;
+#[cfg(feature = "zip")]
+pub use zip_asset_io::*;
 
 use anyhow::Result;
 use bevy_utils::BoxedFuture;
@@ -41,6 +49,10 @@ pub enum AssetIoError {
     /// Failed to watch path.
     #[error("failed to watch path: {0}")]
     PathWatchError(PathBuf),
+
+    /// The asset I/O doesn't support writing files.
+    #[error("writing is not supported by this asset io: {0}")]
+    WriteUnsupported(PathBuf),
 }
 
 /// A storage provider for an [`AssetServer`].
@@ -85,6 +97,46 @@ pub trait AssetIo: Downcast + Send + Sync + 'static {
     /// Enables change tracking in this asset I/O.
     fn watch_for_changes(&self, configuration: &ChangeWatcher) -> Result<(), AssetIoError>;
 
+    /// Returns a future to write `bytes` to the file at the provided path, creating its parent
+    /// directories and replacing the file if it exists.
+    ///
+    /// Fails with [`AssetIoError::WriteUnsupported`] by default, for read-only asset I/Os.
+    fn write_path<'a>(
+        &'a self,
+        path: &'a Path,
+        _bytes: &'a [u8],
+    ) -> BoxedFuture<'a, Result<(), AssetIoError>> {
+        Box::pin(async move { Err(AssetIoError::WriteUnsupported(path.to_owned())) })
+    }
+
+    /// Returns a future to move the file or directory at the path `from` to the path `to`,
+    /// replacing the file at `to` if it exists.
+    ///
+    /// Fails with [`AssetIoError::WriteUnsupported`] by default, for read-only asset I/Os.
+    fn rename_path<'a>(
+        &'a self,
+        from: &'a Path,
+        _to: &'a Path,
+    ) -> BoxedFuture<'a, Result<(), AssetIoError>> {
+        Box::pin(async move { Err(AssetIoError::WriteUnsupported(from.to_owned())) })
+    }
+
+    /// Returns a future to delete the file, or the directory and its contents, at the provided
+    /// path.
+    ///
+    /// Fails with [`AssetIoError::WriteUnsupported`] by default, for read-only asset I/Os.
+    fn remove_path<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<(), AssetIoError>> {
+        Box::pin(async move { Err(AssetIoError::WriteUnsupported(path.to_owned())) })
+    }
+
+    /// Returns a future to create the directory at the provided path, and its missing parent
+    /// directories.
+    ///
+    /// Fails with [`AssetIoError::WriteUnsupported`] by default, for read-only asset I/Os.
+    fn create_directory<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<(), AssetIoError>> {
+        Box::pin(async move { Err(AssetIoError::WriteUnsupported(path.to_owned())) })
+    }
+
     /// Returns `true` if the path is a directory.
     fn is_dir(&self, path: &Path) -> bool {
         self.get_metadata(path)
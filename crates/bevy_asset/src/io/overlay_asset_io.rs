@@ -0,0 +1,211 @@
+use crate::{AssetIo, AssetIoError, ChangeWatcher, Metadata};
+use anyhow::Result;
+use bevy_utils::{BoxedFuture, HashSet};
+use std::path::{Path, PathBuf};
+
+/// I/O implementation layering other asset I/Os, for example to let mods and patches override
+/// the base content of a game.
+///
+/// Layers are added in increasing order of priority: a file is loaded from the last layer which
+/// has it, and a directory lists the entries of the directories of all the layers. Files are
+/// written to the last layer.
+///
+/// ```no_run
+/// # use bevy_asset::{AssetServer, FileAssetIo, OverlayAssetIo};
+/// let asset_io = OverlayAssetIo::default()
+///     .with_layer(FileAssetIo::new("assets", &None))
+///     .with_layer(FileAssetIo::new("mods", &None));
+/// let asset_server = AssetServer::with_boxed_io(Box::new(asset_io));
+/// ```
+#[derive(Default)]
+pub struct OverlayAssetIo {
+    layers: Vec<Box<dyn AssetIo>>,
+}
+
+impl OverlayAssetIo {
+    /// Adds `asset_io` as a layer above the current ones.
+    pub fn with_layer<T: AssetIo>(self, asset_io: T) -> Self {
+        self.with_boxed_layer(Box::new(asset_io))
+    }
+
+    /// Adds the boxed `asset_io` as a layer above the current ones.
+    pub fn with_boxed_layer(mut self, asset_io: Box<dyn AssetIo>) -> Self {
+        self.layers.push(asset_io);
+        self
+    }
+
+    /// Returns the layers, from the lowest to the highest priority.
+    pub fn layers(&self) -> &[Box<dyn AssetIo>] {
+        &self.layers
+    }
+
+    fn top_layer(&self, path: &Path) -> Result<&dyn AssetIo, AssetIoError> {
+        self.layers
+            .last()
+            .map(|layer| &**layer)
+            .ok_or_else(|| AssetIoError::WriteUnsupported(path.to_owned()))
+    }
+}
+
+impl AssetIo for OverlayAssetIo {
+    fn load_path<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<Vec<u8>, AssetIoError>> {
+        Box::pin(async move {
+            for layer in self.layers.iter().rev() {
+                match layer.load_path(path).await {
+                    Err(AssetIoError::NotFound(_)) => continue,
+                    result => return result,
+                }
+            }
+            Err(AssetIoError::NotFound(path.to_owned()))
+        })
+    }
+
+    fn read_directory(
+        &self,
+        path: &Path,
+    ) -> Result<Box<dyn Iterator<Item = PathBuf>>, AssetIoError> {
+        let mut found = false;
+        let mut seen = HashSet::default();
+        let mut entries = Vec::new();
+        for layer in self.layers.iter().rev() {
+            if !layer.is_dir(path) {
+                continue;
+            }
+            found = true;
+            for entry in layer.read_directory(path)? {
+                if seen.insert(entry.clone()) {
+                    entries.push(entry);
+                }
+            }
+        }
+        if !found {
+            return Err(AssetIoError::NotFound(path.to_owned()));
+        }
+        Ok(Box::new(entries.into_iter()))
+    }
+
+    fn get_metadata(&self, path: &Path) -> Result<Metadata, AssetIoError> {
+        for layer in self.layers.iter().rev() {
+            match layer.get_metadata(path) {
+                Err(AssetIoError::NotFound(_)) => continue,
+                result => return result,
+            }
+        }
+        Err(AssetIoError::NotFound(path.to_owned()))
+    }
+
+    fn watch_path_for_changes(
+        &self,
+        to_watch: &Path,
+        to_reload: Option<PathBuf>,
+    ) -> Result<(), AssetIoError> {
+        for layer in &self.layers {
+            match layer.watch_path_for_changes(to_watch, to_reload.clone()) {
+                // the file usually only exists in some of the layers
+                Err(_) if layer.get_metadata(to_watch).is_err() => continue,
+                result => result?,
+            }
+        }
+        Ok(())
+    }
+
+    fn watch_for_changes(&self, configuration: &ChangeWatcher) -> Result<(), AssetIoError> {
+        for layer in &self.layers {
+            layer.watch_for_changes(configuration)?;
+        }
+        Ok(())
+    }
+
+    fn write_path<'a>(
+        &'a self,
+        path: &'a Path,
+        bytes: &'a [u8],
+    ) -> BoxedFuture<'a, Result<(), AssetIoError>> {
+        Box::pin(async move { self.top_layer(path)?.write_path(path, bytes).await })
+    }
+
+    fn rename_path<'a>(
+        &'a self,
+        from: &'a Path,
+        to: &'a Path,
+    ) -> BoxedFuture<'a, Result<(), AssetIoError>> {
+        Box::pin(async move { self.top_layer(from)?.rename_path(from, to).await })
+    }
+
+    fn remove_path<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<(), AssetIoError>> {
+        Box::pin(async move { self.top_layer(path)?.remove_path(path).await })
+    }
+
+    fn create_directory<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<(), AssetIoError>> {
+        Box::pin(async move { self.top_layer(path)?.create_directory(path).await })
+    }
+}
+
+#[cfg(all(test, not(target_arch = "wasm32"), not(target_os = "android")))]
+mod tests {
+    use super::*;
+    use crate::FileAssetIo;
+    use futures_lite::future::block_on;
+    use std::fs;
+
+    fn layer(files: &[(&str, &str)]) -> (tempfile::TempDir, FileAssetIo) {
+        let dir = tempfile::tempdir().unwrap();
+        for (path, contents) in files {
+            let path = dir.path().join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, contents).unwrap();
+        }
+        let asset_io = FileAssetIo::new(dir.path(), &None);
+        (dir, asset_io)
+    }
+
+    #[test]
+    fn override_layers() {
+        let (_base_dir, base) = layer(&[("a.txt", "base a"), ("levels/b.txt", "base b")]);
+        let (mod_dir, mod_layer) = layer(&[("levels/b.txt", "mod b"), ("levels/c.txt", "mod c")]);
+        let asset_io = OverlayAssetIo::default()
+            .with_layer(base)
+            .with_layer(mod_layer);
+
+        let load = |path: &str| block_on(asset_io.load_path(Path::new(path)));
+        assert_eq!(load("a.txt").unwrap(), b"base a");
+        assert_eq!(load("levels/b.txt").unwrap(), b"mod b");
+        assert_eq!(load("levels/c.txt").unwrap(), b"mod c");
+        assert!(matches!(load("d.txt"), Err(AssetIoError::NotFound(_))));
+
+        let mut entries: Vec<PathBuf> = asset_io
+            .read_directory(Path::new("levels"))
+            .unwrap()
+            .collect();
+        entries.sort();
+        assert_eq!(entries, ["levels/b.txt", "levels/c.txt"].map(PathBuf::from));
+        assert!(asset_io.is_dir(Path::new("levels")));
+        assert!(asset_io.read_directory(Path::new("missing")).is_err());
+
+        // files are written to the top layer
+        block_on(asset_io.write_path(Path::new("a.txt"), b"saved a")).unwrap();
+        assert_eq!(fs::read(mod_dir.path().join("a.txt")).unwrap(), b"saved a");
+        assert_eq!(load("a.txt").unwrap(), b"saved a");
+    }
+
+    #[test]
+    #[cfg(feature = "filesystem_watcher")]
+    fn watch_layers() {
+        let (_base_dir, base) = layer(&[("a.txt", "base a")]);
+        let (_mod_dir, mod_layer) = layer(&[("b.txt", "mod b")]);
+        let asset_io = OverlayAssetIo::default()
+            .with_layer(base)
+            .with_layer(mod_layer);
+        asset_io
+            .watch_for_changes(&ChangeWatcher::with_delay(Default::default()).unwrap())
+            .unwrap();
+
+        // files are only watched in the layers which have them
+        asset_io
+            .watch_path_for_changes(Path::new("a.txt"), None)
+            .unwrap();
+        asset_io
+            .watch_path_for_changes(Path::new("b.txt"), None)
+            .unwrap();
+    }
+}
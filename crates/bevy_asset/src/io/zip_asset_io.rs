@@ -0,0 +1,215 @@
+use crate::{AssetIo, AssetIoError, ChangeWatcher, FileType, Metadata};
+use anyhow::Result;
+use bevy_utils::{BoxedFuture, HashMap};
+use parking_lot::Mutex;
+use std::{
+    collections::BTreeSet,
+    io::{self, Read, Seek},
+    path::{Path, PathBuf},
+};
+use zip::{result::ZipError, ZipArchive};
+
+trait ReadSeek: Read + Seek + Send {}
+
+impl<T: Read + Seek + Send> ReadSeek for T {}
+
+/// I/O implementation serving assets from a zip archive.
+///
+/// The paths of the assets are relative to the root of the archive. Directories are listed by
+/// [`AssetIo::read_directory`] even if the archive has no entries for them. The archive is read
+/// only, and never changes, so watching for changes does nothing.
+///
+/// Use an [`OverlayAssetIo`](crate::OverlayAssetIo) to combine several archives, or archives and
+/// loose directories.
+pub struct ZipAssetIo {
+    archive: Mutex<ZipArchive<Box<dyn ReadSeek>>>,
+    files: HashMap<PathBuf, usize>,
+    directories: HashMap<PathBuf, BTreeSet<PathBuf>>,
+}
+
+impl ZipAssetIo {
+    /// Creates a new `ZipAssetIo` reading the zip archive from `reader`.
+    pub fn new<R: Read + Seek + Send + 'static>(reader: R) -> Result<Self, AssetIoError> {
+        let mut archive =
+            ZipArchive::new(Box::new(reader) as Box<dyn ReadSeek>).map_err(zip_error)?;
+
+        let mut files = HashMap::default();
+        let mut directories: HashMap<PathBuf, BTreeSet<PathBuf>> = HashMap::default();
+        directories.insert(PathBuf::new(), BTreeSet::new());
+        for index in 0..archive.len() {
+            // only reads the header of the entry, without decompressing it
+            let name = archive
+                .by_index_raw(index)
+                .map_err(zip_error)?
+                .name()
+                .to_owned();
+            // skip the entries which would escape the root of the archive
+            let Some(path) = entry_path(&name) else {
+                continue;
+            };
+            if name.ends_with('/') {
+                directories.entry(path.clone()).or_default();
+            } else {
+                files.insert(path.clone(), index);
+            }
+
+            // register the path in its parent directories, creating them if needed
+            let mut child = path;
+            while let Some(parent) = child.parent() {
+                let parent = parent.to_owned();
+                let children = directories.entry(parent.clone()).or_default();
+                let inserted = children.insert(child);
+                if !inserted {
+                    break;
+                }
+                child = parent;
+            }
+        }
+
+        Ok(Self {
+            archive: Mutex::new(archive),
+            files,
+            directories,
+        })
+    }
+
+    /// Creates a new `ZipAssetIo` reading the zip archive from the file at `path`.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, AssetIoError> {
+        let path = path.as_ref();
+        let file = std::fs::File::open(path).map_err(|e| {
+            if e.kind() == io::ErrorKind::NotFound {
+                AssetIoError::NotFound(path.to_owned())
+            } else {
+                e.into()
+            }
+        })?;
+        Self::new(io::BufReader::new(file))
+    }
+}
+
+/// Returns the path of an entry of the archive, or `None` if it isn't relative to the root.
+fn entry_path(name: &str) -> Option<PathBuf> {
+    let path = Path::new(name.trim_end_matches('/'));
+    let relative = path.components().all(|component| {
+        matches!(
+            component,
+            std::path::Component::Normal(_) | std::path::Component::CurDir
+        )
+    });
+    (relative && !name.is_empty()).then(|| path.components().collect())
+}
+
+fn zip_error(error: ZipError) -> AssetIoError {
+    match error {
+        ZipError::Io(error) => AssetIoError::Io(error),
+        error => AssetIoError::Io(io::Error::new(io::ErrorKind::InvalidData, error)),
+    }
+}
+
+impl AssetIo for ZipAssetIo {
+    fn load_path<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<Vec<u8>, AssetIoError>> {
+        Box::pin(async move {
+            let index = *self
+                .files
+                .get(path)
+                .ok_or_else(|| AssetIoError::NotFound(path.to_owned()))?;
+            let mut archive = self.archive.lock();
+            let mut file = archive.by_index(index).map_err(zip_error)?;
+            // the size in the header of the archive isn't trusted to allocate the buffer upfront
+            let mut bytes = Vec::new();
+            file.read_to_end(&mut bytes)?;
+            Ok(bytes)
+        })
+    }
+
+    fn read_directory(
+        &self,
+        path: &Path,
+    ) -> Result<Box<dyn Iterator<Item = PathBuf>>, AssetIoError> {
+        let children = self
+            .directories
+            .get(path)
+            .ok_or_else(|| AssetIoError::NotFound(path.to_owned()))?;
+        Ok(Box::new(children.clone().into_iter()))
+    }
+
+    fn get_metadata(&self, path: &Path) -> Result<Metadata, AssetIoError> {
+        if self.files.contains_key(path) {
+            Ok(Metadata::new(FileType::File))
+        } else if self.directories.contains_key(path) {
+            Ok(Metadata::new(FileType::Directory))
+        } else {
+            Err(AssetIoError::NotFound(path.to_owned()))
+        }
+    }
+
+    fn watch_path_for_changes(
+        &self,
+        _to_watch: &Path,
+        _to_reload: Option<PathBuf>,
+    ) -> Result<(), AssetIoError> {
+        Ok(())
+    }
+
+    fn watch_for_changes(&self, _configuration: &ChangeWatcher) -> Result<(), AssetIoError> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_lite::future::block_on;
+    use std::io::{Cursor, Write};
+    use zip::{write::FileOptions, ZipWriter};
+
+    fn archive(files: &[(&str, &str)]) -> ZipAssetIo {
+        let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+        for (name, contents) in files {
+            if name.ends_with('/') {
+                writer.add_directory(*name, FileOptions::default()).unwrap();
+            } else {
+                writer.start_file(*name, FileOptions::default()).unwrap();
+                writer.write_all(contents.as_bytes()).unwrap();
+            }
+        }
+        ZipAssetIo::new(writer.finish().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn read_archive() {
+        let asset_io = archive(&[
+            ("a.txt", "a"),
+            ("empty/", ""),
+            ("textures/b.png", "b"),
+            ("textures/c/d.png", "d"),
+            ("../escape.txt", ""),
+        ]);
+
+        assert_eq!(
+            block_on(asset_io.load_path(Path::new("textures/c/d.png"))).unwrap(),
+            b"d"
+        );
+        assert!(matches!(
+            block_on(asset_io.load_path(Path::new("textures"))),
+            Err(AssetIoError::NotFound(_))
+        ));
+
+        let read_directory = |path: &str| -> Vec<PathBuf> {
+            asset_io.read_directory(Path::new(path)).unwrap().collect()
+        };
+        assert_eq!(
+            read_directory(""),
+            ["a.txt", "empty", "textures"].map(PathBuf::from)
+        );
+        assert_eq!(
+            read_directory("textures"),
+            ["textures/b.png", "textures/c"].map(PathBuf::from)
+        );
+        assert!(read_directory("empty").is_empty());
+
+        assert!(asset_io.is_dir(Path::new("textures/c")));
+        assert!(asset_io.is_file(Path::new("textures/b.png")));
+        assert!(!asset_io.is_file(Path::new("../escape.txt")));
+    }
+}
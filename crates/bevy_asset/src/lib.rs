@@ -16,6 +16,7 @@ mod assets;
 #[cfg(feature = "debug_asset_server")]
 pub mod debug_asset_server;
 pub mod diagnostic;
+mod embedded;
 #[cfg(all(
     feature = "filesystem_watcher",
     all(not(target_arch = "wasm32"), not(target_os = "android"))
@@ -25,8 +26,11 @@ mod handle;
 mod info;
 mod io;
 mod loader;
+mod meta;
 mod path;
+mod processor;
 mod reflect;
+mod saver;
 
 /// The `bevy_asset` prelude.
 pub mod prelude {
@@ -40,15 +44,20 @@ pub use anyhow::Error;
 pub use asset_server::*;
 pub use assets::*;
 pub use bevy_utils::BoxedFuture;
+pub use embedded::*;
 pub use handle::*;
 pub use info::*;
 pub use io::*;
 pub use loader::*;
 pub use path::*;
+pub use processor::*;
 pub use reflect::*;
+pub use saver::*;
 
 use bevy_app::{prelude::*, MainScheduleOrder};
 use bevy_ecs::schedule::ScheduleLabel;
+use bevy_log::{error, info};
+use bevy_tasks::IoTaskPool;
 use bevy_utils::Duration;
 
 /// Asset storages are updated.
@@ -93,6 +102,13 @@ pub struct AssetPlugin {
     /// Whether to watch for changes in asset files. Requires the `filesystem_watcher` feature,
     /// and cannot be supported on the wasm32 arch nor android os.
     pub watch_for_changes: Option<ChangeWatcher>,
+    /// Whether assets are loaded from their processed artifacts, and processed on startup.
+    ///
+    /// See [`AssetProcessor`] for more details.
+    pub mode: AssetMode,
+    /// The base folder where processed assets are written to and loaded from, relative to the
+    /// executable. Unused in [`AssetMode::Unprocessed`].
+    pub processed_asset_folder: String,
 }
 
 impl Default for AssetPlugin {
@@ -100,6 +116,8 @@ impl Default for AssetPlugin {
         Self {
             asset_folder: "assets".to_string(),
             watch_for_changes: None,
+            mode: AssetMode::Unprocessed,
+            processed_asset_folder: "imported_assets".to_string(),
         }
     }
 }
@@ -119,6 +137,19 @@ impl AssetPlugin {
 
         Box::new(source)
     }
+
+    /// Creates an instance of the platform's default [`AssetIo`] for processed assets, in the
+    /// [`processed_asset_folder`](AssetPlugin::processed_asset_folder).
+    pub fn create_platform_default_processed_asset_io(&self) -> Box<dyn AssetIo> {
+        #[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
+        let source = FileAssetIo::new(&self.processed_asset_folder, &None);
+        #[cfg(target_arch = "wasm32")]
+        let source = WasmAssetIo::new(&self.processed_asset_folder);
+        #[cfg(target_os = "android")]
+        let source = AndroidAssetIo::new(&self.processed_asset_folder);
+
+        Box::new(source)
+    }
 }
 
 impl Plugin for AssetPlugin {
@@ -129,6 +160,14 @@ impl Plugin for AssetPlugin {
             app.insert_resource(asset_server);
         }
 
+        if self.mode != AssetMode::Unprocessed {
+            let asset_server = app.world.resource::<AssetServer>();
+            if asset_server.server.processed_asset_io.read().is_none() {
+                asset_server
+                    .set_processed_asset_io(self.create_platform_default_processed_asset_io());
+            }
+        }
+
         app.register_type::<HandleId>();
         app.register_type::<AssetPath>();
 
@@ -136,6 +175,8 @@ impl Plugin for AssetPlugin {
         app.init_schedule(LoadAssets);
         app.init_schedule(AssetEvents);
 
+        app.add_systems(LoadAssets, io::memory_asset_io_system);
+
         #[cfg(all(
             feature = "filesystem_watcher",
             all(not(target_arch = "wasm32"), not(target_os = "android"))
@@ -146,4 +187,19 @@ impl Plugin for AssetPlugin {
         order.insert_after(First, LoadAssets);
         order.insert_after(PostUpdate, AssetEvents);
     }
+
+    fn finish(&self, app: &mut App) {
+        // all the processors were added while building the plugins
+        if self.mode == AssetMode::Processing {
+            let asset_server = app.world.resource::<AssetServer>().clone();
+            IoTaskPool::get()
+                .spawn(async move {
+                    match asset_server.process_folder("").await {
+                        Ok(processed) => info!("processed {processed} assets"),
+                        Err(err) => error!("failed to process assets: {err}"),
+                    }
+                })
+                .detach();
+        }
+    }
 }
@@ -1,16 +1,16 @@
 use crate::{
-    path::AssetPath, AssetIo, AssetIoError, AssetMeta, AssetServer, Assets, Handle, HandleId,
-    HandleUntyped, RefChangeChannel,
+    meta::Meta, path::AssetPath, AssetIo, AssetIoError, AssetMeta, AssetServer, Assets, Handle,
+    HandleId, HandleUntyped, RefChangeChannel,
 };
-use anyhow::Error;
-use anyhow::Result;
+use anyhow::{anyhow, Error, Result};
 use bevy_ecs::system::{Res, ResMut};
 use bevy_reflect::TypePath;
 use bevy_reflect::{TypeUuid, TypeUuidDynamic};
 use bevy_utils::{BoxedFuture, HashMap};
 use crossbeam_channel::{Receiver, Sender};
 use downcast_rs::{impl_downcast, Downcast};
-use std::path::Path;
+use serde::de::DeserializeOwned;
+use std::{any::Any, path::Path, sync::Arc};
 
 /// A loader for an asset source.
 ///
@@ -122,25 +122,31 @@ impl<T: Asset> From<LoadedAsset<T>> for BoxedLoadedAsset {
 /// [`LoadContext::set_labeled_asset`].
 pub struct LoadContext<'a> {
     pub(crate) ref_change_channel: &'a RefChangeChannel,
+    pub(crate) asset_server: &'a AssetServer,
     pub(crate) asset_io: &'a dyn AssetIo,
     pub(crate) labeled_assets: HashMap<Option<String>, BoxedLoadedAsset>,
     pub(crate) path: &'a Path,
     pub(crate) version: usize,
+    pub(crate) meta: Option<Meta>,
+    pub(crate) settings_override: Option<Arc<dyn Any + Send + Sync>>,
 }
 
 impl<'a> LoadContext<'a> {
     pub(crate) fn new(
         path: &'a Path,
-        ref_change_channel: &'a RefChangeChannel,
+        asset_server: &'a AssetServer,
         asset_io: &'a dyn AssetIo,
         version: usize,
     ) -> Self {
         Self {
-            ref_change_channel,
+            ref_change_channel: &asset_server.server.asset_ref_counter.channel,
+            asset_server,
             asset_io,
             labeled_assets: Default::default(),
             version,
             path,
+            meta: None,
+            settings_override: None,
         }
     }
 
@@ -149,6 +155,41 @@ impl<'a> LoadContext<'a> {
         self.path
     }
 
+    /// Gets the settings of the asset loader for this asset.
+    ///
+    /// The settings given to [`AssetServer::load_with_settings`] are used first, then those of
+    /// the `settings` field of the `.meta` file of the asset, deserialized as `S`. Otherwise, the
+    /// default settings are returned.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the settings given to [`AssetServer::load_with_settings`] aren't of type `S`, or
+    /// if the settings of the `.meta` file can't be deserialized as `S`.
+    pub fn settings<S>(&self) -> Result<S, Error>
+    where
+        S: DeserializeOwned + Default + Clone + Send + Sync + 'static,
+    {
+        if let Some(settings) = &self.settings_override {
+            return settings.downcast_ref::<S>().cloned().ok_or_else(|| {
+                anyhow!(
+                    "the settings of {} aren't of type `{}`",
+                    self.path.display(),
+                    std::any::type_name::<S>()
+                )
+            });
+        }
+        match &self.meta {
+            Some(meta) => meta.settings().map_err(|err| {
+                anyhow!(
+                    "invalid settings in the meta file of {}: {}",
+                    self.path.display(),
+                    err
+                )
+            }),
+            None => Ok(S::default()),
+        }
+    }
+
     /// Returns `true` if the load context contains an asset with the specified label.
     pub fn has_labeled_asset(&self, label: &str) -> bool {
         self.labeled_assets.contains_key(&Some(label.to_string()))
@@ -177,12 +218,16 @@ impl<'a> LoadContext<'a> {
         HandleUntyped::strong(id.into(), self.ref_change_channel.sender.clone())
     }
 
-    /// Reads the contents of the file at the specified path through the [`AssetIo`] associated
-    /// with this context.
+    /// Reads the contents of the file at the specified path, the same way the [`AssetServer`]
+    /// reads the files of the assets it loads.
+    ///
+    /// Paths starting with [`EMBEDDED_SOURCE`](crate::EMBEDDED_SOURCE) are read from the
+    /// [embedded assets](AssetServer::embedded_assets), other paths are read through the
+    /// [`AssetIo`] of the server.
     pub async fn read_asset_bytes<P: AsRef<Path>>(&self, path: P) -> Result<Vec<u8>, AssetIoError> {
-        self.asset_io
-            .watch_path_for_changes(path.as_ref(), Some(self.path.to_owned()))?;
-        self.asset_io.load_path(path.as_ref()).await
+        let (asset_io, path) = self.asset_server.source_asset_io(path.as_ref());
+        asset_io.watch_path_for_changes(path, Some(self.path.to_owned()))?;
+        asset_io.load_path(path).await
     }
 
     /// Generates metadata for the assets managed by this load context.
@@ -0,0 +1,301 @@
+use crate::path::append_extension;
+use ron::{error::SpannedError, extensions::Extensions, Options};
+use serde::{de::DeserializeOwned, Deserialize};
+use std::path::{Path, PathBuf};
+
+/// The contents of the `.meta` sidecar file of an asset.
+///
+/// A `.meta` file is a RON file next to the asset source, at the path of the asset followed by
+/// `.meta`, with two optional fields: the type name of the [`AssetLoader`](crate::AssetLoader)
+/// loading the asset, replacing the one chosen from its extension, and the settings of the
+/// loader, read with [`LoadContext::settings`](crate::LoadContext::settings).
+///
+/// ```ron
+/// (
+///     loader: "bevy_render::texture::image_texture_loader::ImageTextureLoader",
+///     settings: (is_srgb: false, sampler: Nearest),
+/// )
+/// ```
+pub(crate) struct Meta {
+    pub(crate) loader: Option<String>,
+    pub(crate) bytes: Vec<u8>,
+}
+
+#[derive(Deserialize)]
+struct MetaLoader {
+    #[serde(default)]
+    loader: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct MetaSettings<S: Default> {
+    #[serde(default)]
+    settings: S,
+}
+
+/// Returns the path of the `.meta` file of the asset at `path`.
+pub(crate) fn meta_path(path: &Path) -> PathBuf {
+    append_extension(path, "meta")
+}
+
+fn options() -> Options {
+    // `loader: "name"` reads better than `loader: Some("name")`
+    Options::default().with_default_extension(Extensions::IMPLICIT_SOME)
+}
+
+impl Meta {
+    /// Parses the `.meta` file `bytes`, only deserializing its loader. Its settings are
+    /// deserialized by [`Meta::settings`], once their type is known.
+    pub(crate) fn parse(bytes: Vec<u8>) -> Result<Self, SpannedError> {
+        let MetaLoader { loader } = options().from_bytes(&bytes)?;
+        Ok(Self { loader, bytes })
+    }
+
+    /// Deserializes the loader settings of the `.meta` file, or returns their default value if
+    /// it has none.
+    pub(crate) fn settings<S: DeserializeOwned + Default>(&self) -> Result<S, SpannedError> {
+        let MetaSettings { settings } = options().from_bytes(&self.bytes)?;
+        Ok(settings)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        update_asset_storage_system, AssetIo, AssetIoError, AssetLoader, AssetServer, Assets,
+        ChangeWatcher, FileAssetIo, Handle, LoadContext, LoadState, LoadedAsset, MemoryAssetIo,
+        Metadata,
+    };
+    use anyhow::Error;
+    use bevy_app::{App, Update};
+    use bevy_reflect::{TypePath, TypeUuid};
+    use bevy_tasks::IoTaskPool;
+    use bevy_utils::BoxedFuture;
+    use serde::Deserialize;
+    use std::{
+        fs,
+        path::{Path, PathBuf},
+    };
+
+    #[derive(Deserialize, Debug, Clone, PartialEq)]
+    #[serde(default)]
+    struct Settings {
+        scale: u32,
+        label: Option<String>,
+    }
+
+    impl Default for Settings {
+        fn default() -> Self {
+            Self {
+                scale: 1,
+                label: None,
+            }
+        }
+    }
+
+    #[derive(Debug, TypeUuid, TypePath)]
+    #[uuid = "0f4b4a0e-5b2a-4a8b-a3b5-2d1c4b8e8f11"]
+    struct Loaded {
+        loader: &'static str,
+        settings: Settings,
+    }
+
+    struct SettingsLoader;
+
+    impl AssetLoader for SettingsLoader {
+        fn load<'a>(
+            &'a self,
+            _: &'a [u8],
+            load_context: &'a mut LoadContext,
+        ) -> BoxedFuture<'a, Result<(), Error>> {
+            Box::pin(async move {
+                let settings = load_context.settings::<Settings>()?;
+                load_context.set_default_asset(LoadedAsset::new(Loaded {
+                    loader: "settings",
+                    settings,
+                }));
+                Ok(())
+            })
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["txt"]
+        }
+    }
+
+    struct OtherLoader;
+
+    impl AssetLoader for OtherLoader {
+        fn load<'a>(
+            &'a self,
+            _: &'a [u8],
+            load_context: &'a mut LoadContext,
+        ) -> BoxedFuture<'a, Result<(), Error>> {
+            load_context.set_default_asset(LoadedAsset::new(Loaded {
+                loader: "other",
+                settings: Settings::default(),
+            }));
+            Box::pin(async move { Ok(()) })
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["other"]
+        }
+    }
+
+    fn setup(files: &[(&str, &str)]) -> (tempfile::TempDir, App) {
+        let dir = tempfile::tempdir().unwrap();
+        for (path, contents) in files {
+            fs::write(dir.path().join(path), contents).unwrap();
+        }
+
+        let app = app(FileAssetIo::new(dir.path(), &None));
+        (dir, app)
+    }
+
+    fn app(asset_io: impl AssetIo) -> App {
+        IoTaskPool::init(Default::default);
+        let asset_server = AssetServer::new(asset_io);
+        asset_server.add_loader(SettingsLoader);
+        asset_server.add_loader(OtherLoader);
+
+        let mut app = App::new();
+        app.insert_resource(asset_server.register_asset_type::<Loaded>())
+            .insert_resource(asset_server)
+            .add_systems(Update, update_asset_storage_system::<Loaded>);
+        app
+    }
+
+    fn load(app: &mut App, path: &str) -> Handle<Loaded> {
+        let asset_server = app.world.resource::<AssetServer>().clone();
+        let id = futures_lite::future::block_on(asset_server.load_async(path.into(), true));
+        app.update();
+        asset_server.get_handle(id.unwrap())
+    }
+
+    fn loaded<'a>(app: &'a App, handle: &Handle<Loaded>) -> &'a Loaded {
+        app.world.resource::<Assets<Loaded>>().get(handle).unwrap()
+    }
+
+    #[test]
+    fn meta_settings() {
+        let (_dir, mut app) = setup(&[
+            ("a.txt", ""),
+            ("a.txt.meta", "(settings: (scale: 2, label: \"a\"))"),
+            ("b.txt", ""),
+            ("c.txt", ""),
+            (
+                "c.txt.meta",
+                "(loader: \"bevy_asset::meta::tests::OtherLoader\")",
+            ),
+        ]);
+
+        let a = load(&mut app, "a.txt");
+        assert_eq!(
+            loaded(&app, &a).settings,
+            Settings {
+                scale: 2,
+                label: Some("a".to_string())
+            }
+        );
+
+        // assets without a `.meta` file are loaded with the default settings
+        let b = load(&mut app, "b.txt");
+        assert_eq!(loaded(&app, &b).settings, Settings::default());
+
+        // the loader of the `.meta` file replaces the loader of the extension
+        let c = load(&mut app, "c.txt");
+        assert_eq!(loaded(&app, &c).loader, "other");
+    }
+
+    #[test]
+    fn invalid_meta() {
+        let (_dir, mut app) = setup(&[
+            ("a.txt", ""),
+            ("a.txt.meta", "(loader: \"missing\")"),
+            ("b.txt", ""),
+            ("b.txt.meta", "(settings: (scale: \"2\"))"),
+        ]);
+        let asset_server = app.world.resource::<AssetServer>().clone();
+
+        let a = futures_lite::future::block_on(asset_server.load_async("a.txt".into(), false));
+        assert_eq!(
+            a.unwrap_err().to_string(),
+            "no `AssetLoader` named `missing`"
+        );
+
+        let b = futures_lite::future::block_on(asset_server.load_async("b.txt".into(), false));
+        assert!(b.is_err());
+        app.update();
+        assert_eq!(asset_server.get_load_state("b.txt"), LoadState::Failed);
+    }
+
+    #[test]
+    fn override_settings() {
+        let (_dir, mut app) = setup(&[("a.txt", ""), ("a.txt.meta", "(settings: (scale: 2))")]);
+        let asset_server = app.world.resource::<AssetServer>().clone();
+
+        let settings = Settings {
+            scale: 3,
+            label: None,
+        };
+        let handle: Handle<Loaded> = asset_server.load_with_settings("a.txt", settings.clone());
+        // the asset is loaded in the background
+        while asset_server.get_load_state(&handle) != LoadState::Loaded {
+            std::thread::sleep(std::time::Duration::from_millis(1));
+            app.update();
+        }
+        assert_eq!(loaded(&app, &handle).settings, settings);
+        assert!(asset_server.server.settings_overrides.read().is_empty());
+    }
+
+    /// Answers reads of missing files with an error page, like some web servers.
+    struct ErrorPageAssetIo(MemoryAssetIo);
+
+    impl AssetIo for ErrorPageAssetIo {
+        fn load_path<'a>(
+            &'a self,
+            path: &'a Path,
+        ) -> BoxedFuture<'a, Result<Vec<u8>, AssetIoError>> {
+            Box::pin(async move {
+                match self.0.load_path(path).await {
+                    Err(AssetIoError::NotFound(_)) => Ok(b"<html>Not Found</html>".to_vec()),
+                    result => result,
+                }
+            })
+        }
+
+        fn read_directory(
+            &self,
+            path: &Path,
+        ) -> Result<Box<dyn Iterator<Item = PathBuf>>, AssetIoError> {
+            self.0.read_directory(path)
+        }
+
+        fn get_metadata(&self, path: &Path) -> Result<Metadata, AssetIoError> {
+            self.0.get_metadata(path)
+        }
+
+        fn watch_path_for_changes(
+            &self,
+            to_watch: &Path,
+            to_reload: Option<PathBuf>,
+        ) -> Result<(), AssetIoError> {
+            self.0.watch_path_for_changes(to_watch, to_reload)
+        }
+
+        fn watch_for_changes(&self, configuration: &ChangeWatcher) -> Result<(), AssetIoError> {
+            self.0.watch_for_changes(configuration)
+        }
+    }
+
+    #[test]
+    fn missing_meta_read_as_error_page() {
+        let mut app = app(ErrorPageAssetIo(
+            MemoryAssetIo::default().with_file("a.txt", b"".as_slice()),
+        ));
+
+        let a = load(&mut app, "a.txt");
+        assert_eq!(loaded(&app, &a).settings, Settings::default());
+    }
+}
@@ -155,6 +155,14 @@ pub(crate) fn get_hasher() -> AHasher {
     RandomState::with_seeds(42, 23, 13, 8).build_hasher()
 }
 
+/// Appends `.extension` to `path`, keeping its existing extensions.
+pub(crate) fn append_extension(path: &Path, extension: &str) -> PathBuf {
+    let mut path = path.as_os_str().to_owned();
+    path.push(".");
+    path.push(extension);
+    path.into()
+}
+
 impl<'a, T> From<T> for AssetPathId
 where
     T: Into<AssetPath<'a>>,
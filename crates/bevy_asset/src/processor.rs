@@ -0,0 +1,605 @@
+use crate::{path::append_extension, AssetIo, AssetIoError, AssetServer, AssetServerError};
+use anyhow::Error;
+use bevy_log::warn;
+use bevy_utils::BoxedFuture;
+use serde::{Deserialize, Serialize};
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+/// A processor for an asset source.
+///
+/// Processors transform asset sources ahead of time into artifacts which are faster to load, for
+/// example by compressing images or converting meshes into a compact binary format. When the
+/// [`AssetServer`] processes assets, the source of each asset with one of the supported
+/// [`extensions`](AssetProcessor::extensions) is passed to the processor, and the returned bytes
+/// are written to the processed asset I/O, at the path of the source followed by the
+/// [`processed_extension`](AssetProcessor::processed_extension).
+///
+/// An asset is only processed again when its source, a dependency read with
+/// [`ProcessContext::read_dependency`], or the [`name`](AssetProcessor::name) or
+/// [`version`](AssetProcessor::version) of its processor changes. An artifact whose source changed is also processed again when it is loaded, for
+/// example when it is hot reloaded.
+///
+/// When an asset was processed, [`AssetServer::load`] loads the artifact with the
+/// [`AssetLoader`](crate::AssetLoader) of the processed extension instead of the source.
+pub trait AssetProcessor: Send + Sync + 'static {
+    /// Processes the asset source in an asynchronous closure, returning the processed artifact.
+    fn process<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        process_context: &'a mut ProcessContext,
+    ) -> BoxedFuture<'a, Result<Vec<u8>, Error>>;
+
+    /// Returns a list of extensions supported by this asset processor, without the preceding dot.
+    fn extensions(&self) -> &[&str];
+
+    /// Returns the name identifying this processor in the records of the processed assets.
+    ///
+    /// The name should stay the same across builds, unlike type names, as assets processed by a
+    /// processor with a different name are processed again.
+    fn name(&self) -> &str;
+
+    /// Returns the extension appended to the path of the processed artifacts, without the
+    /// preceding dot.
+    fn processed_extension(&self) -> &str;
+
+    /// Returns the version of the processor's output. Increasing it reprocesses all the assets
+    /// processed by an older version.
+    fn version(&self) -> u32 {
+        0
+    }
+}
+
+/// An asynchronous context where an asset source is processed.
+///
+/// The process context is created by the [`AssetServer`] and passed to the [`AssetProcessor`] of
+/// the asset. It keeps track of the dependencies read while processing, to process the asset
+/// again when one of them changes.
+pub struct ProcessContext<'a> {
+    path: &'a Path,
+    asset_io: &'a dyn AssetIo,
+    dependencies: Vec<(PathBuf, u64)>,
+}
+
+impl<'a> ProcessContext<'a> {
+    /// Gets the source path of the processed asset.
+    pub fn path(&self) -> &Path {
+        self.path
+    }
+
+    /// Gets the asset I/O the asset sources are read from.
+    pub fn asset_io(&self) -> &dyn AssetIo {
+        self.asset_io
+    }
+
+    /// Reads the contents of the file at the specified path through the source [`AssetIo`], and
+    /// records it as a dependency of the processed asset.
+    pub async fn read_dependency<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+    ) -> Result<Vec<u8>, AssetIoError> {
+        let bytes = self.asset_io.load_path(path.as_ref()).await?;
+        self.dependencies
+            .push((path.as_ref().to_owned(), content_hash(&bytes)));
+        Ok(bytes)
+    }
+}
+
+/// Where the [`AssetServer`] loads assets from, and whether it processes them.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AssetMode {
+    /// Assets are loaded from their sources, and never processed.
+    #[default]
+    Unprocessed,
+    /// Assets are loaded from their processed artifacts when they exist and were processed from
+    /// their current sources, and from their sources otherwise.
+    Processed,
+    /// Assets are processed in the background on startup if they changed, and loaded as in
+    /// [`AssetMode::Processed`]. Assets whose sources change while running are processed again
+    /// when they are reloaded.
+    Processing,
+}
+
+/// The outcome of [`AssetServer::process_asset`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ProcessResult {
+    /// The asset was processed, and its artifact was written.
+    Processed,
+    /// The artifact of the asset was processed from the same inputs, and was kept.
+    UpToDate,
+    /// No processor supports the asset.
+    Skipped,
+}
+
+/// The inputs an artifact was processed from, stored next to it in the processed asset I/O.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub(crate) struct ProcessRecord {
+    processor: String,
+    version: u32,
+    hash: u64,
+    output: PathBuf,
+    dependencies: Vec<(PathBuf, u64)>,
+}
+
+/// Loads the record at `record_path`, if it exists and is valid.
+async fn load_record(
+    processed_asset_io: &dyn AssetIo,
+    record_path: &Path,
+) -> Option<ProcessRecord> {
+    let record = processed_asset_io.load_path(record_path).await.ok()?;
+    ron::de::from_bytes(&record).ok()
+}
+
+/// Hashes the contents of an asset file.
+///
+/// The hashes are stored in the processed asset I/O, so this uses FNV-1a, which is stable across
+/// runs and platforms, rather than the randomly seeded hashers of the standard library.
+fn content_hash(bytes: &[u8]) -> u64 {
+    extend_hash(0xcbf2_9ce4_8422_2325, bytes)
+}
+
+/// Continues the [`content_hash`] `hash` with more bytes.
+fn extend_hash(hash: u64, bytes: &[u8]) -> u64 {
+    bytes.iter().fold(hash, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// Returns the path of the record of the asset at `path` in the processed asset I/O.
+fn record_path(path: &Path) -> PathBuf {
+    append_extension(path, "processed")
+}
+
+impl AssetServer {
+    /// Adds the provided asset processor to the server.
+    ///
+    /// If `processor` has one or more supported extensions in conflict with processors that came
+    /// before it, it will replace them.
+    pub fn add_processor<T>(&self, processor: T)
+    where
+        T: AssetProcessor,
+    {
+        let processor: Arc<dyn AssetProcessor> = Arc::new(processor);
+        let mut processors = self.server.processors.write();
+        for extension in processor.extensions() {
+            processors.insert(extension.to_string(), processor.clone());
+        }
+    }
+
+    /// Sets the asset I/O where processed assets are written to and loaded from.
+    ///
+    /// Until it is set, assets are always loaded from their sources.
+    pub fn set_processed_asset_io(&self, asset_io: Box<dyn AssetIo>) {
+        *self.server.processed_asset_io.write() = Some(Arc::from(asset_io));
+    }
+
+    fn get_path_processor(&self, path: &Path) -> Option<Arc<dyn AssetProcessor>> {
+        let file_name = path.file_name()?.to_str()?.to_lowercase();
+        let processors = self.server.processors.read();
+        let mut extension = file_name.as_str();
+        while let Some(index) = extension.find('.') {
+            extension = &extension[index + 1..];
+            if let Some(processor) = processors.get(extension) {
+                return Some(processor.clone());
+            }
+        }
+        None
+    }
+
+    /// Processes the asset at the provided path with the [`AssetProcessor`] of its extension,
+    /// unless its artifact is up to date.
+    ///
+    /// # Errors
+    ///
+    /// - If no processed asset I/O was [set](AssetServer::set_processed_asset_io), it will fail
+    ///   with [`AssetServerError::MissingProcessedAssetIo`].
+    /// - If the processor fails, it will fail with [`AssetServerError::AssetProcessorError`].
+    /// - If reading the source or writing the artifact fails, it will fail with
+    ///   [`AssetServerError::AssetIoError`].
+    pub async fn process_asset<P: AsRef<Path>>(
+        &self,
+        path: P,
+    ) -> Result<ProcessResult, AssetServerError> {
+        let path = path.as_ref();
+        let processed_asset_io = self
+            .server
+            .processed_asset_io
+            .read()
+            .clone()
+            .ok_or(AssetServerError::MissingProcessedAssetIo)?;
+        let Some(processor) = self.get_path_processor(path) else {
+            return Ok(ProcessResult::Skipped);
+        };
+
+        let bytes = self.asset_io().load_path(path).await?;
+        let hash = self.source_hash(path, &bytes).await?;
+        let record_path = record_path(path);
+        if let Some(record) = load_record(&*processed_asset_io, &record_path).await {
+            if record.processor == processor.name()
+                && record.version == processor.version()
+                && record.hash == hash
+                && processed_asset_io.is_file(&record.output)
+                && self.dependencies_unchanged(&record).await
+            {
+                return Ok(ProcessResult::UpToDate);
+            }
+        }
+
+        let mut process_context = ProcessContext {
+            path,
+            asset_io: self.asset_io(),
+            dependencies: Vec::new(),
+        };
+        let processed = processor
+            .process(&bytes, &mut process_context)
+            .await
+            .map_err(AssetServerError::AssetProcessorError)?;
+
+        // the record is written last, so that an interrupted processing never leaves a record
+        // matching an outdated artifact
+        let output = append_extension(path, processor.processed_extension());
+        processed_asset_io.write_path(&output, &processed).await?;
+        let record = ProcessRecord {
+            processor: processor.name().to_string(),
+            version: processor.version(),
+            hash,
+            output,
+            dependencies: process_context.dependencies,
+        };
+        let record = ron::ser::to_string_pretty(&record, Default::default())
+            .expect("process records can be serialized");
+        processed_asset_io
+            .write_path(&record_path, record.as_bytes())
+            .await?;
+        Ok(ProcessResult::Processed)
+    }
+
+    /// Processes the assets in the specified folder recursively, and returns the number of assets
+    /// which were processed again.
+    ///
+    /// Assets failing to process are logged and skipped.
+    ///
+    /// # Errors
+    ///
+    /// - If the provided path is not a directory, it will fail with
+    ///   [`AssetServerError::AssetFolderNotADirectory`].
+    /// - If no processed asset I/O was [set](AssetServer::set_processed_asset_io), it will fail
+    ///   with [`AssetServerError::MissingProcessedAssetIo`].
+    pub async fn process_folder<P: AsRef<Path>>(&self, path: P) -> Result<usize, AssetServerError> {
+        let path = path.as_ref();
+        if !self.asset_io().is_dir(path) {
+            return Err(AssetServerError::AssetFolderNotADirectory(
+                path.to_str().unwrap().to_string(),
+            ));
+        }
+        if self.server.processed_asset_io.read().is_none() {
+            return Err(AssetServerError::MissingProcessedAssetIo);
+        }
+
+        let mut processed = 0;
+        let mut folders = vec![path.to_owned()];
+        while let Some(folder) = folders.pop() {
+            // collected so that the future stays `Send` across the awaits below
+            let children: Vec<PathBuf> = self.asset_io().read_directory(&folder)?.collect();
+            for child_path in children {
+                if self.asset_io().is_dir(&child_path) {
+                    folders.push(child_path);
+                    continue;
+                }
+                match self.process_asset(&child_path).await {
+                    Ok(ProcessResult::Processed) => processed += 1,
+                    Ok(ProcessResult::UpToDate | ProcessResult::Skipped) => {}
+                    Err(err) => warn!("failed to process {}: {}", child_path.display(), err),
+                }
+            }
+        }
+        Ok(processed)
+    }
+
+    /// Loads the artifact of the asset at `path` if it was processed from its current source,
+    /// returning its path and contents.
+    ///
+    /// If the source changed since it was processed, for example when it is hot reloaded, the asset
+    /// is processed again if a processor was added for it.
+    pub(crate) async fn load_processed(&self, path: &Path) -> Option<(PathBuf, Vec<u8>)> {
+        let processed_asset_io = self.server.processed_asset_io.read().clone()?;
+        let record_path = record_path(path);
+        let mut record = load_record(&*processed_asset_io, &record_path).await?;
+        if !self.source_unchanged(path, &record).await {
+            self.get_path_processor(path)?;
+            if let Err(err) = self.process_asset(path).await {
+                warn!("failed to process {}: {}", path.display(), err);
+                return None;
+            }
+            record = load_record(&*processed_asset_io, &record_path).await?;
+        }
+        let bytes = processed_asset_io.load_path(&record.output).await.ok()?;
+        Some((record.output, bytes))
+    }
+
+    /// Returns `false` if the source of the asset at `path` changed since it was processed.
+    ///
+    /// Artifacts shipped without their sources are always up to date.
+    async fn source_unchanged(&self, path: &Path, record: &ProcessRecord) -> bool {
+        match self.asset_io().load_path(path).await {
+            Ok(bytes) => {
+                matches!(self.source_hash(path, &bytes).await, Ok(hash) if hash == record.hash)
+            }
+            Err(AssetIoError::NotFound(_)) => true,
+            Err(_) => false,
+        }
+    }
+
+    /// Hashes the source `bytes` of the asset at `path` with its `.meta` file, as the settings it
+    /// carries may change the processed artifact.
+    async fn source_hash(&self, path: &Path, bytes: &[u8]) -> Result<u64, AssetIoError> {
+        let hash = content_hash(bytes);
+        Ok(match self.load_meta_bytes(path).await? {
+            Some(meta) => extend_hash(hash, &meta),
+            None => hash,
+        })
+    }
+
+    async fn dependencies_unchanged(&self, record: &ProcessRecord) -> bool {
+        for (path, hash) in &record.dependencies {
+            match self.asset_io().load_path(path).await {
+                Ok(bytes) if content_hash(&bytes) == *hash => {}
+                _ => return false,
+            }
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        update_asset_storage_system, AssetLoader, Assets, FileAssetIo, Handle, LoadContext,
+        LoadedAsset,
+    };
+    use bevy_app::{App, Update};
+    use bevy_reflect::{TypePath, TypeUuid};
+    use bevy_tasks::IoTaskPool;
+    use futures_lite::future::block_on;
+    use std::fs;
+
+    /// Uppercases text files, appending the files they include.
+    struct UppercaseProcessor {
+        version: u32,
+    }
+
+    impl AssetProcessor for UppercaseProcessor {
+        fn process<'a>(
+            &'a self,
+            bytes: &'a [u8],
+            process_context: &'a mut ProcessContext,
+        ) -> BoxedFuture<'a, Result<Vec<u8>, Error>> {
+            Box::pin(async move {
+                let text = std::str::from_utf8(bytes)?;
+                let mut processed = text.to_uppercase();
+                for line in text.lines() {
+                    if let Some(path) = line.strip_prefix("include ") {
+                        let included = process_context.read_dependency(path).await?;
+                        processed.push_str(&String::from_utf8(included)?.to_uppercase());
+                    }
+                }
+                Ok(processed.into_bytes())
+            })
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["txt"]
+        }
+
+        fn name(&self) -> &str {
+            "uppercase"
+        }
+
+        fn processed_extension(&self) -> &str {
+            "upper"
+        }
+
+        fn version(&self) -> u32 {
+            self.version
+        }
+    }
+
+    #[derive(Debug, TypeUuid, TypePath)]
+    #[uuid = "6b1ea3a4-4a4b-4c43-9d3e-8f0d0b5c3a27"]
+    struct Text(String);
+
+    struct TextLoader;
+
+    impl AssetLoader for TextLoader {
+        fn load<'a>(
+            &'a self,
+            bytes: &'a [u8],
+            load_context: &'a mut LoadContext,
+        ) -> BoxedFuture<'a, Result<(), Error>> {
+            Box::pin(async move {
+                let text = String::from_utf8(bytes.to_vec())?;
+                load_context.set_default_asset(LoadedAsset::new(Text(text)));
+                Ok(())
+            })
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["txt", "upper"]
+        }
+    }
+
+    struct Folders {
+        dir: tempfile::TempDir,
+    }
+
+    impl Folders {
+        fn new() -> Self {
+            let dir = tempfile::tempdir().unwrap();
+            fs::create_dir_all(dir.path().join("source")).unwrap();
+            fs::create_dir_all(dir.path().join("processed")).unwrap();
+            Self { dir }
+        }
+
+        fn write(&self, path: &str, contents: &str) {
+            let path = self.dir.path().join("source").join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, contents).unwrap();
+        }
+
+        fn processed(&self, path: &str) -> String {
+            fs::read_to_string(self.dir.path().join("processed").join(path)).unwrap()
+        }
+
+        fn server(&self, version: u32) -> AssetServer {
+            IoTaskPool::init(Default::default);
+            let server = AssetServer::new(FileAssetIo::new(self.dir.path().join("source"), &None));
+            server.set_processed_asset_io(Box::new(FileAssetIo::new(
+                self.dir.path().join("processed"),
+                &None,
+            )));
+            server.add_processor(UppercaseProcessor { version });
+            server
+        }
+    }
+
+    #[test]
+    fn process_only_changed_assets() {
+        let folders = Folders::new();
+        folders.write("a.txt", "hello");
+        folders.write("b.bin", "");
+
+        let server = folders.server(0);
+        let process = |path| block_on(server.process_asset(path)).unwrap();
+        assert_eq!(process("a.txt"), ProcessResult::Processed);
+        assert_eq!(folders.processed("a.txt.upper"), "HELLO");
+        assert_eq!(process("a.txt"), ProcessResult::UpToDate);
+        assert_eq!(process("b.bin"), ProcessResult::Skipped);
+
+        folders.write("a.txt", "world");
+        assert_eq!(process("a.txt"), ProcessResult::Processed);
+        assert_eq!(folders.processed("a.txt.upper"), "WORLD");
+        assert_eq!(process("a.txt"), ProcessResult::UpToDate);
+
+        // so does a change of its `.meta` file
+        folders.write("a.txt.meta", "(settings: ())");
+        assert_eq!(process("a.txt"), ProcessResult::Processed);
+        assert_eq!(process("a.txt"), ProcessResult::UpToDate);
+
+        // a new version of the processor processes the asset again
+        let server = folders.server(1);
+        assert_eq!(
+            block_on(server.process_asset("a.txt")).unwrap(),
+            ProcessResult::Processed
+        );
+    }
+
+    #[test]
+    fn process_changed_dependencies() {
+        let folders = Folders::new();
+        folders.write("a.txt", "include shared/b.txt\n");
+        folders.write("shared/b.txt", "b");
+        folders.write("shared/c.txt", "c");
+
+        let server = folders.server(0);
+        assert_eq!(block_on(server.process_folder("")).unwrap(), 3);
+        assert_eq!(folders.processed("a.txt.upper"), "INCLUDE SHARED/B.TXT\nB");
+        assert_eq!(block_on(server.process_folder("")).unwrap(), 0);
+
+        // only the changed dependency and the asset including it are processed again
+        folders.write("shared/b.txt", "bb");
+        assert_eq!(block_on(server.process_folder("")).unwrap(), 2);
+        assert_eq!(folders.processed("a.txt.upper"), "INCLUDE SHARED/B.TXT\nBB");
+    }
+
+    #[test]
+    fn missing_processed_asset_io() {
+        let folders = Folders::new();
+        IoTaskPool::init(Default::default);
+        let server = AssetServer::new(FileAssetIo::new(folders.dir.path().join("source"), &None));
+        assert!(matches!(
+            block_on(server.process_folder("")),
+            Err(AssetServerError::MissingProcessedAssetIo)
+        ));
+    }
+
+    #[test]
+    fn load_processed_assets() {
+        let folders = Folders::new();
+        folders.write("a.txt", "hello");
+        folders.write("b.txt", "world");
+
+        let server = folders.server(0);
+        block_on(server.process_asset("a.txt")).unwrap();
+        server.add_loader(TextLoader);
+
+        let mut app = App::new();
+        app.insert_resource(server.register_asset_type::<Text>())
+            .insert_resource(server.clone())
+            .add_systems(Update, update_asset_storage_system::<Text>);
+
+        let load = |path: &str| -> Handle<Text> {
+            let id = block_on(server.load_async(path.into(), false)).unwrap();
+            server.get_handle(id)
+        };
+        let a = load("a.txt");
+        let b = load("b.txt");
+        app.update();
+
+        // the processed artifact is loaded instead of the source, when there is one
+        let texts = app.world.resource::<Assets<Text>>();
+        assert_eq!(texts.get(&a).unwrap().0, "HELLO");
+        assert_eq!(texts.get(&b).unwrap().0, "world");
+    }
+
+    #[test]
+    fn process_record() {
+        let folders = Folders::new();
+        folders.write("folder/a file.txt", "include folder/b file.txt\n");
+        folders.write("folder/b file.txt", "");
+
+        let server = folders.server(2);
+        block_on(server.process_asset("folder/a file.txt")).unwrap();
+        let record: ProcessRecord =
+            ron::de::from_str(&folders.processed("folder/a file.txt.processed")).unwrap();
+        assert_eq!(
+            record,
+            ProcessRecord {
+                processor: "uppercase".to_string(),
+                version: 2,
+                hash: content_hash(b"include folder/b file.txt\n"),
+                output: PathBuf::from("folder/a file.txt.upper"),
+                dependencies: vec![(PathBuf::from("folder/b file.txt"), content_hash(b""))],
+            }
+        );
+    }
+
+    #[test]
+    fn outdated_artifacts() {
+        let folders = Folders::new();
+        folders.write("a.txt", "hello");
+
+        let server = folders.server(0);
+        block_on(server.process_asset("a.txt")).unwrap();
+        folders.write("a.txt", "world");
+        let load = |server: &AssetServer| {
+            block_on(server.load_processed(Path::new("a.txt"))).map(|(_, bytes)| bytes)
+        };
+
+        // without a processor, the outdated artifact is not loaded
+        let unprocessed =
+            AssetServer::new(FileAssetIo::new(folders.dir.path().join("source"), &None));
+        unprocessed.set_processed_asset_io(Box::new(FileAssetIo::new(
+            folders.dir.path().join("processed"),
+            &None,
+        )));
+        assert_eq!(load(&unprocessed), None);
+
+        // with a processor, the asset is processed again
+        assert_eq!(load(&server), Some(b"WORLD".to_vec()));
+        assert_eq!(folders.processed("a.txt.upper"), "WORLD");
+        assert_eq!(load(&unprocessed), Some(b"WORLD".to_vec()));
+    }
+}
@@ -0,0 +1,382 @@
+use crate::{
+    path::AssetPath, Asset, AssetServer, AssetServerError, Assets, Handle, HandleId, LoadState,
+    SourceInfo,
+};
+use anyhow::Error;
+use bevy_ecs::system::Res;
+use bevy_log::warn;
+use bevy_reflect::TypeUuid;
+use bevy_tasks::IoTaskPool;
+use bevy_utils::{Duration, Instant};
+use std::{path::Path, sync::Arc};
+
+/// A saver for an asset of type [`AssetSaver::Asset`].
+///
+/// Types implementing this trait are the counterpart of [`AssetLoader`](crate::AssetLoader)s, used
+/// by [`AssetServer::save`] to write assets back to the [`AssetIo`](crate::AssetIo) they are
+/// loaded from, in the format of one of their [`extensions`](AssetSaver::extensions).
+pub trait AssetSaver: Send + Sync + 'static {
+    /// The type of the saved assets.
+    type Asset: Asset;
+
+    /// Serializes the asset into the contents of the file it is saved to.
+    fn save(&self, asset: &Self::Asset, save_context: &SaveContext) -> Result<Vec<u8>, Error>;
+
+    /// Returns a list of extensions supported by this asset saver, without the preceding dot.
+    fn extensions(&self) -> &[&str];
+}
+
+/// The context where an [`Asset`] is saved.
+///
+/// The save context is created by the [`AssetServer`] and passed to the [`AssetSaver`] of the
+/// asset's type and of the extension of the path it is saved to.
+pub struct SaveContext<'a> {
+    path: &'a Path,
+    asset_server: &'a AssetServer,
+}
+
+impl<'a> SaveContext<'a> {
+    /// Gets the path the asset is saved to.
+    pub fn path(&self) -> &Path {
+        self.path
+    }
+
+    /// Gets the asset server, for example to find the paths of the assets the saved asset
+    /// depends on with [`AssetServer::get_handle_path`].
+    pub fn asset_server(&self) -> &AssetServer {
+        self.asset_server
+    }
+}
+
+/// An asset waiting to be saved by [`save_assets_system`].
+pub(crate) struct SaveRequest {
+    handle_id: HandleId,
+    path: AssetPath<'static>,
+}
+
+impl AssetServer {
+    /// Adds the provided asset saver to the server.
+    ///
+    /// If `saver` has one or more supported extensions in conflict with savers of the same asset
+    /// type that came before it, it will replace them.
+    pub fn add_saver<T>(&self, saver: T)
+    where
+        T: AssetSaver,
+    {
+        let saver: Arc<dyn AssetSaver<Asset = T::Asset>> = Arc::new(saver);
+        let mut savers = self.server.savers.write();
+        for extension in saver.extensions() {
+            savers.insert(
+                (T::Asset::TYPE_UUID, extension.to_string()),
+                Box::new(saver.clone()),
+            );
+        }
+    }
+
+    fn get_path_asset_saver<T: Asset>(
+        &self,
+        path: &Path,
+    ) -> Result<Arc<dyn AssetSaver<Asset = T>>, AssetServerError> {
+        let file_name = path
+            .file_name()
+            .and_then(|file_name| file_name.to_str())
+            .map(|file_name| file_name.to_lowercase())
+            .unwrap_or_default();
+
+        let savers = self.server.savers.read();
+        let mut extensions = Vec::new();
+        let mut extension = file_name.as_str();
+        while let Some(index) = extension.find('.') {
+            extension = &extension[index + 1..];
+            extensions.push(extension.to_string());
+            if let Some(saver) = savers.get(&(T::TYPE_UUID, extension.to_string())) {
+                return Ok(saver
+                    .downcast_ref::<Arc<dyn AssetSaver<Asset = T>>>()
+                    .expect("savers are keyed by the uuid of their asset type")
+                    .clone());
+            }
+        }
+        Err(AssetServerError::MissingAssetSaver { extensions })
+    }
+
+    /// Queues the [`Asset`] of the provided handle for saving at the provided path, with the
+    /// [`AssetSaver`] of its type and of the extension of the path.
+    ///
+    /// The asset is serialized when the [`Assets`] of its type are updated, then written in the
+    /// background. Once written, [`AssetServer::get_handle_path`] returns the path the asset was
+    /// saved to, and the change to the file doesn't cause the asset to be hot reloaded.
+    ///
+    /// Failures are logged, see [`AssetServer::save_asset`] to handle them.
+    pub fn save<'a, T: Asset, P: Into<AssetPath<'a>>>(&self, handle: &Handle<T>, path: P) {
+        self.server
+            .save_requests
+            .lock()
+            .entry(T::TYPE_UUID)
+            .or_default()
+            .push(SaveRequest {
+                handle_id: handle.id(),
+                path: path.into().to_owned(),
+            });
+    }
+
+    /// Saves the provided asset at the provided path, with the [`AssetSaver`] of its type and of
+    /// the extension of the path, and returns a future writing it.
+    ///
+    /// The asset is serialized immediately. When `handle_id` is given, the handle is mapped to the
+    /// path once the asset is written, as in [`AssetServer::save`].
+    ///
+    /// # Errors
+    ///
+    /// - If no saver was found, it will fail with [`AssetServerError::MissingAssetSaver`].
+    /// - If the saver fails, it will fail with [`AssetServerError::AssetSaverError`].
+    /// - If writing the file fails, the future will fail with [`AssetServerError::AssetIoError`].
+    pub fn save_asset<'a, T: Asset, P: Into<AssetPath<'a>>>(
+        &self,
+        asset: &T,
+        handle_id: Option<HandleId>,
+        path: P,
+    ) -> Result<impl std::future::Future<Output = Result<(), AssetServerError>>, AssetServerError>
+    {
+        let path = path.into().to_owned();
+        let saver = self.get_path_asset_saver::<T>(path.path())?;
+        let bytes = saver
+            .save(
+                asset,
+                &SaveContext {
+                    path: path.path(),
+                    asset_server: self,
+                },
+            )
+            .map_err(AssetServerError::AssetSaverError)?;
+
+        let asset_server = self.clone();
+        Ok(async move {
+            asset_server
+                .asset_io()
+                .write_path(path.path(), &bytes)
+                .await?;
+            // only watched files echo back, so that the paths of the others aren't kept around
+            #[cfg(all(
+                feature = "filesystem_watcher",
+                all(not(target_arch = "wasm32"), not(target_os = "android"))
+            ))]
+            if crate::is_watching_files(&asset_server) {
+                asset_server
+                    .server
+                    .saved_paths
+                    .lock()
+                    .insert(path.path().to_owned(), Instant::now());
+            }
+            asset_server
+                .server
+                .asset_sources
+                .write()
+                .entry(path.get_id().source_path_id())
+                .or_insert_with(|| SourceInfo {
+                    asset_types: Default::default(),
+                    committed_assets: Default::default(),
+                    load_state: LoadState::NotLoaded,
+                    meta: None,
+                    path: path.path().to_owned(),
+                    version: 0,
+                })
+                .asset_types
+                .insert(path.get_id().label_id(), T::TYPE_UUID);
+            if let Some(handle_id) = handle_id {
+                asset_server
+                    .server
+                    .handle_to_path
+                    .write()
+                    .insert(handle_id, path);
+            }
+            Ok(())
+        })
+    }
+
+    /// Returns `true` if a change to the file at `path`, noticed at `modified`, was caused by
+    /// saving an asset less than `delay` before, in which case it shouldn't be hot reloaded.
+    #[cfg_attr(not(feature = "filesystem_watcher"), allow(dead_code))]
+    pub(crate) fn is_save_echo(&self, path: &Path, modified: Instant, delay: Duration) -> bool {
+        let mut saved_paths = self.server.saved_paths.lock();
+        let is_echo = match saved_paths.remove(path) {
+            Some(saved) => modified <= saved + delay,
+            None => false,
+        };
+        // saves whose echo was never noticed, such as saves of files that aren't loaded, can't be
+        // echoed anymore
+        saved_paths.retain(|_, saved| saved.elapsed() <= delay);
+        is_echo
+    }
+}
+
+/// Saves the assets of type `T` queued by [`AssetServer::save`].
+pub fn save_assets_system<T: Asset>(asset_server: Res<AssetServer>, assets: Res<Assets<T>>) {
+    let Some(requests) = asset_server
+        .server
+        .save_requests
+        .lock()
+        .remove(&T::TYPE_UUID)
+    else {
+        return;
+    };
+    for SaveRequest { handle_id, path } in requests {
+        let Some(asset) = assets.get(&Handle::weak(handle_id)) else {
+            warn!(
+                "failed to save {}: the asset doesn't exist",
+                path.path().display()
+            );
+            continue;
+        };
+        let save = match asset_server.save_asset(asset, Some(handle_id), path.clone()) {
+            Ok(save) => save,
+            Err(err) => {
+                warn!("failed to save {}: {}", path.path().display(), err);
+                continue;
+            }
+        };
+        IoTaskPool::get()
+            .spawn(async move {
+                if let Err(err) = save.await {
+                    warn!("failed to save {}: {}", path.path().display(), err);
+                }
+            })
+            .detach();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ChangeWatcher, FileAssetIo};
+    use bevy_app::{App, Update};
+    use bevy_reflect::TypePath;
+    use futures_lite::future::block_on;
+    use std::fs;
+
+    #[derive(Debug, TypeUuid, TypePath)]
+    #[uuid = "3c8f2f5e-0d0b-4bb4-8a7c-5a8e1f6d2c90"]
+    struct Text(String);
+
+    struct TextSaver;
+
+    impl AssetSaver for TextSaver {
+        type Asset = Text;
+
+        fn save(&self, asset: &Text, _: &SaveContext) -> Result<Vec<u8>, Error> {
+            anyhow::ensure!(!asset.0.is_empty(), "empty text");
+            Ok(asset.0.clone().into_bytes())
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["txt"]
+        }
+    }
+
+    fn setup(
+        watch_for_changes: Option<ChangeWatcher>,
+    ) -> (tempfile::TempDir, AssetServer, Assets<Text>) {
+        let dir = tempfile::tempdir().unwrap();
+        IoTaskPool::init(Default::default);
+        let asset_server = AssetServer::new(FileAssetIo::new(dir.path(), &watch_for_changes));
+        asset_server.add_saver(TextSaver);
+        let assets = asset_server.register_asset_type::<Text>();
+        (dir, asset_server, assets)
+    }
+
+    #[test]
+    fn save_handle() {
+        let (dir, asset_server, mut assets) = setup(None);
+        let handle = assets.add(Text("hello".to_string()));
+
+        let mut app = App::new();
+        app.insert_resource(assets)
+            .insert_resource(asset_server.clone())
+            .add_systems(Update, save_assets_system::<Text>);
+
+        asset_server.save(&handle, "levels/a.txt");
+        app.update();
+
+        // the asset is written in the background
+        while asset_server.get_handle_path(&handle).is_none() {
+            std::thread::sleep(std::time::Duration::from_millis(1));
+        }
+        assert_eq!(
+            fs::read_to_string(dir.path().join("levels/a.txt")).unwrap(),
+            "hello"
+        );
+        assert_eq!(
+            asset_server.get_handle_path(&handle).unwrap().path(),
+            Path::new("levels/a.txt")
+        );
+
+        // the path is known to hold an asset of the saved type
+        let id = AssetPath::from("levels/a.txt").get_id();
+        let asset_sources = asset_server.server.asset_sources.read();
+        assert_eq!(
+            asset_sources[&id.source_path_id()].get_asset_type(id.label_id()),
+            Some(Text::TYPE_UUID)
+        );
+        // without watching for changes, saving doesn't wait for an echo
+        assert!(asset_server.server.saved_paths.lock().is_empty());
+    }
+
+    #[test]
+    fn save_errors() {
+        let (_dir, asset_server, _) = setup(None);
+
+        let err = asset_server
+            .save_asset(&Text("hello".to_string()), None, "a.bin")
+            .err()
+            .unwrap();
+        assert_eq!(
+            err.to_string(),
+            "no `AssetSaver` found for the following extension: bin"
+        );
+
+        let err = asset_server
+            .save_asset(&Text(String::new()), None, "a.txt")
+            .err()
+            .unwrap();
+        assert!(matches!(err, AssetServerError::AssetSaverError(_)));
+    }
+
+    #[test]
+    #[cfg(feature = "filesystem_watcher")]
+    fn save_echo() {
+        let delay = Duration::from_millis(200);
+        let (_dir, asset_server, _) = setup(ChangeWatcher::with_delay(delay));
+
+        let saved = Instant::now();
+        block_on(
+            asset_server
+                .save_asset(&Text("hello".to_string()), None, "a.txt")
+                .unwrap(),
+        )
+        .unwrap();
+
+        // a change noticed right after saving is the echo of the save, once
+        let path = Path::new("a.txt");
+        assert!(asset_server.is_save_echo(path, saved + delay / 2, delay));
+        assert!(!asset_server.is_save_echo(path, saved + delay / 2, delay));
+
+        // later changes were made by someone else
+        block_on(
+            asset_server
+                .save_asset(&Text("hello".to_string()), None, "a.txt")
+                .unwrap(),
+        )
+        .unwrap();
+        assert!(!asset_server.is_save_echo(path, Instant::now() + delay * 2, delay));
+
+        // echoes that never came are forgotten
+        block_on(
+            asset_server
+                .save_asset(&Text("hello".to_string()), None, "b.txt")
+                .unwrap(),
+        )
+        .unwrap();
+        std::thread::sleep(delay * 2);
+        assert!(!asset_server.is_save_echo(path, Instant::now(), delay));
+        assert!(asset_server.server.saved_paths.lock().is_empty());
+    }
+}
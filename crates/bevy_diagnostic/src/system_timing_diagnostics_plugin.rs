@@ -0,0 +1,113 @@
+use std::hash::{BuildHasher, Hash, Hasher};
+
+use bevy_app::prelude::*;
+use bevy_ecs::{prelude::*, schedule::SystemTimings};
+use bevy_utils::{get_short_name, FixedState, HashMap, Instant, Uuid};
+
+use crate::{Diagnostic, DiagnosticId, DiagnosticMeasurement, DiagnosticsStore};
+
+/// Adds a diagnostic for the duration of each system to an App, named `system/<system name>`.
+///
+/// Inserts the [`SystemTimings`] resource, which makes the schedule executors measure their systems.
+/// A measurement is added each time a system runs, so systems skipped by their run conditions keep
+/// their previous value.
+#[derive(Default)]
+pub struct SystemTimingDiagnosticsPlugin;
+
+impl Plugin for SystemTimingDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DiagnosticsStore>()
+            .init_resource::<SystemTimings>()
+            .add_systems(Last, Self::diagnostic_system);
+    }
+}
+
+impl SystemTimingDiagnosticsPlugin {
+    /// The number of measurements kept for each system.
+    pub const MAX_HISTORY_LENGTH: usize = 20;
+
+    /// Returns the id of the diagnostic of the system with the given name.
+    ///
+    /// The id only depends on the name, so it is the same between runs.
+    pub fn diagnostic_id(system_name: &str) -> DiagnosticId {
+        let hash = |salt: u8| {
+            let mut hasher = FixedState.build_hasher();
+            salt.hash(&mut hasher);
+            system_name.hash(&mut hasher);
+            hasher.finish()
+        };
+        DiagnosticId(Uuid::from_u64_pair(hash(0), hash(1)))
+    }
+
+    pub fn diagnostic_system(
+        mut diagnostics: ResMut<DiagnosticsStore>,
+        timings: Res<SystemTimings>,
+        mut run_counts: Local<HashMap<String, u64>>,
+    ) {
+        let time = Instant::now();
+        for (name, timing) in timings.iter() {
+            let run_count = run_counts.entry(name.to_string()).or_default();
+            if *run_count == timing.run_count() {
+                continue;
+            }
+            *run_count = timing.run_count();
+
+            let id = Self::diagnostic_id(name);
+            if diagnostics.get(id).is_none() {
+                diagnostics.add(
+                    Diagnostic::new(
+                        id,
+                        format!("system/{}", get_short_name(name)),
+                        Self::MAX_HISTORY_LENGTH,
+                    )
+                    .with_suffix("ms"),
+                );
+            }
+            let diagnostic = diagnostics.get_mut(id).unwrap();
+            if diagnostic.is_enabled {
+                diagnostic.add_measurement(DiagnosticMeasurement {
+                    time,
+                    value: timing.last().as_secs_f64() * 1000.0,
+                });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_app::prelude::*;
+    use bevy_ecs::prelude::*;
+
+    use super::SystemTimingDiagnosticsPlugin;
+    use crate::DiagnosticsStore;
+
+    fn measured() {}
+
+    #[test]
+    fn system_diagnostics() {
+        let mut app = App::new();
+        app.add_plugins(SystemTimingDiagnosticsPlugin)
+            .add_systems(Update, measured.run_if(|| true))
+            .add_systems(Update, (|| {}).run_if(|| false));
+        app.update();
+        app.update();
+
+        let store = app.world.resource::<DiagnosticsStore>();
+        let diagnostic = store
+            .iter()
+            .find(|diagnostic| diagnostic.name == "system/measured")
+            .unwrap();
+        assert_eq!(diagnostic.suffix, "ms");
+        assert_eq!(diagnostic.history_len(), 2);
+        assert_eq!(
+            diagnostic.id,
+            SystemTimingDiagnosticsPlugin::diagnostic_id(
+                "bevy_diagnostic::system_timing_diagnostics_plugin::tests::measured"
+            )
+        );
+        assert!(store
+            .iter()
+            .all(|diagnostic| !diagnostic.name.contains("closure")));
+    }
+}
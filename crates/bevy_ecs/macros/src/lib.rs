@@ -216,6 +216,10 @@ pub fn impl_param_set(_input: TokenStream) -> TokenStream {
                     <(#(#param,)*) as SystemParam>::new_archetype(state, archetype, system_meta);
                 }
 
+                fn forget_archetypes(state: &mut Self::State) {
+                    <(#(#param,)*) as SystemParam>::forget_archetypes(state);
+                }
+
                 fn apply(state: &mut Self::State, system_meta: &SystemMeta, world: &mut World) {
                     <(#(#param,)*) as SystemParam>::apply(state, system_meta, world);
                 }
@@ -395,6 +399,10 @@ pub fn derive_system_param(input: TokenStream) -> TokenStream {
                     <#fields_alias::<'_, '_, #punctuated_generic_idents> as #path::system::SystemParam>::new_archetype(&mut state.state, archetype, system_meta)
                 }
 
+                fn forget_archetypes(state: &mut Self::State) {
+                    <#fields_alias::<'_, '_, #punctuated_generic_idents> as #path::system::SystemParam>::forget_archetypes(&mut state.state)
+                }
+
                 fn apply(state: &mut Self::State, system_meta: &#path::system::SystemMeta, world: &mut #path::world::World) {
                     <#fields_alias::<'_, '_, #punctuated_generic_idents> as #path::system::SystemParam>::apply(&mut state.state, system_meta, world);
                 }
@@ -11,13 +11,15 @@
 //! archetypes may store their table components in the same table. These archetypes
 //! differ only by the [`SparseSet`] components.
 //!
-//! Like tables, archetypes can be created but are never cleaned up. Empty archetypes are
-//! not removed, and persist until the world is dropped.
+//! Like tables, archetypes are not cleaned up automatically: empty archetypes persist until
+//! they are reclaimed with [`World::reclaim_empty_archetypes`], which changes the ids of the
+//! remaining archetypes.
 //!
 //! Archetypes can be fetched from [`Archetypes`], which is accessible via [`World::archetypes`].
 //!
 //! [`Table`]: crate::storage::Table
 //! [`World::archetypes`]: crate::world::World::archetypes
+//! [`World::reclaim_empty_archetypes`]: crate::world::World::reclaim_empty_archetypes
 
 use crate::{
     bundle::BundleId,
@@ -27,7 +29,7 @@ use crate::{
 };
 use std::{
     hash::Hash,
-    ops::{Index, IndexMut},
+    ops::{Index, IndexMut, Range},
 };
 
 /// An opaque location within a [`Archetype`].
@@ -459,6 +461,10 @@ impl Archetype {
         self.entities.reserve(additional);
     }
 
+    pub(crate) fn shrink_to_fit(&mut self) {
+        self.entities.shrink_to_fit();
+    }
+
     /// Removes the entity at `index` by swapping it out. Returns the table row the entity is stored
     /// in.
     ///
@@ -527,17 +533,42 @@ impl Archetype {
 
 /// An opaque generational id that changes every time the set of [`Archetypes`] changes.
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
-pub struct ArchetypeGeneration(usize);
+pub struct ArchetypeGeneration {
+    /// The number of times empty archetypes were reclaimed, changing the ids of the archetypes.
+    reclaims: u32,
+    len: usize,
+}
 
 impl ArchetypeGeneration {
     #[inline]
     pub(crate) const fn initial() -> Self {
-        ArchetypeGeneration(0)
+        ArchetypeGeneration {
+            reclaims: 0,
+            len: 0,
+        }
     }
 
     #[inline]
     pub(crate) fn value(self) -> usize {
-        self.0
+        self.len
+    }
+
+    /// Returns `true` if empty archetypes were reclaimed between the `old` generation and this one,
+    /// so that the ids of the archetypes seen at the `old` generation are no longer valid.
+    #[inline]
+    pub(crate) fn is_reclaimed_since(self, old: ArchetypeGeneration) -> bool {
+        self.reclaims != old.reclaims
+    }
+
+    /// Returns the indices of the archetypes to register to go from the `old` generation to this one:
+    /// the archetypes created since, or all archetypes if empty archetypes were reclaimed since.
+    #[inline]
+    pub(crate) fn archetypes_since(self, old: ArchetypeGeneration) -> Range<usize> {
+        if self.is_reclaimed_since(old) {
+            0..self.len
+        } else {
+            old.len..self.len
+        }
     }
 }
 
@@ -604,6 +635,7 @@ pub struct Archetypes {
     pub(crate) archetypes: Vec<Archetype>,
     pub(crate) archetype_component_count: usize,
     archetype_ids: bevy_utils::HashMap<ArchetypeIdentity, ArchetypeId>,
+    reclaims: u32,
 }
 
 impl Archetypes {
@@ -612,6 +644,7 @@ impl Archetypes {
             archetypes: Vec::new(),
             archetype_ids: Default::default(),
             archetype_component_count: 0,
+            reclaims: 0,
         };
         archetypes.get_id_or_insert(TableId::empty(), Vec::new(), Vec::new());
         archetypes
@@ -621,7 +654,10 @@ impl Archetypes {
     /// that are registered with the world.
     #[inline]
     pub fn generation(&self) -> ArchetypeGeneration {
-        ArchetypeGeneration(self.archetypes.len())
+        ArchetypeGeneration {
+            reclaims: self.reclaims,
+            len: self.archetypes.len(),
+        }
     }
 
     /// Fetches the total number of [`Archetype`]s within the world.
@@ -737,6 +773,59 @@ impl Archetypes {
             archetype.clear_entities();
         }
     }
+
+    pub(crate) fn shrink_to_fit(&mut self) {
+        for archetype in &mut self.archetypes {
+            archetype.shrink_to_fit();
+        }
+    }
+
+    /// Removes the empty archetypes, except the [empty archetype](ArchetypeId::EMPTY), and gives
+    /// consecutive ids to the remaining archetypes, in the same order.
+    ///
+    /// Returns the new id of each archetype, indexed by its old id, or `None` if nothing was removed.
+    /// The cached [`Edges`] of all archetypes are cleared, as they may point to removed archetypes.
+    pub(crate) fn remove_empty(&mut self) -> Option<Vec<Option<ArchetypeId>>> {
+        let mut len = 0;
+        let new_ids: Vec<_> = self
+            .archetypes
+            .iter()
+            .map(|archetype| {
+                (archetype.id == ArchetypeId::EMPTY || !archetype.is_empty()).then(|| {
+                    len += 1;
+                    ArchetypeId::new(len - 1)
+                })
+            })
+            .collect();
+        if len == self.archetypes.len() {
+            return None;
+        }
+
+        self.archetypes
+            .retain(|archetype| new_ids[archetype.id.index()].is_some());
+        for archetype in &mut self.archetypes {
+            archetype.id = new_ids[archetype.id.index()].unwrap();
+            archetype.edges = Edges::default();
+        }
+        self.archetype_ids
+            .retain(|_, id| match new_ids[id.index()] {
+                Some(new_id) => {
+                    *id = new_id;
+                    true
+                }
+                None => false,
+            });
+        self.reclaims += 1;
+        Some(new_ids)
+    }
+
+    /// Changes the table of each archetype to the new id of its table, indexed by its old id.
+    pub(crate) fn remap_tables(&mut self, new_table_ids: &[Option<TableId>]) {
+        for archetype in &mut self.archetypes {
+            archetype.table_id = new_table_ids[archetype.table_id.index()]
+                .expect("the table of an archetype should not be removed");
+        }
+    }
 }
 
 impl Index<ArchetypeId> for Archetypes {
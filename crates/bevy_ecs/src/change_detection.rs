@@ -7,6 +7,8 @@ use crate::{
 };
 use bevy_ptr::{Ptr, UnsafeCellDeref};
 use std::ops::{Deref, DerefMut};
+#[cfg(feature = "track_change_detection")]
+use std::{borrow::Cow, cell::RefCell, panic::Location};
 
 /// The (arbitrarily chosen) minimum number of world tick increments between `check_tick` scans.
 ///
@@ -62,6 +64,15 @@ pub trait DetectChanges {
     /// [`SystemChangeTick`](crate::system::SystemChangeTick)
     /// [`SystemParam`](crate::system::SystemParam).
     fn last_changed(&self) -> Tick;
+
+    /// Returns where this data was most recently changed through change detection,
+    /// such as a [`DerefMut`] or a call to [`set_changed`](DetectChangesMut::set_changed).
+    ///
+    /// Returns `None` if the data wasn't changed since it was inserted, or if the change isn't known.
+    #[cfg(feature = "track_change_detection")]
+    fn changed_by(&self) -> Option<&ChangedBy> {
+        None
+    }
 }
 
 /// Types that implement reliable change detection.
@@ -106,6 +117,7 @@ pub trait DetectChangesMut: DetectChanges {
     /// However, mutation through interior mutability requires manual reporting.
     ///
     /// **Note**: This operation cannot be undone.
+    #[track_caller]
     fn set_changed(&mut self);
 
     /// Manually sets the change tick recording the time when this data was last mutated.
@@ -157,6 +169,7 @@ pub trait DetectChangesMut: DetectChanges {
     /// # assert!(!score_changed.run((), &mut world));
     /// ```
     #[inline]
+    #[track_caller]
     fn set_if_neq(&mut self, value: Self::Inner)
     where
         Self::Inner: Sized + PartialEq,
@@ -190,6 +203,12 @@ macro_rules! change_detection_impl {
             fn last_changed(&self) -> Tick {
                 *self.ticks.changed
             }
+
+            #[cfg(feature = "track_change_detection")]
+            #[inline]
+            fn changed_by(&self) -> Option<&ChangedBy> {
+                self.ticks.changed_by.as_deref().and_then(Option::as_ref)
+            }
         }
 
         impl<$($generics),*: ?Sized $(+ $traits)?> Deref for $name<$($generics),*> {
@@ -216,8 +235,11 @@ macro_rules! change_detection_mut_impl {
             type Inner = $target;
 
             #[inline]
+            #[track_caller]
             fn set_changed(&mut self) {
                 *self.ticks.changed = self.ticks.this_run;
+                #[cfg(feature = "track_change_detection")]
+                self.ticks.set_changed_by();
             }
 
             #[inline]
@@ -233,6 +255,7 @@ macro_rules! change_detection_mut_impl {
 
         impl<$($generics),* : ?Sized $(+ $traits)?> DerefMut for $name<$($generics),*> {
             #[inline]
+            #[track_caller]
             fn deref_mut(&mut self) -> &mut Self::Target {
                 self.set_changed();
                 self.value
@@ -241,6 +264,7 @@ macro_rules! change_detection_mut_impl {
 
         impl<$($generics),* $(: $traits)?> AsMut<$target> for $name<$($generics),*> {
             #[inline]
+            #[track_caller]
             fn as_mut(&mut self) -> &mut $target {
                 self.deref_mut()
             }
@@ -254,6 +278,7 @@ macro_rules! impl_methods {
             /// Consume `self` and return a mutable reference to the
             /// contained value while marking `self` as "changed".
             #[inline]
+            #[track_caller]
             pub fn into_inner(mut self) -> &'a mut $target {
                 self.set_changed();
                 self.value
@@ -269,6 +294,8 @@ macro_rules! impl_methods {
                     ticks: TicksMut {
                         added: self.ticks.added,
                         changed: self.ticks.changed,
+                        #[cfg(feature = "track_change_detection")]
+                        changed_by: self.ticks.changed_by.as_deref_mut(),
                         last_run: self.ticks.last_run,
                         this_run: self.ticks.this_run,
                     }
@@ -326,6 +353,9 @@ macro_rules! impl_debug {
 pub(crate) struct Ticks<'a> {
     pub(crate) added: &'a Tick,
     pub(crate) changed: &'a Tick,
+    /// `None` if the pointer wasn't created from the world's storage.
+    #[cfg(feature = "track_change_detection")]
+    pub(crate) changed_by: Option<&'a Option<ChangedBy>>,
     pub(crate) last_run: Tick,
     pub(crate) this_run: Tick,
 }
@@ -342,6 +372,8 @@ impl<'a> Ticks<'a> {
         Self {
             added: cells.added.deref(),
             changed: cells.changed.deref(),
+            #[cfg(feature = "track_change_detection")]
+            changed_by: Some(cells.changed_by.deref()),
             last_run,
             this_run,
         }
@@ -351,6 +383,9 @@ impl<'a> Ticks<'a> {
 pub(crate) struct TicksMut<'a> {
     pub(crate) added: &'a mut Tick,
     pub(crate) changed: &'a mut Tick,
+    /// `None` if the pointer wasn't created from the world's storage.
+    #[cfg(feature = "track_change_detection")]
+    pub(crate) changed_by: Option<&'a mut Option<ChangedBy>>,
     pub(crate) last_run: Tick,
     pub(crate) this_run: Tick,
 }
@@ -367,10 +402,22 @@ impl<'a> TicksMut<'a> {
         Self {
             added: cells.added.deref_mut(),
             changed: cells.changed.deref_mut(),
+            #[cfg(feature = "track_change_detection")]
+            changed_by: Some(cells.changed_by.deref_mut()),
             last_run,
             this_run,
         }
     }
+
+    /// Records the caller and the running system as the last change.
+    #[cfg(feature = "track_change_detection")]
+    #[inline]
+    #[track_caller]
+    pub(crate) fn set_changed_by(&mut self) {
+        if let Some(changed_by) = &mut self.changed_by {
+            **changed_by = Some(ChangedBy::caller());
+        }
+    }
 }
 
 impl<'a> From<TicksMut<'a>> for Ticks<'a> {
@@ -378,6 +425,8 @@ impl<'a> From<TicksMut<'a>> for Ticks<'a> {
         Ticks {
             added: ticks.added,
             changed: ticks.changed,
+            #[cfg(feature = "track_change_detection")]
+            changed_by: ticks.changed_by.map(|changed_by| &*changed_by),
             last_run: ticks.last_run,
             this_run: ticks.this_run,
         }
@@ -479,6 +528,7 @@ where
     type Item = <&'a mut T as IntoIterator>::Item;
     type IntoIter = <&'a mut T as IntoIterator>::IntoIter;
 
+    #[track_caller]
     fn into_iter(self) -> Self::IntoIter {
         self.set_changed();
         self.value.into_iter()
@@ -581,6 +631,8 @@ impl<'a, T: ?Sized> Ref<'a, T> {
             ticks: Ticks {
                 added,
                 changed,
+                #[cfg(feature = "track_change_detection")]
+                changed_by: None,
                 last_run,
                 this_run,
             },
@@ -636,6 +688,8 @@ impl<'a, T: ?Sized> Mut<'a, T> {
             ticks: TicksMut {
                 added,
                 changed: last_changed,
+                #[cfg(feature = "track_change_detection")]
+                changed_by: None,
                 last_run,
                 this_run,
             },
@@ -671,6 +725,7 @@ where
     type Item = <&'a mut T as IntoIterator>::Item;
     type IntoIter = <&'a mut T as IntoIterator>::IntoIter;
 
+    #[track_caller]
     fn into_iter(self) -> Self::IntoIter {
         self.set_changed();
         self.value.into_iter()
@@ -700,6 +755,7 @@ impl<'a> MutUntyped<'a> {
     ///
     /// In order to avoid marking the value as changed, you need to call [`bypass_change_detection`](DetectChangesMut::bypass_change_detection).
     #[inline]
+    #[track_caller]
     pub fn into_inner(mut self) -> PtrMut<'a> {
         self.set_changed();
         self.value
@@ -714,6 +770,8 @@ impl<'a> MutUntyped<'a> {
             ticks: TicksMut {
                 added: self.ticks.added,
                 changed: self.ticks.changed,
+                #[cfg(feature = "track_change_detection")]
+                changed_by: self.ticks.changed_by.as_deref_mut(),
                 last_run: self.ticks.last_run,
                 this_run: self.ticks.this_run,
             },
@@ -724,6 +782,7 @@ impl<'a> MutUntyped<'a> {
     ///
     /// In order to avoid marking the value as changed, you need to call [`bypass_change_detection`](DetectChangesMut::bypass_change_detection).
     #[inline]
+    #[track_caller]
     pub fn as_mut(&mut self) -> PtrMut<'_> {
         self.set_changed();
         self.value.reborrow()
@@ -766,14 +825,23 @@ impl<'a> DetectChanges for MutUntyped<'a> {
     fn last_changed(&self) -> Tick {
         *self.ticks.changed
     }
+
+    #[cfg(feature = "track_change_detection")]
+    #[inline]
+    fn changed_by(&self) -> Option<&ChangedBy> {
+        self.ticks.changed_by.as_deref().and_then(Option::as_ref)
+    }
 }
 
 impl<'a> DetectChangesMut for MutUntyped<'a> {
     type Inner = PtrMut<'a>;
 
     #[inline]
+    #[track_caller]
     fn set_changed(&mut self) {
         *self.ticks.changed = self.ticks.this_run;
+        #[cfg(feature = "track_change_detection")]
+        self.ticks.set_changed_by();
     }
 
     #[inline]
@@ -795,6 +863,106 @@ impl std::fmt::Debug for MutUntyped<'_> {
     }
 }
 
+/// Where a component or resource was most recently changed through change detection.
+///
+/// Only recorded with the `track_change_detection` feature, and returned by
+/// [`DetectChanges::changed_by`], [`EntityRef::get_changed_by`](crate::world::EntityRef::get_changed_by)
+/// and [`World::get_resource_changed_by`](crate::world::World::get_resource_changed_by).
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// #[derive(Component)]
+/// struct Health(u32);
+///
+/// fn take_damage(mut query: Query<&mut Health>) {
+///     for mut health in &mut query {
+///         health.0 -= 1;
+///     }
+/// }
+///
+/// # #[cfg(feature = "track_change_detection")]
+/// fn report_changes(query: Query<Ref<Health>, Changed<Health>>) {
+///     for health in &query {
+///         if let Some(changed_by) = health.changed_by() {
+///             // "Health changed by `take_damage` at src/main.rs:7:13"
+///             println!("Health changed by {changed_by}");
+///         }
+///     }
+/// }
+/// ```
+#[cfg(feature = "track_change_detection")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ChangedBy {
+    location: &'static Location<'static>,
+    system: Option<Cow<'static, str>>,
+}
+
+#[cfg(feature = "track_change_detection")]
+thread_local! {
+    /// The name of the system running on this thread, if any.
+    static RUNNING_SYSTEM: RefCell<Option<Cow<'static, str>>> = const { RefCell::new(None) };
+}
+
+#[cfg(feature = "track_change_detection")]
+impl ChangedBy {
+    /// Records the location of the caller, and the system running on this thread.
+    #[track_caller]
+    pub(crate) fn caller() -> Self {
+        Self {
+            location: Location::caller(),
+            system: RUNNING_SYSTEM.with(|system| system.borrow().clone()),
+        }
+    }
+
+    /// Returns the source location of the change.
+    pub fn location(&self) -> &'static Location<'static> {
+        self.location
+    }
+
+    /// Returns the name of the system that made the change,
+    /// or `None` if it wasn't made while a system was running.
+    pub fn system(&self) -> Option<&str> {
+        self.system.as_deref()
+    }
+
+    /// Makes `system` the system recording the changes made on this thread, until the
+    /// returned guard is dropped.
+    pub(crate) fn enter_system(system: Cow<'static, str>) -> RunningSystemGuard {
+        RunningSystemGuard {
+            outer: RUNNING_SYSTEM.with(|running| running.replace(Some(system))),
+        }
+    }
+}
+
+#[cfg(feature = "track_change_detection")]
+impl std::fmt::Display for ChangedBy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.system {
+            Some(system) => write!(
+                f,
+                "`{}` at {}",
+                bevy_utils::get_short_name(system),
+                self.location
+            ),
+            None => write!(f, "{}", self.location),
+        }
+    }
+}
+
+/// Restores the system running on this thread before [`ChangedBy::enter_system`] when dropped.
+#[cfg(feature = "track_change_detection")]
+pub(crate) struct RunningSystemGuard {
+    outer: Option<Cow<'static, str>>,
+}
+
+#[cfg(feature = "track_change_detection")]
+impl Drop for RunningSystemGuard {
+    fn drop(&mut self) {
+        let outer = self.outer.take();
+        RUNNING_SYSTEM.with(|running| *running.borrow_mut() = outer);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use bevy_ecs_macros::Resource;
@@ -912,6 +1080,8 @@ mod tests {
         let ticks = TicksMut {
             added: &mut component_ticks.added,
             changed: &mut component_ticks.changed,
+            #[cfg(feature = "track_change_detection")]
+            changed_by: None,
             last_run: Tick::new(3),
             this_run: Tick::new(4),
         };
@@ -957,6 +1127,8 @@ mod tests {
         let ticks = TicksMut {
             added: &mut component_ticks.added,
             changed: &mut component_ticks.changed,
+            #[cfg(feature = "track_change_detection")]
+            changed_by: None,
             last_run: Tick::new(3),
             this_run: Tick::new(4),
         };
@@ -987,6 +1159,8 @@ mod tests {
         let ticks = TicksMut {
             added: &mut component_ticks.added,
             changed: &mut component_ticks.changed,
+            #[cfg(feature = "track_change_detection")]
+            changed_by: None,
             last_run,
             this_run,
         };
@@ -1034,4 +1208,38 @@ mod tests {
             "Resource must be changed after setting to a different value."
         );
     }
+
+    #[cfg(feature = "track_change_detection")]
+    #[test]
+    fn changed_by() {
+        fn mutate(mut query: Query<&mut C>, mut r: ResMut<R2>) {
+            query.single_mut().set_changed();
+            r.0 += 1;
+        }
+
+        let mut world = World::new();
+        let entity = world.spawn(C).id();
+        world.insert_resource(R2(0));
+        assert_eq!(world.entity(entity).get_changed_by::<C>(), None);
+        assert_eq!(world.get_resource_changed_by::<R2>(), None);
+
+        let mut system = IntoSystem::into_system(mutate);
+        system.initialize(&mut world);
+        system.run((), &mut world);
+
+        let changed_by = world.entity(entity).get_changed_by::<C>().unwrap();
+        assert!(changed_by.system().unwrap().ends_with("mutate"));
+        assert_eq!(changed_by.location().file(), file!());
+        let changed_by = world.get_resource_changed_by::<R2>().unwrap();
+        assert!(changed_by.system().unwrap().ends_with("mutate"));
+        assert_eq!(
+            world.resource_mut::<R2>().changed_by().unwrap(),
+            &changed_by
+        );
+
+        world.resource_mut::<R2>().0 += 1;
+        let changed_by = world.get_resource_changed_by::<R2>().unwrap();
+        assert_eq!(changed_by.system(), None);
+        assert_eq!(changed_by.location().file(), file!());
+    }
 }
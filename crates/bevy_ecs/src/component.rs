@@ -1,15 +1,18 @@
 //! Types for declaring and storing [`Component`]s.
 
+#[cfg(feature = "track_change_detection")]
+use crate::change_detection::ChangedBy;
 use crate::{
     self as bevy_ecs,
     change_detection::MAX_CHANGE_AGE,
+    entity::{Entity, EntityMapper, MapEntities},
     storage::{SparseSetIndex, Storages},
     system::{Local, Resource, SystemParam},
-    world::{FromWorld, World},
+    world::{DeferredWorld, FromWorld, World},
     TypeIdMap,
 };
 pub use bevy_ecs_macros::Component;
-use bevy_ptr::{OwningPtr, UnsafeCellDeref};
+use bevy_ptr::{OwningPtr, Ptr, PtrMut, UnsafeCellDeref};
 use std::cell::UnsafeCell;
 use std::{
     alloc::Layout,
@@ -150,6 +153,12 @@ pub trait Component: Send + Sync + 'static {
     /// A marker type indicating the storage type used for this component.
     /// This must be either [`TableStorage`] or [`SparseStorage`].
     type Storage: ComponentStorage;
+
+    /// Called when registering this component, allowing mutable access to its [`ComponentHooks`].
+    ///
+    /// When deriving [`Component`], hooks can be set with the `on_add`, `on_insert` and `on_remove`
+    /// attributes: `#[component(on_add = my_on_add_hook)]`.
+    fn register_component_hooks(_hooks: &mut ComponentHooks) {}
 }
 
 /// Marker type for components stored in a [`Table`](crate::storage::Table).
@@ -201,6 +210,157 @@ pub enum StorageType {
     SparseSet,
 }
 
+/// The type used for [`Component`] lifecycle hooks such as `on_add`, `on_insert` or `on_remove`.
+///
+/// The [`DeferredWorld`] passed to the hook gives access to component and resource data, but
+/// structural changes (spawning, despawning, inserting or removing components) must be queued
+/// through [`DeferredWorld::commands`]. These commands are applied as soon as the operation that
+/// triggered the hook completes.
+pub type ComponentHook = for<'w> fn(DeferredWorld<'w>, Entity, ComponentId);
+
+/// A function that clones the component value behind the given pointer, passing the clone
+/// to the given callback.
+///
+/// These are registered with [`World::register_component_clone`](crate::world::World::register_component_clone)
+/// or [`ComponentDescriptor::with_clone_fn`], and are used to copy components that are only known
+/// by their [`ComponentId`].
+///
+/// # Safety
+///
+/// The pointer must point to a valid value of the component type the function was registered for.
+pub type ComponentCloneFn = for<'a> unsafe fn(Ptr<'a>, &mut dyn FnMut(OwningPtr<'_>));
+
+/// A function that maps the [`Entity`] references stored in the component value behind the given pointer,
+/// using the component's [`MapEntities`] implementation.
+///
+/// These are registered with [`World::register_component_map_entities`](crate::world::World::register_component_map_entities)
+/// or [`ComponentDescriptor::with_map_entities_fn`].
+///
+/// # Safety
+///
+/// The pointer must point to a valid value of the component type the function was registered for.
+pub type ComponentMapEntitiesFn = for<'a> unsafe fn(PtrMut<'a>, &mut EntityMapper);
+
+/// Lifecycle hooks for a given [`Component`], stored in its [`ComponentInfo`].
+///
+/// Unlike [`RemovedComponents`](crate::removal_detection::RemovedComponents) or the
+/// [`Added`](crate::query::Added) and [`Changed`](crate::query::Changed) filters, hooks run
+/// immediately, inside the [`World`] operation that caused them, so they cannot be skipped or
+/// delayed by system ordering. This makes them well suited to maintaining invariants such as indexes.
+///
+/// - `on_add` runs when a component is added to an entity that did not already have it.
+/// - `on_insert` runs whenever a component is inserted, after `on_add` if the component was not present,
+///   and also when an existing value is overwritten.
+/// - `on_remove` runs when a component is removed from an entity, including when the entity is despawned.
+///   It runs *before* the value is removed, so the hook can still read it.
+///
+/// Each hook can only be set once per component, since hooks are owned by the component's author.
+///
+/// # Example
+///
+/// ```
+/// use bevy_ecs::prelude::*;
+/// use bevy_utils::HashSet;
+///
+/// #[derive(Component)]
+/// struct Tracked;
+///
+/// #[derive(Resource, Default)]
+/// struct TrackedEntities(HashSet<Entity>);
+///
+/// let mut world = World::new();
+/// world.init_resource::<TrackedEntities>();
+/// world
+///     .register_component_hooks::<Tracked>()
+///     .on_add(|mut world, entity, _| {
+///         world.resource_mut::<TrackedEntities>().0.insert(entity);
+///     })
+///     .on_remove(|mut world, entity, _| {
+///         world.resource_mut::<TrackedEntities>().0.remove(&entity);
+///     });
+///
+/// let entity = world.spawn(Tracked).id();
+/// assert!(world.resource::<TrackedEntities>().0.contains(&entity));
+///
+/// world.despawn(entity);
+/// assert!(world.resource::<TrackedEntities>().0.is_empty());
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ComponentHooks {
+    pub(crate) on_add: Option<ComponentHook>,
+    pub(crate) on_insert: Option<ComponentHook>,
+    pub(crate) on_remove: Option<ComponentHook>,
+}
+
+impl ComponentHooks {
+    /// Sets a hook that runs when this component is added to an entity.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an `on_add` hook was already registered for this component.
+    pub fn on_add(&mut self, hook: ComponentHook) -> &mut Self {
+        self.try_on_add(hook)
+            .expect("Component already has an on_add hook")
+    }
+
+    /// Sets a hook that runs when this component is inserted on an entity, whether or not it was
+    /// already present.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an `on_insert` hook was already registered for this component.
+    pub fn on_insert(&mut self, hook: ComponentHook) -> &mut Self {
+        self.try_on_insert(hook)
+            .expect("Component already has an on_insert hook")
+    }
+
+    /// Sets a hook that runs when this component is removed from an entity or the entity is despawned.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an `on_remove` hook was already registered for this component.
+    pub fn on_remove(&mut self, hook: ComponentHook) -> &mut Self {
+        self.try_on_remove(hook)
+            .expect("Component already has an on_remove hook")
+    }
+
+    /// Fallible version of [`Self::on_add`].
+    /// Returns `None` if the component already has an `on_add` hook.
+    pub fn try_on_add(&mut self, hook: ComponentHook) -> Option<&mut Self> {
+        if self.on_add.is_some() {
+            return None;
+        }
+        self.on_add = Some(hook);
+        Some(self)
+    }
+
+    /// Fallible version of [`Self::on_insert`].
+    /// Returns `None` if the component already has an `on_insert` hook.
+    pub fn try_on_insert(&mut self, hook: ComponentHook) -> Option<&mut Self> {
+        if self.on_insert.is_some() {
+            return None;
+        }
+        self.on_insert = Some(hook);
+        Some(self)
+    }
+
+    /// Fallible version of [`Self::on_remove`].
+    /// Returns `None` if the component already has an `on_remove` hook.
+    pub fn try_on_remove(&mut self, hook: ComponentHook) -> Option<&mut Self> {
+        if self.on_remove.is_some() {
+            return None;
+        }
+        self.on_remove = Some(hook);
+        Some(self)
+    }
+
+    /// Returns `true` if no hook is registered.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.on_add.is_none() && self.on_insert.is_none() && self.on_remove.is_none()
+    }
+}
+
 /// Stores metadata for a type of component or resource stored in a specific [`World`].
 #[derive(Debug)]
 pub struct ComponentInfo {
@@ -259,6 +419,30 @@ impl ComponentInfo {
         self.descriptor.is_send_and_sync
     }
 
+    /// Returns the function used to clone values of this component, if one was registered.
+    #[inline]
+    pub fn clone_fn(&self) -> Option<ComponentCloneFn> {
+        self.descriptor.clone
+    }
+
+    /// Returns the function used to map the entities referenced by this component, if one was registered.
+    #[inline]
+    pub fn map_entities_fn(&self) -> Option<ComponentMapEntitiesFn> {
+        self.descriptor.map_entities
+    }
+
+    /// Returns the lifecycle hooks of this component.
+    #[inline]
+    pub fn hooks(&self) -> &ComponentHooks {
+        &self.descriptor.hooks
+    }
+
+    /// Returns a mutable reference to the lifecycle hooks of this component.
+    #[inline]
+    pub(crate) fn hooks_mut(&mut self) -> &mut ComponentHooks {
+        &mut self.descriptor.hooks
+    }
+
     /// Create a new [`ComponentInfo`].
     pub(crate) fn new(id: ComponentId, descriptor: ComponentDescriptor) -> Self {
         ComponentInfo { id, descriptor }
@@ -329,9 +513,16 @@ pub struct ComponentDescriptor {
     // this descriptor describes.
     // None if the underlying type doesn't need to be dropped
     drop: Option<for<'a> unsafe fn(OwningPtr<'a>)>,
+    // SAFETY: this function must be safe to call with pointers pointing to items of the type
+    // this descriptor describes, and must pass a value of that type to its callback.
+    clone: Option<ComponentCloneFn>,
+    // SAFETY: this function must be safe to call with pointers pointing to items of the type
+    // this descriptor describes.
+    map_entities: Option<ComponentMapEntitiesFn>,
+    hooks: ComponentHooks,
 }
 
-// We need to ignore the `drop` field in our `Debug` impl
+// We need to ignore the `drop`, `clone` and `map_entities` fields in our `Debug` impl
 impl std::fmt::Debug for ComponentDescriptor {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("ComponentDescriptor")
@@ -340,6 +531,9 @@ impl std::fmt::Debug for ComponentDescriptor {
             .field("is_send_and_sync", &self.is_send_and_sync)
             .field("type_id", &self.type_id)
             .field("layout", &self.layout)
+            .field("clone", &self.clone.is_some())
+            .field("map_entities", &self.map_entities.is_some())
+            .field("hooks", &self.hooks)
             .finish()
     }
 }
@@ -350,8 +544,20 @@ impl ComponentDescriptor {
         x.drop_as::<T>();
     }
 
+    // SAFETY: The pointer points to a valid value of type `T`.
+    unsafe fn clone_ptr<T: Clone>(x: Ptr<'_>, f: &mut dyn FnMut(OwningPtr<'_>)) {
+        OwningPtr::make(x.deref::<T>().clone(), f);
+    }
+
+    // SAFETY: The pointer points to a valid value of type `T`.
+    unsafe fn map_entities_ptr<T: MapEntities>(x: PtrMut<'_>, mapper: &mut EntityMapper) {
+        x.deref_mut::<T>().map_entities(mapper);
+    }
+
     /// Create a new `ComponentDescriptor` for the type `T`.
     pub fn new<T: Component>() -> Self {
+        let mut hooks = ComponentHooks::default();
+        T::register_component_hooks(&mut hooks);
         Self {
             name: Cow::Borrowed(std::any::type_name::<T>()),
             storage_type: T::Storage::STORAGE_TYPE,
@@ -359,6 +565,9 @@ impl ComponentDescriptor {
             type_id: Some(TypeId::of::<T>()),
             layout: Layout::new::<T>(),
             drop: needs_drop::<T>().then_some(Self::drop_ptr::<T> as _),
+            clone: None,
+            map_entities: None,
+            hooks,
         }
     }
 
@@ -380,6 +589,9 @@ impl ComponentDescriptor {
             type_id: None,
             layout,
             drop,
+            clone: None,
+            map_entities: None,
+            hooks: ComponentHooks::default(),
         }
     }
 
@@ -396,6 +608,9 @@ impl ComponentDescriptor {
             type_id: Some(TypeId::of::<T>()),
             layout: Layout::new::<T>(),
             drop: needs_drop::<T>().then_some(Self::drop_ptr::<T> as _),
+            clone: None,
+            map_entities: None,
+            hooks: ComponentHooks::default(),
         }
     }
 
@@ -407,9 +622,40 @@ impl ComponentDescriptor {
             type_id: Some(TypeId::of::<T>()),
             layout: Layout::new::<T>(),
             drop: needs_drop::<T>().then_some(Self::drop_ptr::<T> as _),
+            clone: None,
+            map_entities: None,
+            hooks: ComponentHooks::default(),
         }
     }
 
+    /// Sets the lifecycle hooks of the described component.
+    ///
+    /// This is mostly useful for components that do not correspond to a Rust type;
+    /// [`ComponentDescriptor::new`] uses the hooks set by [`Component::register_component_hooks`].
+    pub fn with_hooks(mut self, hooks: ComponentHooks) -> Self {
+        self.hooks = hooks;
+        self
+    }
+
+    /// Sets the function used to clone values of the described component.
+    ///
+    /// # Safety
+    /// - `clone` must be safe to call with a pointer to a value of the described component,
+    ///   and must pass a valid value of that component to its callback.
+    pub unsafe fn with_clone_fn(mut self, clone: ComponentCloneFn) -> Self {
+        self.clone = Some(clone);
+        self
+    }
+
+    /// Sets the function used to map the entities referenced by the described component.
+    ///
+    /// # Safety
+    /// - `map_entities` must be safe to call with a pointer to a value of the described component.
+    pub unsafe fn with_map_entities_fn(mut self, map_entities: ComponentMapEntitiesFn) -> Self {
+        self.map_entities = Some(map_entities);
+        self
+    }
+
     /// Returns a value indicating the storage strategy for the current component.
     #[inline]
     pub fn storage_type(&self) -> StorageType {
@@ -525,6 +771,46 @@ impl Components {
         self.components.get_unchecked(id.0)
     }
 
+    /// Gets the metadata associated with the given component mutably, which gives access to its [`ComponentHooks`].
+    #[inline]
+    pub(crate) fn get_info_mut(&mut self, id: ComponentId) -> Option<&mut ComponentInfo> {
+        self.components.get_mut(id.0)
+    }
+
+    /// Registers the [`Clone`] implementation of `T` as the clone function of the component or
+    /// resource with the given id.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `id` doesn't belong to the type `T`.
+    pub(crate) fn register_clone<T: Clone + 'static>(&mut self, id: ComponentId) {
+        let info = &mut self.components[id.0];
+        assert_eq!(
+            info.descriptor.type_id,
+            Some(TypeId::of::<T>()),
+            "{id:?} does not belong to {}",
+            std::any::type_name::<T>()
+        );
+        info.descriptor.clone = Some(ComponentDescriptor::clone_ptr::<T>);
+    }
+
+    /// Registers the [`MapEntities`] implementation of `T` as the map entities function of the
+    /// component with the given id.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `id` doesn't belong to the type `T`.
+    pub(crate) fn register_map_entities<T: MapEntities + 'static>(&mut self, id: ComponentId) {
+        let info = &mut self.components[id.0];
+        assert_eq!(
+            info.descriptor.type_id,
+            Some(TypeId::of::<T>()),
+            "{id:?} does not belong to {}",
+            std::any::type_name::<T>()
+        );
+        info.descriptor.map_entities = Some(ComponentDescriptor::map_entities_ptr::<T>);
+    }
+
     /// Type-erased equivalent of [`Components::component_id`].
     #[inline]
     pub fn get_id(&self, type_id: TypeId) -> Option<ComponentId> {
@@ -723,6 +1009,9 @@ pub struct TickCells<'a> {
     pub added: &'a UnsafeCell<Tick>,
     /// The tick indicating the last time the value was modified.
     pub changed: &'a UnsafeCell<Tick>,
+    /// Where the value was last modified through change detection.
+    #[cfg(feature = "track_change_detection")]
+    pub changed_by: &'a UnsafeCell<Option<ChangedBy>>,
 }
 
 impl<'a> TickCells<'a> {
@@ -847,3 +1136,175 @@ impl<T: Component> FromWorld for InitComponentId<T> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{ComponentHooks, ComponentId};
+    use crate::{
+        self as bevy_ecs,
+        component::Component,
+        entity::Entity,
+        system::Resource,
+        world::{DeferredWorld, World},
+    };
+
+    #[derive(Component)]
+    struct A;
+
+    #[derive(Component)]
+    struct B;
+
+    #[derive(Component)]
+    #[component(on_add = hooked_on_add, on_remove = hooked_on_remove)]
+    struct Hooked;
+
+    #[derive(Component, Debug, PartialEq)]
+    struct Mirror(Entity);
+
+    #[derive(Resource, Default, Debug, PartialEq)]
+    struct Log(Vec<&'static str>);
+
+    fn hooked_on_add(mut world: DeferredWorld, entity: Entity, _: ComponentId) {
+        world.commands().entity(entity).insert(Mirror(entity));
+    }
+
+    fn hooked_on_remove(mut world: DeferredWorld, entity: Entity, _: ComponentId) {
+        world.commands().entity(entity).remove::<Mirror>();
+    }
+
+    fn log(world: &mut DeferredWorld, message: &'static str) {
+        world.resource_mut::<Log>().0.push(message);
+    }
+
+    fn logging_world() -> World {
+        let mut world = World::new();
+        world.init_resource::<Log>();
+        world
+            .register_component_hooks::<A>()
+            .on_add(|mut world, _, _| log(&mut world, "add A"))
+            .on_insert(|mut world, _, _| log(&mut world, "insert A"))
+            .on_remove(|mut world, _, _| log(&mut world, "remove A"));
+        world
+    }
+
+    fn take_log(world: &mut World) -> Vec<&'static str> {
+        std::mem::take(&mut world.resource_mut::<Log>().0)
+    }
+
+    #[test]
+    fn hooks_run_on_spawn_insert_remove() {
+        let mut world = logging_world();
+
+        let entity = world.spawn(A).id();
+        assert_eq!(take_log(&mut world), vec!["add A", "insert A"]);
+
+        world.entity_mut(entity).insert(A);
+        assert_eq!(take_log(&mut world), vec!["insert A"]);
+
+        world.entity_mut(entity).insert(B);
+        assert!(take_log(&mut world).is_empty());
+
+        world.entity_mut(entity).remove::<(A, B)>();
+        assert_eq!(take_log(&mut world), vec!["remove A"]);
+
+        world.entity_mut(entity).remove::<A>();
+        assert!(take_log(&mut world).is_empty());
+
+        world.entity_mut(entity).insert((A, B));
+        assert_eq!(take_log(&mut world), vec!["add A", "insert A"]);
+
+        assert!(world.entity_mut(entity).take::<A>().is_some());
+        assert_eq!(take_log(&mut world), vec!["remove A"]);
+
+        world.entity_mut(entity).insert(A);
+        take_log(&mut world);
+        world.despawn(entity);
+        assert_eq!(take_log(&mut world), vec!["remove A"]);
+    }
+
+    #[test]
+    fn hooks_run_on_batches() {
+        let mut world = logging_world();
+        let existing = world.spawn(A).id();
+        take_log(&mut world);
+
+        world.spawn_batch([(A, B), (A, B)]).for_each(drop);
+        assert_eq!(
+            take_log(&mut world),
+            vec!["add A", "insert A", "add A", "insert A"]
+        );
+
+        let reserved = world.entities().reserve_entity();
+        world
+            .insert_or_spawn_batch([(existing, A), (reserved, A)])
+            .unwrap();
+        assert_eq!(take_log(&mut world), vec!["insert A", "add A", "insert A"]);
+    }
+
+    #[test]
+    fn hook_commands_are_applied_immediately() {
+        let mut world = World::new();
+
+        let entity = world.spawn(Hooked).id();
+        assert_eq!(world.get::<Mirror>(entity), Some(&Mirror(entity)));
+
+        world.entity_mut(entity).remove::<Hooked>();
+        assert!(world.get::<Mirror>(entity).is_none());
+
+        let mut entity_mut = world.spawn_empty();
+        entity_mut.insert(Hooked);
+        assert_eq!(entity_mut.get::<Mirror>(), Some(&Mirror(entity_mut.id())));
+    }
+
+    fn despawning_world() -> World {
+        let mut world = World::new();
+        world
+            .register_component_hooks::<A>()
+            .on_add(|mut world, entity, _| world.commands().entity(entity).despawn());
+        world
+    }
+
+    #[test]
+    fn hooks_despawn_on_spawn_and_insert() {
+        let mut world = despawning_world();
+
+        let entity = world.spawn(A).id();
+        assert!(world.get_entity(entity).is_none());
+
+        let mut entity_mut = world.spawn(B);
+        entity_mut.insert(A);
+        let entity = entity_mut.id();
+        // despawning the entity again does nothing
+        entity_mut.despawn();
+        assert!(world.get_entity(entity).is_none());
+    }
+
+    #[test]
+    #[should_panic = "was despawned by the commands of a component hook"]
+    fn get_after_spawn_despawned_by_hook() {
+        let mut world = despawning_world();
+        world.spawn(A).get::<A>();
+    }
+
+    #[test]
+    #[should_panic = "was despawned by the commands of a component hook"]
+    fn get_after_insert_despawned_by_hook() {
+        let mut world = despawning_world();
+        world.spawn(B).insert(A).get::<B>();
+    }
+
+    #[test]
+    #[should_panic = "was despawned by the commands of a component hook"]
+    fn insert_after_insert_despawned_by_hook() {
+        let mut world = despawning_world();
+        world.spawn(B).insert(A).insert(B);
+    }
+
+    #[test]
+    #[should_panic = "Component already has an on_add hook"]
+    fn hooks_cannot_be_overwritten() {
+        let mut hooks = ComponentHooks::default();
+        hooks.on_add(|_, _, _| {});
+        hooks.on_add(|_, _, _| {});
+    }
+}
@@ -0,0 +1,397 @@
+use std::any::TypeId;
+
+use bevy_utils::HashSet;
+
+use crate::{
+    component::{Component, ComponentId, ComponentInfo},
+    entity::{Entity, EntityMap},
+    query::DebugCheckedUnwrap,
+    storage::BlobVec,
+    world::World,
+};
+
+/// Clones entities along with their components.
+///
+/// Components are copied with the clone function registered for them, for example with
+/// [`World::register_component_clone`]. Components without a clone function are skipped.
+/// The clones get new change ticks, and their component hooks run as if they were inserted.
+///
+/// By default every component is cloned. This can be restricted with [`EntityCloner::deny`],
+/// or by switching to an allow list with [`EntityCloner::deny_all`] and [`EntityCloner::allow`].
+///
+/// When several entities are cloned together with [`EntityCloner::clone_entities`], components that have
+/// a [`MapEntities`](crate::entity::MapEntities) function registered with
+/// [`World::register_component_map_entities`] have their references to the cloned entities remapped to the clones.
+/// References to other entities are left unchanged.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::entity::EntityCloner;
+/// #[derive(Component, Clone)]
+/// struct Health(u32);
+///
+/// #[derive(Component, Clone)]
+/// struct Player;
+///
+/// let mut world = World::new();
+/// world.register_component_clone::<Health>();
+/// world.register_component_clone::<Player>();
+/// let player = world.spawn((Health(10), Player)).id();
+///
+/// let clone = EntityCloner::default()
+///     .deny::<Player>()
+///     .clone_entity(&mut world, player);
+/// assert_eq!(world.get::<Health>(clone).unwrap().0, 10);
+/// assert!(world.get::<Player>(clone).is_none());
+/// ```
+#[derive(Debug, Clone)]
+pub struct EntityCloner {
+    // If `true`, every component except the listed ones is cloned.
+    // Otherwise, only the listed components are cloned.
+    clone_all: bool,
+    type_ids: HashSet<TypeId>,
+    component_ids: HashSet<ComponentId>,
+    map_entities: bool,
+}
+
+impl Default for EntityCloner {
+    fn default() -> Self {
+        Self {
+            clone_all: true,
+            type_ids: HashSet::default(),
+            component_ids: HashSet::default(),
+            map_entities: true,
+        }
+    }
+}
+
+impl EntityCloner {
+    /// Clones the component `T`, even if [`EntityCloner::deny_all`] was called before.
+    pub fn allow<T: Component>(mut self) -> Self {
+        self.set_type_listed(TypeId::of::<T>(), !self.clone_all);
+        self
+    }
+
+    /// Clones the component with the given id, even if [`EntityCloner::deny_all`] was called before.
+    pub fn allow_id(mut self, component_id: ComponentId) -> Self {
+        self.set_id_listed(component_id, !self.clone_all);
+        self
+    }
+
+    /// Doesn't clone the component `T`.
+    pub fn deny<T: Component>(mut self) -> Self {
+        self.set_type_listed(TypeId::of::<T>(), self.clone_all);
+        self
+    }
+
+    /// Doesn't clone the component with the given id.
+    pub fn deny_id(mut self, component_id: ComponentId) -> Self {
+        self.set_id_listed(component_id, self.clone_all);
+        self
+    }
+
+    /// Clones every component, forgetting any previous [`EntityCloner::deny`].
+    pub fn allow_all(mut self) -> Self {
+        self.clone_all = true;
+        self.type_ids.clear();
+        self.component_ids.clear();
+        self
+    }
+
+    /// Clones no component, forgetting any previous [`EntityCloner::allow`].
+    /// Components can then be added back with [`EntityCloner::allow`].
+    pub fn deny_all(mut self) -> Self {
+        self.clone_all = false;
+        self.type_ids.clear();
+        self.component_ids.clear();
+        self
+    }
+
+    /// Sets whether the entity references of cloned components are remapped to the clones. Defaults to `true`.
+    pub fn map_entities(mut self, map_entities: bool) -> Self {
+        self.map_entities = map_entities;
+        self
+    }
+
+    /// Returns `true` if the component is cloned by this [`EntityCloner`], provided it has a clone function.
+    pub fn is_allowed(&self, info: &ComponentInfo) -> bool {
+        let listed = self.component_ids.contains(&info.id())
+            || info
+                .type_id()
+                .is_some_and(|type_id| self.type_ids.contains(&type_id));
+        listed != self.clone_all
+    }
+
+    fn set_type_listed(&mut self, type_id: TypeId, listed: bool) {
+        if listed {
+            self.type_ids.insert(type_id);
+        } else {
+            self.type_ids.remove(&type_id);
+        }
+    }
+
+    fn set_id_listed(&mut self, component_id: ComponentId, listed: bool) {
+        if listed {
+            self.component_ids.insert(component_id);
+        } else {
+            self.component_ids.remove(&component_id);
+        }
+    }
+
+    /// Spawns a clone of `source` and returns it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `source` doesn't exist.
+    pub fn clone_entity(&self, world: &mut World, source: Entity) -> Entity {
+        assert_source_exists(world, source);
+        let mut map = EntityMap::default();
+        map.insert(source, world.spawn_empty().id());
+        self.clone_mapped(world, &mut map);
+        map.get(source).unwrap()
+    }
+
+    /// Spawns a clone of each of the `sources`, and returns the map from each source to its clone.
+    ///
+    /// References between the sources are remapped to references between the clones.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any of the `sources` doesn't exist.
+    pub fn clone_entities(&self, world: &mut World, sources: &[Entity]) -> EntityMap {
+        // Nothing is spawned unless every source can be cloned.
+        for &source in sources {
+            assert_source_exists(world, source);
+        }
+        let mut map = EntityMap::default();
+        for &source in sources {
+            map.insert(source, world.spawn_empty().id());
+        }
+        self.clone_mapped(world, &mut map);
+        map
+    }
+
+    /// Clones the components of each source entity in `map` into the entity it is mapped to.
+    ///
+    /// References to the source entities are remapped through `map`. Components the targets already have
+    /// are overwritten.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any of the entities in `map` doesn't exist.
+    pub fn clone_mapped(&self, world: &mut World, map: &mut EntityMap) {
+        let pairs: Vec<(Entity, Entity)> = map.iter().collect();
+        // Nothing is cloned unless every entity exists.
+        for &(source, target) in &pairs {
+            assert_source_exists(world, source);
+            assert!(
+                world.get_entity(target).is_some(),
+                "Cannot clone into entity {target:?}: it doesn't exist."
+            );
+        }
+        for (source, target) in pairs {
+            let Some(source_ref) = world.get_entity(source) else {
+                // A hook despawned the source while the previous entities were cloned.
+                continue;
+            };
+
+            let mut component_ids = Vec::new();
+            let mut values = Vec::new();
+            for component_id in source_ref.archetype().components() {
+                // SAFETY: the component is in an archetype of this world.
+                let info = unsafe { world.components().get_info_unchecked(component_id) };
+                if !self.is_allowed(info) {
+                    continue;
+                }
+                let Some(clone) = info.clone_fn() else {
+                    continue;
+                };
+
+                // SAFETY: the drop function matches the layout of the component.
+                let mut value = unsafe { BlobVec::new(info.layout(), info.drop(), 1) };
+                // SAFETY: the source has the component, and `clone` and `value` both belong to it.
+                unsafe {
+                    let ptr = source_ref.get_by_id(component_id).debug_checked_unwrap();
+                    clone(ptr, &mut |ptr| value.push(ptr));
+                }
+                if let Some(map_entities) = info.map_entities_fn().filter(|_| self.map_entities) {
+                    map.passthrough_scope(|mapper| {
+                        // SAFETY: `value` holds the cloned value, and `map_entities` belongs to its component.
+                        unsafe { map_entities(value.get_unchecked_mut(0), mapper) };
+                    });
+                }
+                component_ids.push(component_id);
+                values.push(value);
+            }
+
+            if component_ids.is_empty() {
+                continue;
+            }
+            let Some(mut target_mut) = world.get_entity_mut(target) else {
+                // A hook despawned the target while the previous entities were cloned.
+                continue;
+            };
+            // SAFETY: each value was cloned from the component with the matching id. The values are
+            // forgotten by their `BlobVec`s, so they are only dropped by the target entity.
+            unsafe {
+                target_mut.insert_by_ids(
+                    &component_ids,
+                    values
+                        .iter_mut()
+                        .map(|value| value.swap_remove_and_forget_unchecked(0)),
+                );
+            }
+        }
+    }
+}
+
+fn assert_source_exists(world: &World, source: Entity) {
+    assert!(
+        world.get_entity(source).is_some(),
+        "Cannot clone entity {source:?}: it doesn't exist."
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        self as bevy_ecs,
+        component::Component,
+        entity::{Entity, EntityCloner, EntityMapper, MapEntities},
+        system::Resource,
+        world::World,
+    };
+
+    #[derive(Component, Clone, Debug, PartialEq)]
+    struct A(String);
+
+    #[derive(Component, Clone, Debug, PartialEq)]
+    #[component(storage = "SparseSet")]
+    struct B(usize);
+
+    #[derive(Component)]
+    struct NotCloned;
+
+    #[derive(Component, Clone)]
+    struct Target(Entity);
+
+    #[derive(Resource, Default)]
+    struct Added(usize);
+
+    impl MapEntities for Target {
+        fn map_entities(&mut self, entity_mapper: &mut EntityMapper) {
+            self.0 = entity_mapper.get_or_reserve(self.0);
+        }
+    }
+
+    fn world() -> World {
+        let mut world = World::new();
+        world.register_component_clone::<A>();
+        world.register_component_clone::<B>();
+        world.register_component_clone::<Target>();
+        world.register_component_map_entities::<Target>();
+        world
+    }
+
+    #[test]
+    fn clone_entity() {
+        let mut world = world();
+        let source = world.spawn((A("a".to_string()), B(1), NotCloned)).id();
+
+        let clone = world.clone_entity(source);
+
+        assert_ne!(clone, source);
+        assert_eq!(world.get::<A>(clone), Some(&A("a".to_string())));
+        assert_eq!(world.get::<B>(clone), Some(&B(1)));
+        assert!(world.get::<NotCloned>(clone).is_none());
+        assert!(world.get::<NotCloned>(source).is_some());
+    }
+
+    #[test]
+    fn deny_and_allow() {
+        let mut world = world();
+        let b = world.init_component::<B>();
+        let source = world.spawn((A("a".to_string()), B(1))).id();
+
+        let clone = EntityCloner::default()
+            .deny::<A>()
+            .clone_entity(&mut world, source);
+        assert!(world.get::<A>(clone).is_none());
+        assert!(world.get::<B>(clone).is_some());
+
+        let clone = EntityCloner::default()
+            .deny_all()
+            .allow_id(b)
+            .clone_entity(&mut world, source);
+        assert!(world.get::<A>(clone).is_none());
+        assert!(world.get::<B>(clone).is_some());
+
+        let clone = EntityCloner::default()
+            .deny::<A>()
+            .allow::<A>()
+            .clone_entity(&mut world, source);
+        assert!(world.get::<A>(clone).is_some());
+    }
+
+    #[test]
+    fn clone_entities_maps_references() {
+        let mut world = world();
+        let outside = world.spawn_empty().id();
+        let first = world.spawn_empty().id();
+        let second = world.spawn(Target(first)).id();
+        world.entity_mut(first).insert(Target(outside));
+
+        let map = EntityCloner::default().clone_entities(&mut world, &[first, second]);
+        let first_clone = map.get(first).unwrap();
+        let second_clone = map.get(second).unwrap();
+
+        assert_eq!(world.get::<Target>(first_clone).unwrap().0, outside);
+        assert_eq!(world.get::<Target>(second_clone).unwrap().0, first_clone);
+        assert_eq!(world.get::<Target>(second).unwrap().0, first);
+
+        let map = EntityCloner::default()
+            .map_entities(false)
+            .clone_entities(&mut world, &[first, second]);
+        assert_eq!(
+            world.get::<Target>(map.get(second).unwrap()).unwrap().0,
+            first
+        );
+    }
+
+    #[test]
+    fn clone_runs_hooks() {
+        let mut world = world();
+        world.init_resource::<Added>();
+        world
+            .register_component_hooks::<A>()
+            .on_add(|mut world, _, _| world.resource_mut::<Added>().0 += 1);
+        let source = world.spawn(A("a".to_string())).id();
+
+        world.clone_entity(source);
+
+        assert_eq!(world.resource::<Added>().0, 2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn clone_missing_entity() {
+        let mut world = world();
+        let entity = world.spawn_empty().id();
+        world.despawn(entity);
+        world.clone_entity(entity);
+    }
+
+    #[test]
+    fn clone_missing_entity_spawns_nothing() {
+        let mut world = world();
+        let source = world.spawn(B(1)).id();
+        let missing = world.spawn_empty().id();
+        world.despawn(missing);
+
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            EntityCloner::default().clone_entities(&mut world, &[source, missing])
+        }));
+        assert!(result.is_err());
+        assert_eq!(world.entities().len(), 1);
+    }
+}
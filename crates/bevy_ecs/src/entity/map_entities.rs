@@ -122,6 +122,21 @@ impl EntityMap {
         mapper.finish(world);
         result
     }
+
+    /// Creates an [`EntityMapper`] from this [`EntityMap`] that leaves entities missing from the map unchanged,
+    /// then calls the provided function with it.
+    ///
+    /// This is useful when the source and destination entities live in the same [`World`], like when
+    /// [cloning entities](crate::entity::EntityCloner): references to entities outside the mapped set
+    /// keep pointing at the same entity instead of being mapped to a dead one.
+    pub fn passthrough_scope<R>(&mut self, f: impl FnOnce(&mut EntityMapper) -> R) -> R {
+        let mut mapper = EntityMapper {
+            map: self,
+            dead_start: None,
+            generations: 0,
+        };
+        f(&mut mapper)
+    }
 }
 
 /// A wrapper for [`EntityMap`], augmenting it with the ability to allocate new [`Entity`] references in a destination
@@ -130,26 +145,34 @@ impl EntityMap {
 /// References are allocated by returning increasing generations starting from an internally initialized base
 /// [`Entity`]. After it is finished being used by [`MapEntities`] implementations, this entity is despawned and the
 /// requisite number of generations reserved.
+///
+/// A mapper created with [`EntityMap::passthrough_scope`] doesn't allocate new references: entities missing from
+/// the map are returned unchanged.
 pub struct EntityMapper<'m> {
     /// The wrapped [`EntityMap`].
     map: &'m mut EntityMap,
-    /// A base [`Entity`] used to allocate new references.
-    dead_start: Entity,
+    /// A base [`Entity`] used to allocate new references, or `None` if unmapped entities are passed through.
+    dead_start: Option<Entity>,
     /// The number of generations this mapper has allocated thus far.
     generations: u32,
 }
 
 impl<'m> EntityMapper<'m> {
     /// Returns the corresponding mapped entity or reserves a new dead entity ID if it is absent.
+    ///
+    /// If this mapper was created with [`EntityMap::passthrough_scope`], absent entities are returned unchanged.
     pub fn get_or_reserve(&mut self, entity: Entity) -> Entity {
         if let Some(mapped) = self.map.get(entity) {
             return mapped;
         }
+        let Some(dead_start) = self.dead_start else {
+            return entity;
+        };
 
         // this new entity reference is specifically designed to never represent any living entity
         let new = Entity {
-            generation: self.dead_start.generation + self.generations,
-            index: self.dead_start.index,
+            generation: dead_start.generation + self.generations,
+            index: dead_start.index,
         };
         self.generations += 1;
 
@@ -173,7 +196,7 @@ impl<'m> EntityMapper<'m> {
         Self {
             map,
             // SAFETY: Entities data is kept in a valid state via `EntityMap::world_scope`
-            dead_start: unsafe { world.entities_mut().alloc() },
+            dead_start: Some(unsafe { world.entities_mut().alloc() }),
             generations: 0,
         }
     }
@@ -183,10 +206,13 @@ impl<'m> EntityMapper<'m> {
     /// renders the [`EntityMapper`] unable to safely allocate any more references, this method takes ownership of
     /// `self` in order to render it unusable.
     fn finish(self, world: &mut World) {
+        let Some(dead_start) = self.dead_start else {
+            return;
+        };
         // SAFETY: Entities data is kept in a valid state via `EntityMap::world_scope`
         let entities = unsafe { world.entities_mut() };
-        assert!(entities.free(self.dead_start).is_some());
-        assert!(entities.reserve_generations(self.dead_start.index, self.generations));
+        assert!(entities.free(dead_start).is_some());
+        assert!(entities.reserve_generations(dead_start.index, self.generations));
     }
 }
 
@@ -239,4 +265,25 @@ mod tests {
         assert_eq!(entity.index(), dead_ref.index());
         assert!(entity.generation() > dead_ref.generation());
     }
+
+    #[test]
+    fn passthrough_scope_keeps_unmapped_entities() {
+        let mut map = EntityMap::default();
+        map.insert(Entity::new(0, 0), Entity::new(1, 0));
+
+        let (mapped, unmapped) = map.passthrough_scope(|mapper| {
+            (
+                mapper.get_or_reserve(Entity::new(0, 0)),
+                mapper.get_or_reserve(Entity::new(2, 0)),
+            )
+        });
+
+        assert_eq!(mapped, Entity::new(1, 0));
+        assert_eq!(unmapped, Entity::new(2, 0));
+        assert_eq!(
+            map.len(),
+            1,
+            "unmapped entities should not be added to the map"
+        );
+    }
 }
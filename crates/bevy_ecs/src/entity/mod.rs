@@ -35,8 +35,10 @@
 //! [`World::despawn`]: crate::world::World::despawn
 //! [`EntityMut::insert`]: crate::world::EntityMut::insert
 //! [`EntityMut::remove`]: crate::world::EntityMut::remove
+mod clone_entities;
 mod map_entities;
 
+pub use clone_entities::*;
 pub use map_entities::*;
 
 use crate::{
@@ -565,6 +567,15 @@ impl Entities {
         Some(loc)
     }
 
+    /// Calls `update` with the location of each entity that is stored in an archetype.
+    pub(crate) fn update_locations(&mut self, mut update: impl FnMut(&mut EntityLocation)) {
+        for meta in &mut self.meta {
+            if meta.location.archetype_id != ArchetypeId::INVALID {
+                update(&mut meta.location);
+            }
+        }
+    }
+
     /// Ensure at least `n` allocations can succeed without reallocating.
     pub fn reserve(&mut self, additional: u32) {
         self.verify_flushed();
@@ -665,6 +676,48 @@ impl Entities {
         }
     }
 
+    /// Saves the state of the allocator: the generation of every index, and the free list.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are reserved entities that haven't been flushed.
+    pub(crate) fn snapshot(&mut self) -> EntitiesSnapshot {
+        assert!(
+            !self.needs_flush(),
+            "Entities must be flushed before taking a snapshot"
+        );
+        EntitiesSnapshot {
+            generations: self.meta.iter().map(|meta| meta.generation).collect(),
+            pending: self.pending.clone(),
+            len: self.len,
+        }
+    }
+
+    /// Restores the allocator state saved by [`Entities::snapshot`], so that the same entities are
+    /// allocated from now on.
+    ///
+    /// The locations of entities are kept, so the caller must make sure that exactly the entities
+    /// that were alive when the snapshot was taken are alive.
+    pub(crate) fn restore(&mut self, snapshot: &EntitiesSnapshot) {
+        assert!(
+            !self.needs_flush(),
+            "Entities must be flushed before restoring a snapshot"
+        );
+        debug_assert!(self
+            .meta
+            .iter()
+            .skip(snapshot.generations.len())
+            .all(|meta| meta.location.archetype_id == ArchetypeId::INVALID));
+        self.meta
+            .resize(snapshot.generations.len(), EntityMeta::EMPTY);
+        for (meta, &generation) in self.meta.iter_mut().zip(&snapshot.generations) {
+            meta.generation = generation;
+        }
+        self.pending.clone_from(&snapshot.pending);
+        *self.free_cursor.get_mut() = self.pending.len() as IdCursor;
+        self.len = snapshot.len;
+    }
+
     fn needs_flush(&mut self) -> bool {
         *self.free_cursor.get_mut() != self.pending.len() as IdCursor
     }
@@ -768,6 +821,14 @@ impl Entities {
     }
 }
 
+/// The allocator state of [`Entities`], saved by [`Entities::snapshot`].
+#[derive(Debug, Clone)]
+pub(crate) struct EntitiesSnapshot {
+    generations: Vec<u32>,
+    pending: Vec<u32>,
+    len: u32,
+}
+
 // This type is repr(C) to ensure that the layout and values within it can be safe to fully fill
 // with u8::MAX, as required by [`Entities::flush_and_reserve_invalid_assuming_no_entities`].
 // Safety:
@@ -821,7 +882,7 @@ pub struct EntityLocation {
 
 impl EntityLocation {
     /// location for **pending entity** and **invalid entity**
-    const INVALID: EntityLocation = EntityLocation {
+    pub(crate) const INVALID: EntityLocation = EntityLocation {
         archetype_id: ArchetypeId::INVALID,
         archetype_row: ArchetypeRow::INVALID,
         table_id: TableId::INVALID,
@@ -3,15 +3,20 @@
 use crate as bevy_ecs;
 use crate::system::{Local, Res, ResMut, Resource, SystemParam};
 pub use bevy_ecs_macros::Event;
-use bevy_utils::detailed_trace;
+use bevy_utils::{detailed_trace, tracing::warn};
 use std::ops::{Deref, DerefMut};
 use std::{
     cmp::Ordering,
+    collections::VecDeque,
     fmt,
     hash::{Hash, Hasher},
     iter::Chain,
     marker::PhantomData,
     slice::Iter,
+    sync::{
+        atomic::{AtomicUsize, Ordering as AtomicOrdering},
+        Arc, Mutex, Weak,
+    },
 };
 /// A type that can be stored in an [`Events<E>`] resource
 /// You can conveniently access events using the [`EventReader`] and [`EventWriter`] system parameter.
@@ -109,7 +114,11 @@ struct EventInstance<E: Event> {
 /// Events will persist across a single frame boundary and so ordering of event producers and
 /// consumers is not critical (although poorly-planned ordering may cause accumulating lag).
 /// If events are not handled by the end of the frame after they are updated, they will be
-/// dropped silently.
+/// dropped. Readers that miss events log a warning, and the number of missed events is counted
+/// by [`Events::missed_event_count`].
+///
+/// How long events are kept can be configured with an [`EventRetention`] policy,
+/// for example for events read by systems that don't run every frame.
 ///
 /// # Example
 /// ```
@@ -151,6 +160,9 @@ struct EventInstance<E: Event> {
 ///
 /// The buffers in [`Events`] will grow indefinitely if [`update`](Events::update) is never called.
 ///
+/// With an [`EventRetention`] other than the default, the oldest buffer keeps the events
+/// of several updates, and [`update`](Events::update) only clears the events the policy allows to drop.
+///
 /// An alternative call pattern would be to call [`update`](Events::update)
 /// manually across frames to control when events are cleared.
 /// This complicates consumption and risks ever-expanding memory usage if not cleaned up,
@@ -168,6 +180,12 @@ pub struct Events<E: Event> {
     /// Holds the newer events.
     events_b: EventSequence<E>,
     event_count: usize,
+    retention: EventRetention,
+    /// The event count at each of the last [`Events::update`] calls, oldest first.
+    update_starts: VecDeque<usize>,
+    /// The position of every reader that has read these events.
+    readers: Mutex<Vec<Weak<AtomicUsize>>>,
+    missed_event_count: AtomicUsize,
 }
 
 // Derived Default impl would incorrectly require E: Default
@@ -177,10 +195,42 @@ impl<E: Event> Default for Events<E> {
             events_a: Default::default(),
             events_b: Default::default(),
             event_count: Default::default(),
+            retention: Default::default(),
+            update_starts: Default::default(),
+            readers: Default::default(),
+            missed_event_count: Default::default(),
         }
     }
 }
 
+/// How long an [`Events`] collection keeps its events, set with [`Events::set_retention`].
+///
+/// Events that are dropped before a reader reads them are reported as missed events,
+/// see [`Events::missed_event_count`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EventRetention {
+    /// Events are dropped by the given number of [`Events::update`] calls after they were sent.
+    ///
+    /// The default is `Updates(2)`: events sent before an update are kept until the next one.
+    /// A value of 0 is treated as 1.
+    Updates(usize),
+    /// Events are kept for at least two updates, and after that until every reader that has
+    /// read these events once has read them.
+    ///
+    /// Readers are tracked until they are dropped, so a reader that stops reading, like an
+    /// [`EventReader`] in a system that no longer runs, keeps all later events alive.
+    UntilConsumed,
+    /// Events are never dropped by [`Events::update`], only by [`Events::clear`],
+    /// [`Events::drain`] or [`Events::drop_events_before`].
+    Manual,
+}
+
+impl Default for EventRetention {
+    fn default() -> Self {
+        Self::Updates(2)
+    }
+}
+
 impl<E: Event> Events<E> {
     /// Returns the index of the oldest event stored in the event buffer.
     pub fn oldest_event_count(&self) -> usize {
@@ -188,6 +238,60 @@ impl<E: Event> Events<E> {
             .start_event_count
             .min(self.events_b.start_event_count)
     }
+
+    /// Creates an empty event collection with the given [`EventRetention`] policy.
+    pub fn with_retention(retention: EventRetention) -> Self {
+        Self {
+            retention,
+            ..Default::default()
+        }
+    }
+
+    /// Returns the [`EventRetention`] policy of these events.
+    pub fn retention(&self) -> EventRetention {
+        self.retention
+    }
+
+    /// Sets the [`EventRetention`] policy of these events, which applies from the next [`Events::update`].
+    pub fn set_retention(&mut self, retention: EventRetention) {
+        self.retention = retention;
+    }
+
+    /// Returns the total number of events that readers missed, because the events were dropped
+    /// before the readers read them.
+    pub fn missed_event_count(&self) -> usize {
+        self.missed_event_count.load(AtomicOrdering::Relaxed)
+    }
+
+    /// Tracks the position of a reader, so events kept until consumed wait for it.
+    fn register_reader(&self, position: usize) -> Arc<AtomicUsize> {
+        let cursor = Arc::new(AtomicUsize::new(position));
+        let mut readers = self.readers.lock().unwrap_or_else(|e| e.into_inner());
+        readers.retain(|reader| reader.strong_count() > 0);
+        readers.push(Arc::downgrade(&cursor));
+        cursor
+    }
+
+    /// Returns the position of the reader that has read the fewest events, if any reader is tracked.
+    fn slowest_reader(&self) -> Option<usize> {
+        let mut readers = self.readers.lock().unwrap_or_else(|e| e.into_inner());
+        readers.retain(|reader| reader.strong_count() > 0);
+        readers
+            .iter()
+            .filter_map(|reader| reader.upgrade())
+            .map(|cursor| cursor.load(AtomicOrdering::Relaxed))
+            .min()
+    }
+
+    fn report_missed_events(&self, missed: usize) {
+        self.missed_event_count
+            .fetch_add(missed, AtomicOrdering::Relaxed);
+        warn!(
+            "A reader missed {missed} events of type `{}`, which were dropped before it read them. \
+            Consider reading these events more often, or changing their `EventRetention`.",
+            std::any::type_name::<E>()
+        );
+    }
 }
 
 #[derive(Debug)]
@@ -367,6 +471,11 @@ impl<'w, E: Event> EventWriter<'w, E> {
 #[derive(Debug)]
 pub struct ManualEventReader<E: Event> {
     last_event_count: usize,
+    /// Whether this reader has read events, after which it reports the events it missed.
+    has_read: bool,
+    /// The position of this reader, shared with the [`Events`] it reads once it has read them,
+    /// if they are kept until consumed.
+    cursor: Option<Arc<AtomicUsize>>,
     _marker: PhantomData<E>,
 }
 
@@ -374,6 +483,8 @@ impl<E: Event> Default for ManualEventReader<E> {
     fn default() -> Self {
         ManualEventReader {
             last_event_count: 0,
+            has_read: false,
+            cursor: None,
             _marker: Default::default(),
         }
     }
@@ -421,6 +532,14 @@ impl<E: Event> ManualEventReader<E> {
     /// See [`EventReader::clear()`]
     pub fn clear(&mut self, events: &Events<E>) {
         self.last_event_count = events.event_count;
+        self.store_position();
+    }
+
+    /// Shares the position of this reader with the [`Events`] it reads.
+    fn store_position(&self) {
+        if let Some(cursor) = &self.cursor {
+            cursor.store(self.last_event_count, AtomicOrdering::Relaxed);
+        }
     }
 }
 
@@ -472,6 +591,19 @@ pub struct ManualEventIteratorWithId<'a, E: Event> {
 impl<'a, E: Event> ManualEventIteratorWithId<'a, E> {
     /// Creates a new iterator that yields any `events` that have not yet been seen by `reader`.
     pub fn new(reader: &'a mut ManualEventReader<E>, events: &'a Events<E>) -> Self {
+        // Events dropped before a reader first reads aren't missed by it.
+        if reader.has_read {
+            let missed = reader.missed_events(events);
+            if missed > 0 {
+                events.report_missed_events(missed);
+            }
+        }
+        reader.has_read = true;
+        // Only readers of events kept until consumed hold back their removal.
+        if reader.cursor.is_none() && events.retention == EventRetention::UntilConsumed {
+            reader.cursor = Some(events.register_reader(reader.last_event_count));
+        }
+
         let a_index = (reader.last_event_count).saturating_sub(events.events_a.start_event_count);
         let b_index = (reader.last_event_count).saturating_sub(events.events_b.start_event_count);
         let a = events.events_a.get(a_index..).unwrap_or_default();
@@ -481,6 +613,7 @@ impl<'a, E: Event> ManualEventIteratorWithId<'a, E> {
         // Ensure `len` is implemented correctly
         debug_assert_eq!(unread_count, reader.len(events));
         reader.last_event_count = events.event_count - unread_count;
+        reader.store_position();
         // Iterate the oldest first, then the newer events
         let chain = a.iter().chain(b.iter());
 
@@ -508,6 +641,7 @@ impl<'a, E: Event> Iterator for ManualEventIteratorWithId<'a, E> {
             Some(item) => {
                 detailed_trace!("EventReader::iter() -> {}", item.1);
                 self.reader.last_event_count += 1;
+                self.reader.store_position();
                 self.unread -= 1;
                 Some(item)
             }
@@ -518,10 +652,12 @@ impl<'a, E: Event> Iterator for ManualEventIteratorWithId<'a, E> {
     fn nth(&mut self, n: usize) -> Option<Self::Item> {
         if let Some(EventInstance { event_id, event }) = self.chain.nth(n) {
             self.reader.last_event_count += n + 1;
+            self.reader.store_position();
             self.unread -= n + 1;
             Some((event, *event_id))
         } else {
             self.reader.last_event_count += self.unread;
+            self.reader.store_position();
             self.unread = 0;
             None
         }
@@ -533,11 +669,13 @@ impl<'a, E: Event> Iterator for ManualEventIteratorWithId<'a, E> {
     {
         let EventInstance { event_id, event } = self.chain.last()?;
         self.reader.last_event_count += self.unread;
+        self.reader.store_position();
         Some((event, *event_id))
     }
 
     fn count(self) -> usize {
         self.reader.last_event_count += self.unread;
+        self.reader.store_position();
         self.unread
     }
 
@@ -592,9 +730,40 @@ impl<E: Event> Events<E> {
 
     /// Swaps the event buffers and clears the oldest event buffer. In general, this should be
     /// called once per frame/update.
+    ///
+    /// With an [`EventRetention`] other than the default, the events of the newest buffer are
+    /// moved to the oldest one instead, and only the events the policy allows to drop are cleared.
     pub fn update(&mut self) {
-        std::mem::swap(&mut self.events_a, &mut self.events_b);
-        self.events_b.clear();
+        let updates = match self.retention {
+            EventRetention::Updates(updates) => updates.max(1),
+            EventRetention::UntilConsumed | EventRetention::Manual => 2,
+        };
+        self.update_starts.push_back(self.event_count);
+        while self.update_starts.len() > updates {
+            self.update_starts.pop_front();
+        }
+        // the events sent before the first update kept are dropped
+        let oldest_kept = if self.update_starts.len() == updates {
+            self.update_starts[0]
+        } else {
+            0
+        };
+        let oldest_kept = match self.retention {
+            EventRetention::Updates(_) => oldest_kept,
+            EventRetention::UntilConsumed => self
+                .slowest_reader()
+                .map_or(oldest_kept, |position| position.min(oldest_kept)),
+            EventRetention::Manual => 0,
+        };
+        self.drop_events_before(oldest_kept);
+
+        if self.events_a.is_empty() {
+            std::mem::swap(&mut self.events_a, &mut self.events_b);
+            self.events_b.clear();
+        } else {
+            let events_b = std::mem::take(&mut self.events_b.events);
+            self.events_a.extend(events_b);
+        }
         self.events_b.start_event_count = self.event_count;
         debug_assert_eq!(
             self.events_a.start_event_count + self.events_a.len(),
@@ -602,6 +771,21 @@ impl<E: Event> Events<E> {
         );
     }
 
+    /// Drops the events sent before the event with the given id, whatever the [`EventRetention`] policy.
+    pub fn drop_events_before(&mut self, id: usize) {
+        let dropped = id
+            .saturating_sub(self.events_a.start_event_count)
+            .min(self.events_a.len());
+        self.events_a.drain(..dropped);
+        self.events_a.start_event_count += dropped;
+        if self.events_a.is_empty() && id > self.events_b.start_event_count {
+            let dropped = (id - self.events_b.start_event_count).min(self.events_b.len());
+            self.events_b.drain(..dropped);
+            self.events_b.start_event_count += dropped;
+            self.events_a.start_event_count = self.events_b.start_event_count;
+        }
+    }
+
     /// A system that calls [`Events::update`] once per frame.
     pub fn update_system(mut events: ResMut<Self>) {
         events.update();
@@ -1055,4 +1239,119 @@ mod tests {
         }
         read_for::<EmptyTestEvent>();
     }
+
+    #[test]
+    fn test_retention_updates() {
+        let mut events = Events::<E>::with_retention(EventRetention::Updates(3));
+        let mut reader = events.get_reader();
+
+        events.send(E(0));
+        events.update();
+        events.send(E(1));
+        events.update();
+        events.send(E(2));
+        events.update();
+        assert!(reader.iter(&events).eq([E(1), E(2)].iter()));
+        assert_eq!(events.missed_event_count(), 0);
+
+        events.send(E(3));
+        events.update();
+        events.update();
+        assert!(reader.iter(&events).eq([E(3)].iter()));
+
+        events.set_retention(EventRetention::Updates(1));
+        events.send(E(4));
+        events.update();
+        assert!(events.is_empty());
+    }
+
+    #[test]
+    fn test_retention_until_consumed() {
+        let mut events = Events::<E>::with_retention(EventRetention::UntilConsumed);
+        let mut fast = events.get_reader();
+        let mut slow = events.get_reader();
+        assert_eq!(fast.iter(&events).count(), 0);
+        assert_eq!(slow.iter(&events).count(), 0);
+
+        events.send(E(0));
+        for _ in 0..5 {
+            events.update();
+            fast.iter(&events).count();
+        }
+        assert!(slow.iter(&events).eq([E(0)].iter()));
+
+        events.send(E(1));
+        events.update();
+        events.update();
+        events.update();
+        assert_eq!(events.len(), 1);
+
+        // dropped readers no longer keep events alive
+        drop(slow);
+        assert!(fast.iter(&events).eq([E(1)].iter()));
+        events.update();
+        assert!(events.is_empty());
+        assert_eq!(events.missed_event_count(), 0);
+    }
+
+    #[test]
+    fn test_retention_tracks_readers_until_consumed() {
+        let mut events = Events::<E>::default();
+        let mut reader = events.get_reader();
+        events.send(E(0));
+        assert_eq!(reader.iter(&events).count(), 1);
+        assert!(events.readers.lock().unwrap().is_empty());
+
+        events.set_retention(EventRetention::UntilConsumed);
+        events.send(E(1));
+        assert_eq!(reader.iter(&events).count(), 1);
+        assert_eq!(events.readers.lock().unwrap().len(), 1);
+
+        events.send(E(2));
+        for _ in 0..5 {
+            events.update();
+        }
+        assert!(reader.iter(&events).eq([E(2)].iter()));
+    }
+
+    #[test]
+    fn test_retention_manual() {
+        let mut events = Events::<E>::with_retention(EventRetention::Manual);
+        let mut reader = events.get_reader();
+
+        events.send(E(0));
+        events.send(E(1));
+        for _ in 0..5 {
+            events.update();
+        }
+        events.send(E(2));
+        assert_eq!(events.get_event(0).map(|(event, _)| event), Some(&E(0)));
+
+        events.drop_events_before(1);
+        assert!(reader.iter(&events).eq([E(1), E(2)].iter()));
+        events.drop_events_before(3);
+        assert!(events.is_empty());
+    }
+
+    #[test]
+    fn test_missed_events() {
+        let mut events = Events::<E>::default();
+        let mut reader = events.get_reader();
+
+        events.send(E(0));
+        events.update();
+        events.update();
+        // events dropped before the first read are not missed
+        assert_eq!(reader.iter(&events).count(), 0);
+        assert_eq!(events.missed_event_count(), 0);
+
+        events.send(E(1));
+        events.send(E(2));
+        events.update();
+        events.send(E(3));
+        events.update();
+        assert_eq!(reader.missed_events(&events), 2);
+        assert!(reader.iter(&events).eq([E(3)].iter()));
+        assert_eq!(events.missed_event_count(), 2);
+    }
 }
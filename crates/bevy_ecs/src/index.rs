@@ -0,0 +1,294 @@
+//! Hash indexes over component values, to look up entities by the value of one of their components.
+//!
+//! See [`ComponentIndex`] for more details.
+
+use std::hash::Hash;
+
+use bevy_utils::{HashMap, HashSet};
+
+use crate::{
+    self as bevy_ecs,
+    component::{Component, ComponentId},
+    entity::Entity,
+    query::Changed,
+    system::{Query, Res, ResMut, Resource},
+    world::{DeferredWorld, World},
+};
+
+/// The entities of a [`World`] grouped by the value of their component `C`.
+///
+/// The index is created with [`init_index`], or `add_index::<C>()` in an app. It is kept up to date
+/// incrementally: component hooks track the components `C` as soon as they are inserted or removed,
+/// and the [`refresh_index`] system picks up the values changed through
+/// [`Mut<C>`](crate::change_detection::Mut), from the components `C` [`Changed`] since it last ran.
+///
+/// Systems look up entities through the read-only [`Index<C>`] parameter, so they can run in
+/// parallel with each other and mutate the components `C`.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::index::Index;
+/// #[derive(Component, PartialEq, Eq, Hash, Clone)]
+/// struct NetworkId(u64);
+///
+/// #[derive(Event)]
+/// struct Disconnected(NetworkId);
+///
+/// fn despawn_disconnected(
+///     mut commands: Commands,
+///     mut disconnected: EventReader<Disconnected>,
+///     index: Index<NetworkId>,
+/// ) {
+///     for Disconnected(id) in disconnected.iter() {
+///         for &entity in index.get(id) {
+///             commands.entity(entity).despawn();
+///         }
+///     }
+/// }
+/// # bevy_ecs::system::assert_is_system(despawn_disconnected);
+/// ```
+#[derive(Resource)]
+pub struct ComponentIndex<C: Component + Eq + Hash + Clone> {
+    entities: HashMap<C, HashSet<Entity>>,
+    values: HashMap<Entity, C>,
+    empty: HashSet<Entity>,
+}
+
+/// A [`SystemParam`](crate::system::SystemParam) looking up entities by the value of their
+/// component `C`, through its [`ComponentIndex<C>`].
+pub type Index<'w, C> = Res<'w, ComponentIndex<C>>;
+
+impl<C: Component + Eq + Hash + Clone> Default for ComponentIndex<C> {
+    fn default() -> Self {
+        Self {
+            entities: HashMap::default(),
+            values: HashMap::default(),
+            empty: HashSet::default(),
+        }
+    }
+}
+
+impl<C: Component + Eq + Hash + Clone> ComponentIndex<C> {
+    /// Returns the entities whose component `C` is equal to `value`.
+    pub fn get(&self, value: &C) -> &HashSet<Entity> {
+        self.entities.get(value).unwrap_or(&self.empty)
+    }
+
+    /// Returns the single entity whose component `C` is equal to `value`,
+    /// or `None` if there are no such entities or more than one.
+    pub fn get_single(&self, value: &C) -> Option<Entity> {
+        let entities = self.get(value);
+        if entities.len() == 1 {
+            entities.iter().next().copied()
+        } else {
+            None
+        }
+    }
+
+    /// Returns `true` if an entity has a component `C` equal to `value`.
+    pub fn contains(&self, value: &C) -> bool {
+        !self.get(value).is_empty()
+    }
+
+    /// Returns the value of the component `C` of `entity` in the index.
+    pub fn value(&self, entity: Entity) -> Option<&C> {
+        self.values.get(&entity)
+    }
+
+    /// Returns the number of distinct values of the component `C` in the index.
+    pub fn len(&self) -> usize {
+        self.entities.len()
+    }
+
+    /// Returns `true` if no entity is in the index.
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+
+    fn insert(&mut self, entity: Entity, value: &C) {
+        if self.values.get(&entity) == Some(value) {
+            return;
+        }
+        self.remove(entity);
+        self.entities
+            .entry(value.clone())
+            .or_default()
+            .insert(entity);
+        self.values.insert(entity, value.clone());
+    }
+
+    fn remove(&mut self, entity: Entity) {
+        let Some(value) = self.values.remove(&entity) else {
+            return;
+        };
+        if let Some(entities) = self.entities.get_mut(&value) {
+            entities.remove(&entity);
+            if entities.is_empty() {
+                self.entities.remove(&value);
+            }
+        }
+    }
+
+    fn on_insert(mut world: DeferredWorld, entity: Entity, _: ComponentId) {
+        let Some(value) = world.get::<C>(entity).cloned() else {
+            return;
+        };
+        if let Some(mut index) = world.get_resource_mut::<Self>() {
+            index.insert(entity, &value);
+        }
+    }
+
+    fn on_remove(mut world: DeferredWorld, entity: Entity, _: ComponentId) {
+        if let Some(mut index) = world.get_resource_mut::<Self>() {
+            index.remove(entity);
+        }
+    }
+}
+
+/// Inserts the [`ComponentIndex<C>`] resource, and registers the hooks of the component `C`
+/// keeping it up to date. Does nothing if the index already exists.
+///
+/// The values of the components `C` existing before the index are added by [`refresh_index`].
+///
+/// # Panics
+///
+/// Panics if the component `C` already has an `on_insert` or `on_remove` hook.
+pub fn init_index<C: Component + Eq + Hash + Clone>(world: &mut World) {
+    if world.contains_resource::<ComponentIndex<C>>() {
+        return;
+    }
+    world.init_resource::<ComponentIndex<C>>();
+    world
+        .register_component_hooks::<C>()
+        .try_on_insert(ComponentIndex::<C>::on_insert)
+        .and_then(|hooks| hooks.try_on_remove(ComponentIndex::<C>::on_remove))
+        .unwrap_or_else(|| {
+            panic!(
+                "Cannot index the component {}: it already has component hooks",
+                std::any::type_name::<C>()
+            )
+        });
+}
+
+/// Updates the [`ComponentIndex<C>`] with the components `C` changed since this system last ran.
+///
+/// Added to the `Last` schedule by `add_index::<C>()`.
+pub fn refresh_index<C: Component + Eq + Hash + Clone>(
+    mut index: ResMut<ComponentIndex<C>>,
+    changed: Query<(Entity, &C), Changed<C>>,
+) {
+    for (entity, value) in &changed {
+        index.insert(entity, value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_utils::HashSet;
+
+    use crate::{
+        self as bevy_ecs,
+        index::{init_index, refresh_index, ComponentIndex, Index},
+        prelude::*,
+    };
+
+    #[derive(Component, PartialEq, Eq, Hash, Clone, Debug)]
+    struct NetworkId(u8);
+
+    #[derive(Resource, Default)]
+    struct Found(Vec<HashSet<Entity>>);
+
+    fn find(index: Index<NetworkId>, mut found: ResMut<Found>) {
+        found.0 = (0..3).map(|id| index.get(&NetworkId(id)).clone()).collect();
+    }
+
+    fn run(world: &mut World, schedule: &mut Schedule) -> Vec<HashSet<Entity>> {
+        schedule.run(world);
+        world.clear_trackers();
+        std::mem::take(&mut world.resource_mut::<Found>().0)
+    }
+
+    #[test]
+    fn index_stays_in_sync() {
+        let mut world = World::new();
+        init_index::<NetworkId>(&mut world);
+        world.init_resource::<Found>();
+        let mut schedule = Schedule::new();
+        schedule.add_systems((refresh_index::<NetworkId>, find).chain());
+
+        let a = world.spawn(NetworkId(0)).id();
+        let b = world.spawn(NetworkId(1)).id();
+        let c = world.spawn(NetworkId(1)).id();
+        assert_eq!(
+            run(&mut world, &mut schedule),
+            vec![[a].into(), [b, c].into(), HashSet::new()]
+        );
+
+        world.get_mut::<NetworkId>(b).unwrap().0 = 2;
+        world.entity_mut(c).remove::<NetworkId>();
+        assert_eq!(
+            run(&mut world, &mut schedule),
+            vec![[a].into(), HashSet::new(), [b].into()]
+        );
+
+        // a removal followed by an insertion in the same update
+        world.entity_mut(a).remove::<NetworkId>();
+        world.entity_mut(a).insert(NetworkId(1));
+        world.despawn(b);
+        // marked as changed without changing its value
+        world.get_mut::<NetworkId>(a).unwrap().set_changed();
+        assert_eq!(
+            run(&mut world, &mut schedule),
+            vec![HashSet::new(), [a].into(), HashSet::new()]
+        );
+
+        let index = world.resource::<ComponentIndex<NetworkId>>();
+        assert_eq!(index.len(), 1);
+        assert_eq!(index.value(a), Some(&NetworkId(1)));
+    }
+
+    #[test]
+    fn hooks_update_index() {
+        let mut world = World::new();
+        init_index::<NetworkId>(&mut world);
+
+        // insertions and removals are indexed right away, without running `refresh_index`
+        let entity = world.spawn(NetworkId(0)).id();
+        assert_eq!(
+            world
+                .resource::<ComponentIndex<NetworkId>>()
+                .get_single(&NetworkId(0)),
+            Some(entity)
+        );
+        world.entity_mut(entity).insert(NetworkId(1));
+        let index = world.resource::<ComponentIndex<NetworkId>>();
+        assert!(!index.contains(&NetworkId(0)));
+        assert_eq!(index.get(&NetworkId(1)), &[entity].into());
+
+        world.despawn(entity);
+        assert!(world.resource::<ComponentIndex<NetworkId>>().is_empty());
+    }
+
+    #[test]
+    fn refresh_index_system() {
+        let mut world = World::new();
+        let existing = world.spawn(NetworkId(0)).id();
+        init_index::<NetworkId>(&mut world);
+        let refresh = world.register_system(refresh_index::<NetworkId>);
+
+        // components existing before the index are added by the first refresh
+        world.run_system(refresh).unwrap();
+        assert_eq!(
+            world
+                .resource::<ComponentIndex<NetworkId>>()
+                .get_single(&NetworkId(0)),
+            Some(existing)
+        );
+
+        world.get_mut::<NetworkId>(existing).unwrap().0 = 1;
+        world.run_system(refresh).unwrap();
+        let index = world.resource::<ComponentIndex<NetworkId>>();
+        assert!(index.get(&NetworkId(0)).is_empty());
+        assert_eq!(index.get(&NetworkId(1)), &[existing].into());
+    }
+}
@@ -12,9 +12,11 @@ pub mod change_detection;
 pub mod component;
 pub mod entity;
 pub mod event;
+pub mod index;
 pub mod query;
 #[cfg(feature = "bevy_reflect")]
 pub mod reflect;
+pub mod relation;
 pub mod removal_detection;
 pub mod schedule;
 pub mod storage;
@@ -40,9 +42,10 @@ pub mod prelude {
         query::{Added, AnyOf, Changed, Or, QueryState, With, Without},
         removal_detection::RemovedComponents,
         schedule::{
-            apply_deferred, apply_state_transition, common_conditions::*, Condition,
-            IntoSystemConfigs, IntoSystemSet, IntoSystemSetConfig, IntoSystemSetConfigs, NextState,
-            OnEnter, OnExit, OnTransition, Schedule, Schedules, State, States, SystemSet,
+            apply_deferred, common_conditions::*, ComputedStates, Condition, IntoSystemConfigs,
+            IntoSystemSet, IntoSystemSetConfig, IntoSystemSetConfigs, NextState, OnEnter, OnExit,
+            OnPause, OnResume, OnTransition, Schedule, Schedules, State, StateStack, States,
+            SubStates, SystemSet,
         },
         system::{
             adapter as system_adapter,
@@ -0,0 +1,700 @@
+use crate::{
+    archetype::{Archetype, ArchetypeComponentId},
+    change_detection::{MutUntyped, TicksMut},
+    component::{ComponentId, StorageType, Tick},
+    entity::Entity,
+    query::{Access, FilteredAccess, QueryState, ReadOnlyWorldQuery, WorldQuery},
+    storage::{Column, ComponentSparseSet, Table, TableRow},
+    world::{unsafe_world_cell::UnsafeWorldCell, World},
+};
+use bevy_ptr::Ptr;
+use std::fmt;
+
+/// How a [`DynamicTerm`] uses its component.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DynamicTermKind {
+    /// Reads the component, like `&T`.
+    Read,
+    /// Writes the component, like `&mut T`.
+    Write,
+    /// Reads the component if the entity has it, like `Option<&T>`.
+    OptionalRead,
+    /// Writes the component if the entity has it, like `Option<&mut T>`.
+    OptionalWrite,
+    /// Requires the component without accessing it, like `With<T>`.
+    With,
+    /// Requires the absence of the component, like `Without<T>`.
+    Without,
+}
+
+impl DynamicTermKind {
+    /// Returns `true` if this term yields an item for each matched entity.
+    #[inline]
+    pub fn is_data(self) -> bool {
+        !matches!(self, DynamicTermKind::With | DynamicTermKind::Without)
+    }
+
+    /// Returns `true` if this term accesses its component mutably.
+    #[inline]
+    pub fn is_write(self) -> bool {
+        matches!(
+            self,
+            DynamicTermKind::Write | DynamicTermKind::OptionalWrite
+        )
+    }
+}
+
+/// A single term of a [`DynamicQuery`], created by a [`QueryBuilder`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DynamicTerm {
+    component_id: ComponentId,
+    kind: DynamicTermKind,
+    storage_type: StorageType,
+}
+
+impl DynamicTerm {
+    /// Returns the id of the component this term refers to.
+    #[inline]
+    pub fn component_id(&self) -> ComponentId {
+        self.component_id
+    }
+
+    /// Returns how this term uses its component.
+    #[inline]
+    pub fn kind(&self) -> DynamicTermKind {
+        self.kind
+    }
+}
+
+/// An error that occurs when building a [`DynamicQuery`] with a [`QueryBuilder`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DynamicQueryError {
+    /// The [`ComponentId`] is not registered in the [`World`].
+    UnknownComponent(ComponentId),
+    /// The component is accessed mutably by one term and accessed again by another term.
+    ConflictingAccess(ComponentId),
+}
+
+impl std::error::Error for DynamicQueryError {}
+
+impl fmt::Display for DynamicQueryError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DynamicQueryError::UnknownComponent(id) => {
+                write!(f, "The component {id:?} is not registered in the world.")
+            }
+            DynamicQueryError::ConflictingAccess(id) => write!(
+                f,
+                "The component {id:?} is accessed mutably by one term and accessed again by another. Mutable component access must be unique."
+            ),
+        }
+    }
+}
+
+/// Builds a [`QueryState`] over components that are only known at runtime by their [`ComponentId`].
+///
+/// Each call adds a term to the query. Data terms ([`ref_id`](Self::ref_id), [`mut_id`](Self::mut_id)
+/// and their optional variants) yield one item per matched entity in the order they were added,
+/// while [`with_id`](Self::with_id) and [`without_id`](Self::without_id) only filter.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::query::QueryBuilder;
+/// #[derive(Component)]
+/// struct Position(f32);
+/// #[derive(Component)]
+/// struct Velocity(f32);
+///
+/// let mut world = World::new();
+/// world.spawn((Position(0.0), Velocity(1.0)));
+/// let position = world.init_component::<Position>();
+/// let velocity = world.init_component::<Velocity>();
+///
+/// let mut query = QueryBuilder::new(&world)
+///     .mut_id(position)
+///     .ref_id(velocity)
+///     .build()
+///     .unwrap();
+///
+/// for mut row in query.iter_mut(&mut world) {
+///     // SAFETY: the terms were built from the ids of these component types.
+///     let velocity = unsafe { row.get(1).unwrap().deref::<Velocity>().0 };
+///     let mut position = row.get_mut(0).unwrap();
+///     unsafe { position.as_mut().deref_mut::<Position>().0 += velocity };
+/// }
+/// ```
+pub struct QueryBuilder<'w> {
+    world: &'w World,
+    terms: Vec<DynamicTerm>,
+    access: FilteredAccess<ComponentId>,
+    error: Option<DynamicQueryError>,
+}
+
+impl<'w> QueryBuilder<'w> {
+    /// Creates a builder for a query without any terms.
+    pub fn new(world: &'w World) -> Self {
+        Self {
+            world,
+            terms: Vec::new(),
+            access: FilteredAccess::default(),
+            error: None,
+        }
+    }
+
+    /// Reads the component with the given id, like `&T`.
+    pub fn ref_id(&mut self, component_id: ComponentId) -> &mut Self {
+        self.term(component_id, DynamicTermKind::Read)
+    }
+
+    /// Writes the component with the given id, like `&mut T`.
+    pub fn mut_id(&mut self, component_id: ComponentId) -> &mut Self {
+        self.term(component_id, DynamicTermKind::Write)
+    }
+
+    /// Reads the component with the given id if the entity has it, like `Option<&T>`.
+    pub fn optional_ref_id(&mut self, component_id: ComponentId) -> &mut Self {
+        self.term(component_id, DynamicTermKind::OptionalRead)
+    }
+
+    /// Writes the component with the given id if the entity has it, like `Option<&mut T>`.
+    pub fn optional_mut_id(&mut self, component_id: ComponentId) -> &mut Self {
+        self.term(component_id, DynamicTermKind::OptionalWrite)
+    }
+
+    /// Only matches entities that have the component with the given id, like `With<T>`.
+    pub fn with_id(&mut self, component_id: ComponentId) -> &mut Self {
+        self.term(component_id, DynamicTermKind::With)
+    }
+
+    /// Only matches entities that don't have the component with the given id, like `Without<T>`.
+    pub fn without_id(&mut self, component_id: ComponentId) -> &mut Self {
+        self.term(component_id, DynamicTermKind::Without)
+    }
+
+    /// Adds a term of the given kind.
+    ///
+    /// Invalid terms are reported by [`build`](Self::build).
+    pub fn term(&mut self, component_id: ComponentId, kind: DynamicTermKind) -> &mut Self {
+        if self.error.is_some() {
+            return self;
+        }
+        let Some(info) = self.world.components().get_info(component_id) else {
+            self.error = Some(DynamicQueryError::UnknownComponent(component_id));
+            return self;
+        };
+        let conflicts = match kind {
+            DynamicTermKind::Read | DynamicTermKind::OptionalRead => {
+                self.access.access().has_write(component_id)
+            }
+            DynamicTermKind::Write | DynamicTermKind::OptionalWrite => {
+                self.access.access().has_read(component_id)
+            }
+            DynamicTermKind::With | DynamicTermKind::Without => false,
+        };
+        if conflicts {
+            self.error = Some(DynamicQueryError::ConflictingAccess(component_id));
+            return self;
+        }
+
+        let term = DynamicTerm {
+            component_id,
+            kind,
+            storage_type: info.storage_type(),
+        };
+        update_component_access(std::slice::from_ref(&term), &mut self.access, false);
+        self.terms.push(term);
+        self
+    }
+
+    /// Returns the terms added so far.
+    pub fn terms(&self) -> &[DynamicTerm] {
+        &self.terms
+    }
+
+    /// Returns the component access of the terms added so far.
+    pub fn access(&self) -> &FilteredAccess<ComponentId> {
+        &self.access
+    }
+
+    /// Creates a [`QueryState`] from the terms added so far.
+    ///
+    /// Returns an error if any of the terms refers to an unknown component, or if the terms
+    /// access a component mutably more than once.
+    pub fn build(&mut self) -> Result<QueryState<DynamicQuery>, DynamicQueryError> {
+        if let Some(error) = self.error {
+            return Err(error);
+        }
+        Ok(QueryState::new_with_state(
+            self.world,
+            self.terms.clone(),
+            (),
+        ))
+    }
+}
+
+/// A [`WorldQuery`] whose components are chosen at runtime, usually by a [`QueryBuilder`].
+///
+/// Its items are [`DynamicRow`]s, which hold one untyped pointer per data term.
+/// A [`QueryState`] created with [`QueryState::new`] has no terms, so it matches every entity
+/// without accessing any component.
+pub struct DynamicQuery;
+
+/// The read-only variant of [`DynamicQuery`], where every write term is treated as a read.
+pub struct ReadOnlyDynamicQuery;
+
+/// An item yielded by a data term of a [`DynamicQuery`].
+pub enum DynamicItem<'w> {
+    /// A shared pointer to a component, yielded by read terms.
+    Ref(Ptr<'w>),
+    /// A mutable pointer to a component, yielded by write terms.
+    Mut(MutUntyped<'w>),
+}
+
+impl<'w> DynamicItem<'w> {
+    /// Returns a shared pointer to the component.
+    #[inline]
+    pub fn as_ref(&self) -> Ptr<'_> {
+        match self {
+            DynamicItem::Ref(ptr) => *ptr,
+            DynamicItem::Mut(mut_untyped) => mut_untyped.as_ref(),
+        }
+    }
+}
+
+/// The item of a [`DynamicQuery`]: the matched entity and one item per data term.
+///
+/// Items are indexed in the order their terms were added to the [`QueryBuilder`],
+/// skipping [`With`](DynamicTermKind::With) and [`Without`](DynamicTermKind::Without) terms.
+pub struct DynamicRow<'w> {
+    entity: Entity,
+    items: Vec<Option<DynamicItem<'w>>>,
+}
+
+impl<'w> DynamicRow<'w> {
+    /// Returns the matched entity.
+    #[inline]
+    pub fn entity(&self) -> Entity {
+        self.entity
+    }
+
+    /// Returns the number of data terms.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Returns `true` if the query has no data terms.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Returns a shared pointer to the component of the data term at `index`.
+    ///
+    /// Returns `None` if `index` is out of bounds, or if the term is optional and the entity
+    /// doesn't have the component.
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<Ptr<'_>> {
+        self.items.get(index)?.as_ref().map(DynamicItem::as_ref)
+    }
+
+    /// Returns a mutable pointer to the component of the data term at `index`.
+    ///
+    /// Returns `None` if `index` is out of bounds, if the term is optional and the entity
+    /// doesn't have the component, or if the term only has read access.
+    #[inline]
+    pub fn get_mut(&mut self, index: usize) -> Option<MutUntyped<'_>> {
+        match self.items.get_mut(index)? {
+            Some(DynamicItem::Mut(mut_untyped)) => Some(mut_untyped.reborrow()),
+            _ => None,
+        }
+    }
+
+    /// Consumes the row, returning one item per data term.
+    #[inline]
+    pub fn into_items(self) -> Vec<Option<DynamicItem<'w>>> {
+        self.items
+    }
+}
+
+#[doc(hidden)]
+pub struct DynamicFetch<'w> {
+    terms: Vec<TermFetch<'w>>,
+    last_run: Tick,
+    this_run: Tick,
+}
+
+#[derive(Clone, Copy)]
+struct TermFetch<'w> {
+    component_id: ComponentId,
+    storage_type: StorageType,
+    write: bool,
+    // StorageType::Table
+    column: Option<&'w Column>,
+    // StorageType::SparseSet
+    sparse_set: Option<&'w ComponentSparseSet>,
+}
+
+impl<'w> DynamicFetch<'w> {
+    unsafe fn new(
+        world: UnsafeWorldCell<'w>,
+        terms: &[DynamicTerm],
+        last_run: Tick,
+        this_run: Tick,
+        read_only: bool,
+    ) -> Self {
+        let terms = terms
+            .iter()
+            .filter(|term| term.kind.is_data())
+            .map(|term| TermFetch {
+                component_id: term.component_id,
+                storage_type: term.storage_type,
+                write: !read_only && term.kind.is_write(),
+                column: None,
+                sparse_set: (term.storage_type == StorageType::SparseSet)
+                    .then(|| {
+                        world
+                            // SAFETY: See &T::init_fetch.
+                            .unsafe_world()
+                            .storages()
+                            .sparse_sets
+                            .get(term.component_id)
+                    })
+                    .flatten(),
+            })
+            .collect();
+        DynamicFetch {
+            terms,
+            last_run,
+            this_run,
+        }
+    }
+
+    fn clone_fetch(&self) -> Self {
+        DynamicFetch {
+            terms: self.terms.clone(),
+            last_run: self.last_run,
+            this_run: self.this_run,
+        }
+    }
+
+    fn set_table(&mut self, table: &'w Table) {
+        for term in &mut self.terms {
+            if term.storage_type == StorageType::Table {
+                term.column = table.get_column(term.component_id);
+            }
+        }
+    }
+
+    /// # Safety
+    ///
+    /// See [`WorldQuery::fetch`]. Additionally, the query must have write access to every write term,
+    /// and no other item for `entity` may be alive.
+    unsafe fn fetch(&mut self, entity: Entity, table_row: TableRow) -> DynamicRow<'w> {
+        let items = self
+            .terms
+            .iter()
+            .map(|term| {
+                let (ptr, ticks) = match term.storage_type {
+                    StorageType::Table => term.column?.get(table_row)?,
+                    StorageType::SparseSet => term.sparse_set?.get_with_ticks(entity)?,
+                };
+                Some(if term.write {
+                    DynamicItem::Mut(MutUntyped {
+                        value: ptr.assert_unique(),
+                        ticks: TicksMut::from_tick_cells(ticks, self.last_run, self.this_run),
+                    })
+                } else {
+                    DynamicItem::Ref(ptr)
+                })
+            })
+            .collect();
+        DynamicRow { entity, items }
+    }
+}
+
+fn update_component_access(
+    terms: &[DynamicTerm],
+    access: &mut FilteredAccess<ComponentId>,
+    read_only: bool,
+) {
+    for term in terms {
+        let id = term.component_id;
+        let write = !read_only && term.kind.is_write();
+        if term.kind.is_data() {
+            if write {
+                assert!(
+                    !access.access().has_read(id),
+                    "Write access to {id:?} conflicts with a previous access in this query. Mutable component access must be unique.",
+                );
+            } else {
+                assert!(
+                    !access.access().has_write(id),
+                    "Read access to {id:?} conflicts with a previous access in this query. Shared access cannot coincide with exclusive access.",
+                );
+            }
+        }
+        match term.kind {
+            DynamicTermKind::Read | DynamicTermKind::Write if write => access.add_write(id),
+            DynamicTermKind::Read | DynamicTermKind::Write => access.add_read(id),
+            // See the `WorldQuery` impl of `Option<T>` for why the filters of optional terms are dropped.
+            DynamicTermKind::OptionalRead | DynamicTermKind::OptionalWrite => {
+                let mut intermediate = access.clone();
+                if write {
+                    intermediate.add_write(id);
+                } else {
+                    intermediate.add_read(id);
+                }
+                access.extend_access(&intermediate);
+            }
+            DynamicTermKind::With => access.and_with(id),
+            DynamicTermKind::Without => access.and_without(id),
+        }
+    }
+}
+
+fn update_archetype_component_access(
+    terms: &[DynamicTerm],
+    archetype: &Archetype,
+    access: &mut Access<ArchetypeComponentId>,
+    read_only: bool,
+) {
+    for term in terms.iter().filter(|term| term.kind.is_data()) {
+        if let Some(archetype_component_id) =
+            archetype.get_archetype_component_id(term.component_id)
+        {
+            if !read_only && term.kind.is_write() {
+                access.add_write(archetype_component_id);
+            } else {
+                access.add_read(archetype_component_id);
+            }
+        }
+    }
+}
+
+fn matches_component_set(
+    terms: &[DynamicTerm],
+    set_contains_id: &impl Fn(ComponentId) -> bool,
+) -> bool {
+    terms.iter().all(|term| match term.kind {
+        DynamicTermKind::Read | DynamicTermKind::Write | DynamicTermKind::With => {
+            set_contains_id(term.component_id)
+        }
+        DynamicTermKind::Without => !set_contains_id(term.component_id),
+        DynamicTermKind::OptionalRead | DynamicTermKind::OptionalWrite => true,
+    })
+}
+
+macro_rules! impl_dynamic_world_query {
+    ($query:ty, $read_only_query:ty, $read_only:expr) => {
+        // SAFETY: access is only registered for data terms, and the fetch only creates mutable
+        // items for write terms when `$read_only` is false.
+        unsafe impl WorldQuery for $query {
+            type Fetch<'w> = DynamicFetch<'w>;
+            type Item<'w> = DynamicRow<'w>;
+            type ReadOnly = $read_only_query;
+            type State = Vec<DynamicTerm>;
+
+            fn shrink<'wlong: 'wshort, 'wshort>(item: Self::Item<'wlong>) -> Self::Item<'wshort> {
+                item
+            }
+
+            // The storage type of the terms is only known at runtime.
+            const IS_DENSE: bool = false;
+
+            const IS_ARCHETYPAL: bool = true;
+
+            #[inline]
+            unsafe fn init_fetch<'w>(
+                world: UnsafeWorldCell<'w>,
+                state: &Self::State,
+                last_run: Tick,
+                this_run: Tick,
+            ) -> Self::Fetch<'w> {
+                DynamicFetch::new(world, state, last_run, this_run, $read_only)
+            }
+
+            unsafe fn clone_fetch<'w>(fetch: &Self::Fetch<'w>) -> Self::Fetch<'w> {
+                fetch.clone_fetch()
+            }
+
+            #[inline]
+            unsafe fn set_archetype<'w>(
+                fetch: &mut Self::Fetch<'w>,
+                _state: &Self::State,
+                _archetype: &'w Archetype,
+                table: &'w Table,
+            ) {
+                fetch.set_table(table);
+            }
+
+            #[inline]
+            unsafe fn set_table<'w>(
+                fetch: &mut Self::Fetch<'w>,
+                _state: &Self::State,
+                table: &'w Table,
+            ) {
+                fetch.set_table(table);
+            }
+
+            #[inline]
+            unsafe fn fetch<'w>(
+                fetch: &mut Self::Fetch<'w>,
+                entity: Entity,
+                table_row: TableRow,
+            ) -> Self::Item<'w> {
+                fetch.fetch(entity, table_row)
+            }
+
+            fn update_component_access(
+                state: &Self::State,
+                access: &mut FilteredAccess<ComponentId>,
+            ) {
+                update_component_access(state, access, $read_only);
+            }
+
+            fn update_archetype_component_access(
+                state: &Self::State,
+                archetype: &Archetype,
+                access: &mut Access<ArchetypeComponentId>,
+            ) {
+                update_archetype_component_access(state, archetype, access, $read_only);
+            }
+
+            fn init_state(_world: &mut World) -> Self::State {
+                Vec::new()
+            }
+
+            fn matches_component_set(
+                state: &Self::State,
+                set_contains_id: &impl Fn(ComponentId) -> bool,
+            ) -> bool {
+                matches_component_set(state, set_contains_id)
+            }
+        }
+    };
+}
+
+impl_dynamic_world_query!(DynamicQuery, ReadOnlyDynamicQuery, false);
+impl_dynamic_world_query!(ReadOnlyDynamicQuery, ReadOnlyDynamicQuery, true);
+
+/// SAFETY: every term of [`ReadOnlyDynamicQuery`] is fetched with read access.
+unsafe impl ReadOnlyWorldQuery for ReadOnlyDynamicQuery {}
+
+#[cfg(test)]
+mod tests {
+    use super::{DynamicQueryError, QueryBuilder};
+    use crate::{
+        self as bevy_ecs,
+        component::{Component, ComponentId},
+        prelude::*,
+    };
+
+    #[derive(Component, Debug, PartialEq)]
+    struct A(usize);
+
+    #[derive(Component, Debug, PartialEq)]
+    #[component(storage = "SparseSet")]
+    struct B(usize);
+
+    #[derive(Component)]
+    struct C;
+
+    #[test]
+    fn dynamic_query() {
+        let mut world = World::new();
+        let both = world.spawn((A(1), B(2))).id();
+        let only_a = world.spawn(A(3)).id();
+        world.spawn((A(4), B(5), C));
+        world.spawn(B(6));
+        let a = world.init_component::<A>();
+        let b = world.init_component::<B>();
+        let c = world.init_component::<C>();
+
+        let mut query = QueryBuilder::new(&world)
+            .mut_id(a)
+            .optional_ref_id(b)
+            .without_id(c)
+            .build()
+            .unwrap();
+
+        for mut row in query.iter_mut(&mut world) {
+            assert_eq!(row.len(), 2);
+            // SAFETY: the ids belong to `A` and `B`.
+            let b = row.get(1).map(|b| unsafe { b.deref::<B>().0 });
+            let mut a = row.get_mut(0).unwrap();
+            // SAFETY: see above.
+            unsafe { a.as_mut().deref_mut::<A>().0 += b.unwrap_or(100) };
+        }
+
+        assert_eq!(world.get::<A>(both), Some(&A(3)));
+        assert_eq!(world.get::<A>(only_a), Some(&A(103)));
+        let mut all = world.query::<&A>();
+        let mut values: Vec<_> = all.iter(&world).map(|a| a.0).collect();
+        values.sort();
+        assert_eq!(values, vec![3, 4, 103]);
+    }
+
+    #[test]
+    fn dynamic_query_read_only_items() {
+        let mut world = World::new();
+        let entity = world.spawn(A(1)).id();
+        let a = world.init_component::<A>();
+
+        let mut query = QueryBuilder::new(&world).mut_id(a).build().unwrap();
+        let mut row = query.get(&world, entity).unwrap();
+        assert_eq!(row.entity(), entity);
+        assert!(row.get(0).is_some());
+        assert!(row.get_mut(0).is_none());
+    }
+
+    #[test]
+    fn dynamic_query_change_detection() {
+        let mut world = World::new();
+        let entity = world.spawn(A(1)).id();
+        let a = world.init_component::<A>();
+        world.clear_trackers();
+
+        let mut changed = world.query_filtered::<Entity, Changed<A>>();
+        assert_eq!(changed.iter(&world).count(), 0);
+
+        let mut query = QueryBuilder::new(&world).mut_id(a).build().unwrap();
+        let mut row = query.get_mut(&mut world, entity).unwrap();
+        row.get_mut(0).unwrap().set_changed();
+
+        assert_eq!(changed.iter(&world).collect::<Vec<_>>(), vec![entity]);
+    }
+
+    #[test]
+    fn dynamic_query_errors() {
+        let mut world = World::new();
+        let a = world.init_component::<A>();
+        let b = world.init_component::<B>();
+
+        assert_eq!(
+            QueryBuilder::new(&world).ref_id(a).mut_id(a).build().err(),
+            Some(DynamicQueryError::ConflictingAccess(a))
+        );
+        assert_eq!(
+            QueryBuilder::new(&world)
+                .mut_id(b)
+                .optional_ref_id(b)
+                .build()
+                .err(),
+            Some(DynamicQueryError::ConflictingAccess(b))
+        );
+
+        let unknown = ComponentId::new(1000);
+        assert_eq!(
+            QueryBuilder::new(&world).ref_id(unknown).build().err(),
+            Some(DynamicQueryError::UnknownComponent(unknown))
+        );
+
+        assert!(QueryBuilder::new(&world)
+            .ref_id(a)
+            .with_id(a)
+            .without_id(b)
+            .build()
+            .is_ok());
+    }
+}
@@ -1,3 +1,5 @@
+#[cfg(feature = "track_change_detection")]
+use crate::change_detection::ChangedBy;
 use crate::{
     archetype::{Archetype, ArchetypeComponentId},
     change_detection::{Ticks, TicksMut},
@@ -771,6 +773,9 @@ pub struct RefFetch<'w, T> {
         ThinSlicePtr<'w, UnsafeCell<Tick>>,
         ThinSlicePtr<'w, UnsafeCell<Tick>>,
     )>,
+    // T::Storage = TableStorage
+    #[cfg(feature = "track_change_detection")]
+    table_changed_by: Option<ThinSlicePtr<'w, UnsafeCell<Option<ChangedBy>>>>,
     // T::Storage = SparseStorage
     sparse_set: Option<&'w ComponentSparseSet>,
 
@@ -807,6 +812,8 @@ unsafe impl<'__w, T: Component> WorldQuery for Ref<'__w, T> {
     ) -> RefFetch<'w, T> {
         RefFetch {
             table_data: None,
+            #[cfg(feature = "track_change_detection")]
+            table_changed_by: None,
             sparse_set: (T::Storage::STORAGE_TYPE == StorageType::SparseSet).then(|| {
                 world
                     // SAFETY: See &T::init_fetch.
@@ -824,6 +831,8 @@ unsafe impl<'__w, T: Component> WorldQuery for Ref<'__w, T> {
     unsafe fn clone_fetch<'w>(fetch: &Self::Fetch<'w>) -> Self::Fetch<'w> {
         RefFetch {
             table_data: fetch.table_data,
+            #[cfg(feature = "track_change_detection")]
+            table_changed_by: fetch.table_changed_by,
             sparse_set: fetch.sparse_set,
             last_run: fetch.last_run,
             this_run: fetch.this_run,
@@ -854,6 +863,10 @@ unsafe impl<'__w, T: Component> WorldQuery for Ref<'__w, T> {
             column.get_added_ticks_slice().into(),
             column.get_changed_ticks_slice().into(),
         ));
+        #[cfg(feature = "track_change_detection")]
+        {
+            fetch.table_changed_by = Some(column.get_changed_by_slice().into());
+        }
     }
 
     #[inline(always)]
@@ -871,6 +884,14 @@ unsafe impl<'__w, T: Component> WorldQuery for Ref<'__w, T> {
                     ticks: Ticks {
                         added: added_ticks.get(table_row.index()).deref(),
                         changed: changed_ticks.get(table_row.index()).deref(),
+                        #[cfg(feature = "track_change_detection")]
+                        changed_by: Some(
+                            fetch
+                                .table_changed_by
+                                .debug_checked_unwrap()
+                                .get(table_row.index())
+                                .deref(),
+                        ),
                         this_run: fetch.this_run,
                         last_run: fetch.last_run,
                     },
@@ -935,6 +956,9 @@ pub struct WriteFetch<'w, T> {
         ThinSlicePtr<'w, UnsafeCell<Tick>>,
         ThinSlicePtr<'w, UnsafeCell<Tick>>,
     )>,
+    // T::Storage = TableStorage
+    #[cfg(feature = "track_change_detection")]
+    table_changed_by: Option<ThinSlicePtr<'w, UnsafeCell<Option<ChangedBy>>>>,
     // T::Storage = SparseStorage
     sparse_set: Option<&'w ComponentSparseSet>,
 
@@ -971,6 +995,8 @@ unsafe impl<'__w, T: Component> WorldQuery for &'__w mut T {
     ) -> WriteFetch<'w, T> {
         WriteFetch {
             table_data: None,
+            #[cfg(feature = "track_change_detection")]
+            table_changed_by: None,
             sparse_set: (T::Storage::STORAGE_TYPE == StorageType::SparseSet).then(|| {
                 world
                     // SAFETY: See &T::init_fetch.
@@ -988,6 +1014,8 @@ unsafe impl<'__w, T: Component> WorldQuery for &'__w mut T {
     unsafe fn clone_fetch<'w>(fetch: &Self::Fetch<'w>) -> Self::Fetch<'w> {
         WriteFetch {
             table_data: fetch.table_data,
+            #[cfg(feature = "track_change_detection")]
+            table_changed_by: fetch.table_changed_by,
             sparse_set: fetch.sparse_set,
             last_run: fetch.last_run,
             this_run: fetch.this_run,
@@ -1018,6 +1046,10 @@ unsafe impl<'__w, T: Component> WorldQuery for &'__w mut T {
             column.get_added_ticks_slice().into(),
             column.get_changed_ticks_slice().into(),
         ));
+        #[cfg(feature = "track_change_detection")]
+        {
+            fetch.table_changed_by = Some(column.get_changed_by_slice().into());
+        }
     }
 
     #[inline(always)]
@@ -1035,6 +1067,14 @@ unsafe impl<'__w, T: Component> WorldQuery for &'__w mut T {
                     ticks: TicksMut {
                         added: added_ticks.get(table_row.index()).deref_mut(),
                         changed: changed_ticks.get(table_row.index()).deref_mut(),
+                        #[cfg(feature = "track_change_detection")]
+                        changed_by: Some(
+                            fetch
+                                .table_changed_by
+                                .debug_checked_unwrap()
+                                .get(table_row.index())
+                                .deref_mut(),
+                        ),
                         this_run: fetch.this_run,
                         last_run: fetch.last_run,
                     },
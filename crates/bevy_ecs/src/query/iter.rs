@@ -207,6 +207,161 @@ where
 {
 }
 
+/// An [`Iterator`] over the items of two queries for the entities of an iterator of [`Entity`]s
+/// matched by both queries.
+///
+/// Items are returned in the order of the provided iterator.
+/// Entities that don't match both queries are skipped.
+///
+/// This struct is created by the [`Query::iter_many_join`](crate::system::Query::iter_many_join)
+/// method.
+pub struct QueryManyJoinIter<
+    'w,
+    's,
+    Q1: ReadOnlyWorldQuery,
+    F1: ReadOnlyWorldQuery,
+    Q2: ReadOnlyWorldQuery,
+    F2: ReadOnlyWorldQuery,
+    I: Iterator,
+> where
+    I::Item: Borrow<Entity>,
+{
+    entity_iter: I,
+    entities: &'w Entities,
+    tables: &'w Tables,
+    archetypes: &'w Archetypes,
+    fetch: (Q1::Fetch<'w>, Q2::Fetch<'w>),
+    filter: (F1::Fetch<'w>, F2::Fetch<'w>),
+    query_states: (&'s QueryState<Q1, F1>, &'s QueryState<Q2, F2>),
+}
+
+impl<
+        'w,
+        's,
+        Q1: ReadOnlyWorldQuery,
+        F1: ReadOnlyWorldQuery,
+        Q2: ReadOnlyWorldQuery,
+        F2: ReadOnlyWorldQuery,
+        I: Iterator,
+    > QueryManyJoinIter<'w, 's, Q1, F1, Q2, F2, I>
+where
+    I::Item: Borrow<Entity>,
+{
+    /// # Safety
+    /// - `world` must have permission to access any of the components registered in both query states.
+    /// - `world` must be the same one used to initialize both query states.
+    pub(crate) unsafe fn new<EntityList: IntoIterator<IntoIter = I>>(
+        world: UnsafeWorldCell<'w>,
+        query_states: (&'s QueryState<Q1, F1>, &'s QueryState<Q2, F2>),
+        entity_list: EntityList,
+        last_run: Tick,
+        this_run: Tick,
+    ) -> Self {
+        let (state1, state2) = query_states;
+        QueryManyJoinIter {
+            query_states,
+            entities: world.entities(),
+            archetypes: world.archetypes(),
+            // SAFETY: We only access table data that has been registered in the query states.
+            // This means `world` has permission to access the data we use.
+            tables: &world.unsafe_world().storages.tables,
+            fetch: (
+                Q1::init_fetch(world, &state1.fetch_state, last_run, this_run),
+                Q2::init_fetch(world, &state2.fetch_state, last_run, this_run),
+            ),
+            filter: (
+                F1::init_fetch(world, &state1.filter_state, last_run, this_run),
+                F2::init_fetch(world, &state2.filter_state, last_run, this_run),
+            ),
+            entity_iter: entity_list.into_iter(),
+        }
+    }
+}
+
+impl<
+        'w,
+        's,
+        Q1: ReadOnlyWorldQuery,
+        F1: ReadOnlyWorldQuery,
+        Q2: ReadOnlyWorldQuery,
+        F2: ReadOnlyWorldQuery,
+        I: Iterator,
+    > Iterator for QueryManyJoinIter<'w, 's, Q1, F1, Q2, F2, I>
+where
+    I::Item: Borrow<Entity>,
+{
+    type Item = (Q1::Item<'w>, Q2::Item<'w>);
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (state1, state2) = self.query_states;
+        for entity in self.entity_iter.by_ref() {
+            let entity = *entity.borrow();
+            // The location is looked up once for both queries.
+            let Some(location) = self.entities.get(entity) else {
+                continue;
+            };
+            let archetype_index = location.archetype_id.index();
+            if !state1.matched_archetypes.contains(archetype_index)
+                || !state2.matched_archetypes.contains(archetype_index)
+            {
+                continue;
+            }
+
+            // SAFETY: the entity exists, so its archetype and table exist too.
+            let (archetype, table) = unsafe {
+                (
+                    self.archetypes
+                        .get(location.archetype_id)
+                        .debug_checked_unwrap(),
+                    self.tables.get(location.table_id).debug_checked_unwrap(),
+                )
+            };
+
+            // SAFETY: `archetype` and `table` are from the world that the fetches were created
+            // for, and the states are the ones the fetches were initialized with. The archetype is
+            // matched by both queries, and `location.table_row` is in range of `table`.
+            // Both queries are read-only, so their items can alias.
+            unsafe {
+                Q1::set_archetype(&mut self.fetch.0, &state1.fetch_state, archetype, table);
+                F1::set_archetype(&mut self.filter.0, &state1.filter_state, archetype, table);
+                Q2::set_archetype(&mut self.fetch.1, &state2.fetch_state, archetype, table);
+                F2::set_archetype(&mut self.filter.1, &state2.filter_state, archetype, table);
+
+                if F1::filter_fetch(&mut self.filter.0, entity, location.table_row)
+                    && F2::filter_fetch(&mut self.filter.1, entity, location.table_row)
+                {
+                    return Some((
+                        Q1::fetch(&mut self.fetch.0, entity, location.table_row),
+                        Q2::fetch(&mut self.fetch.1, entity, location.table_row),
+                    ));
+                }
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (_, max_size) = self.entity_iter.size_hint();
+        (0, max_size)
+    }
+}
+
+// This is correct as [`QueryManyJoinIter`] always returns `None` once exhausted.
+impl<
+        'w,
+        's,
+        Q1: ReadOnlyWorldQuery,
+        F1: ReadOnlyWorldQuery,
+        Q2: ReadOnlyWorldQuery,
+        F2: ReadOnlyWorldQuery,
+        I: Iterator,
+    > FusedIterator for QueryManyJoinIter<'w, 's, Q1, F1, Q2, F2, I>
+where
+    I::Item: Borrow<Entity>,
+{
+}
+
 /// An iterator over `K`-sized combinations of query items without repetition.
 ///
 /// A combination is an arrangement of a collection of items where order does not matter.
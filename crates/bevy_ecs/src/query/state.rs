@@ -14,7 +14,7 @@ use bevy_tasks::ComputeTaskPool;
 #[cfg(feature = "trace")]
 use bevy_utils::tracing::Instrument;
 use fixedbitset::FixedBitSet;
-use std::{borrow::Borrow, fmt, mem::MaybeUninit};
+use std::{borrow::Borrow, fmt, mem::MaybeUninit, sync::Mutex};
 
 use super::{NopWorldQuery, QueryManyIter, ROQueryItem, ReadOnlyWorldQuery};
 
@@ -36,6 +36,41 @@ pub struct QueryState<Q: WorldQuery, F: ReadOnlyWorldQuery = ()> {
     pub(crate) matched_archetype_ids: Vec<ArchetypeId>,
     pub(crate) fetch_state: Q::State,
     pub(crate) filter_state: F::State,
+    // The states joined with this one by `Query::join`, by type of joined state.
+    join_cache: JoinCache,
+}
+
+/// The archetypes matched by the states joined with a [`QueryState`] by `Query::join`, along with
+/// the access of the query they were joined with.
+#[derive(Default)]
+pub(crate) struct JoinCache(Mutex<Vec<(FilteredAccess<ComponentId>, JoinedArchetypes)>>);
+
+/// The archetypes matched by a joined [`QueryState`], which don't depend on its type.
+pub(crate) struct JoinedArchetypes {
+    world_id: WorldId,
+    archetype_generation: ArchetypeGeneration,
+    matched_tables: FixedBitSet,
+    matched_archetypes: FixedBitSet,
+    archetype_component_access: Access<ArchetypeComponentId>,
+    matched_table_ids: Vec<TableId>,
+    matched_archetype_ids: Vec<ArchetypeId>,
+}
+
+impl JoinCache {
+    fn take(&self, other_access: &FilteredAccess<ComponentId>) -> Option<JoinedArchetypes> {
+        let mut cache = self.0.lock().unwrap();
+        let index = cache
+            .iter()
+            .position(|(access, _)| access == other_access)?;
+        Some(cache.swap_remove(index).1)
+    }
+
+    /// Caches the archetypes matched by a state joined with a query with the access `other_access`.
+    pub(crate) fn put(&self, other_access: &FilteredAccess<ComponentId>, joined: JoinedArchetypes) {
+        let mut cache = self.0.lock().unwrap();
+        cache.retain(|(access, _)| access != other_access);
+        cache.push((other_access.clone(), joined));
+    }
 }
 
 impl<Q: WorldQuery, F: ReadOnlyWorldQuery> std::fmt::Debug for QueryState<Q, F> {
@@ -98,7 +133,15 @@ impl<Q: WorldQuery, F: ReadOnlyWorldQuery> QueryState<Q, F> {
     pub fn new(world: &mut World) -> Self {
         let fetch_state = Q::init_state(world);
         let filter_state = F::init_state(world);
+        Self::new_with_state(world, fetch_state, filter_state)
+    }
 
+    /// Creates a new [`QueryState`] from already initialized fetch and filter states.
+    pub(crate) fn new_with_state(
+        world: &World,
+        fetch_state: Q::State,
+        filter_state: F::State,
+    ) -> Self {
         let mut component_access = FilteredAccess::default();
         Q::update_component_access(&fetch_state, &mut component_access);
 
@@ -123,11 +166,226 @@ impl<Q: WorldQuery, F: ReadOnlyWorldQuery> QueryState<Q, F> {
             matched_tables: Default::default(),
             matched_archetypes: Default::default(),
             archetype_component_access: Default::default(),
+            join_cache: Default::default(),
         };
         state.update_archetypes(world);
         state
     }
 
+    /// Joins this query with `other`, creating a [`QueryState`] that yields the items of both queries
+    /// for the entities they have in common, in a single pass.
+    ///
+    /// The joined state only visits the archetypes matched by both queries, so this is much cheaper
+    /// than iterating one query and calling [`get`](Self::get) on the other for each entity.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #[derive(Component, Debug, PartialEq)]
+    /// struct Position(f32);
+    /// #[derive(Component, Debug, PartialEq)]
+    /// struct Health(u32);
+    /// #[derive(Component)]
+    /// struct Enemy;
+    ///
+    /// let mut world = World::new();
+    /// world.spawn((Position(1.0), Health(10), Enemy));
+    /// world.spawn((Position(2.0), Enemy));
+    /// world.spawn((Position(3.0), Health(30)));
+    ///
+    /// let enemies = world.query_filtered::<&Position, With<Enemy>>();
+    /// let health = world.query::<&Health>();
+    /// let mut joined = enemies.join(&world, &health);
+    ///
+    /// let items: Vec<_> = joined.iter(&world).collect();
+    /// assert_eq!(items, vec![(&Position(1.0), &Health(10))]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the two queries were created from different worlds, or if their component accesses
+    /// conflict (for example if both of them access the same component mutably).
+    pub fn join<Q2: WorldQuery, F2: ReadOnlyWorldQuery>(
+        &self,
+        world: &World,
+        other: &QueryState<Q2, F2>,
+    ) -> QueryState<(Q, Q2), (F, F2)>
+    where
+        Q::State: Clone,
+        F::State: Clone,
+        Q2::State: Clone,
+        F2::State: Clone,
+    {
+        self.join_unsafe_world_cell(world.as_unsafe_world_cell_readonly(), other)
+    }
+
+    /// Equivalent to [`QueryState::join`], using an [`UnsafeWorldCell`] to update archetypes.
+    ///
+    /// # Note
+    ///
+    /// This method only accesses world metadata.
+    pub fn join_unsafe_world_cell<Q2: WorldQuery, F2: ReadOnlyWorldQuery>(
+        &self,
+        world: UnsafeWorldCell,
+        other: &QueryState<Q2, F2>,
+    ) -> QueryState<(Q, Q2), (F, F2)>
+    where
+        Q::State: Clone,
+        F::State: Clone,
+        Q2::State: Clone,
+        F2::State: Clone,
+    {
+        self.validate_world(world.id());
+        other.validate_world(world.id());
+
+        // Both items are fetched for the same entity, so filters can't make these accesses disjoint.
+        let conflicts = self
+            .component_access
+            .access()
+            .get_conflicts(other.component_access.access());
+        if !conflicts.is_empty() {
+            let components = world.components();
+            let names: Vec<_> = conflicts
+                .iter()
+                .map(|id| components.get_name(*id).unwrap_or("<unknown>"))
+                .collect();
+            panic!(
+                "Cannot join {} with {}: both access {names:?} and at least one of them does so mutably.",
+                std::any::type_name::<Self>(),
+                std::any::type_name::<QueryState<Q2, F2>>(),
+            );
+        }
+
+        let mut component_access = self.component_access.clone();
+        component_access.extend(&other.component_access);
+
+        // Archetypes that are newer than this generation haven't been seen by both queries.
+        // If archetypes were reclaimed since either query was updated, their ids may have changed.
+        let generation = world.archetypes().generation();
+        let archetype_generation = if generation.is_reclaimed_since(self.archetype_generation)
+            || generation.is_reclaimed_since(other.archetype_generation)
+        {
+            ArchetypeGeneration::initial()
+        } else {
+            self.archetype_generation.min(other.archetype_generation)
+        };
+
+        let mut joined = QueryState {
+            world_id: self.world_id,
+            archetype_generation,
+            matched_tables: Default::default(),
+            matched_archetypes: Default::default(),
+            archetype_component_access: Default::default(),
+            component_access,
+            matched_table_ids: Vec::new(),
+            matched_archetype_ids: Vec::new(),
+            fetch_state: (self.fetch_state.clone(), other.fetch_state.clone()),
+            filter_state: (self.filter_state.clone(), other.filter_state.clone()),
+            join_cache: Default::default(),
+        };
+
+        let archetypes = world.archetypes();
+        for archetype_id in &self.matched_archetype_ids {
+            if archetype_id.index() < joined.archetype_generation.value()
+                && other.matched_archetypes.contains(archetype_id.index())
+            {
+                joined.new_archetype(&archetypes[*archetype_id]);
+            }
+        }
+        joined.update_archetypes_unsafe_world_cell(world);
+        joined
+    }
+
+    /// Equivalent to [`QueryState::join_unsafe_world_cell`], reusing the archetypes matched by the
+    /// last state joining this query with a query with the same access as `other`, stored back with
+    /// [`QueryState::take_joined_archetypes`].
+    ///
+    /// # Note
+    ///
+    /// This method only accesses world metadata.
+    pub(crate) fn join_cached<Q2: WorldQuery, F2: ReadOnlyWorldQuery>(
+        &self,
+        world: UnsafeWorldCell,
+        other: &QueryState<Q2, F2>,
+    ) -> QueryState<(Q, Q2), (F, F2)>
+    where
+        Q::State: Clone,
+        F::State: Clone,
+        Q2::State: Clone,
+        F2::State: Clone,
+    {
+        let Some(cached) = self.join_cache.take(&other.component_access) else {
+            return self.join_unsafe_world_cell(world, other);
+        };
+        self.validate_world(world.id());
+        other.validate_world(world.id());
+
+        // The cached archetypes can only be reused if they are still the archetypes matched by
+        // both queries: queries of different types can have the same access.
+        let generation = world.archetypes().generation();
+        let reusable = cached.world_id == world.id()
+            && !generation.is_reclaimed_since(cached.archetype_generation)
+            && !generation.is_reclaimed_since(self.archetype_generation)
+            && !generation.is_reclaimed_since(other.archetype_generation)
+            && self.archetype_generation >= cached.archetype_generation
+            && other.archetype_generation >= cached.archetype_generation
+            && self
+                .matched_archetype_ids
+                .iter()
+                .filter(|id| {
+                    id.index() < cached.archetype_generation.value()
+                        && other.matched_archetypes.contains(id.index())
+                })
+                .count()
+                == cached.matched_archetype_ids.len()
+            && cached.matched_archetype_ids.iter().all(|id| {
+                self.matched_archetypes.contains(id.index())
+                    && other.matched_archetypes.contains(id.index())
+            });
+        if !reusable {
+            return self.join_unsafe_world_cell(world, other);
+        }
+
+        let mut joined = QueryState {
+            world_id: cached.world_id,
+            archetype_generation: cached.archetype_generation,
+            matched_tables: cached.matched_tables,
+            matched_archetypes: cached.matched_archetypes,
+            archetype_component_access: cached.archetype_component_access,
+            component_access: {
+                let mut component_access = self.component_access.clone();
+                component_access.extend(&other.component_access);
+                component_access
+            },
+            matched_table_ids: cached.matched_table_ids,
+            matched_archetype_ids: cached.matched_archetype_ids,
+            fetch_state: (self.fetch_state.clone(), other.fetch_state.clone()),
+            filter_state: (self.filter_state.clone(), other.filter_state.clone()),
+            join_cache: Default::default(),
+        };
+        // only the archetypes created since the state was cached need to be matched
+        joined.update_archetypes_unsafe_world_cell(world);
+        joined
+    }
+
+    /// Takes the archetypes matched by this state, to cache them in the [`JoinCache`] of the state
+    /// it was joined from.
+    pub(crate) fn take_joined_archetypes(&mut self) -> JoinedArchetypes {
+        JoinedArchetypes {
+            world_id: self.world_id,
+            archetype_generation: self.archetype_generation,
+            matched_tables: std::mem::take(&mut self.matched_tables),
+            matched_archetypes: std::mem::take(&mut self.matched_archetypes),
+            archetype_component_access: std::mem::take(&mut self.archetype_component_access),
+            matched_table_ids: std::mem::take(&mut self.matched_table_ids),
+            matched_archetype_ids: std::mem::take(&mut self.matched_archetype_ids),
+        }
+    }
+
+    /// Returns the cache of the archetypes matched by the states joined with this one.
+    pub(crate) fn join_cache(&self) -> &JoinCache {
+        &self.join_cache
+    }
+
     /// Checks if the query is empty for the given [`World`], where the last change and current tick are given.
     #[inline]
     pub fn is_empty(&self, world: &World, last_run: Tick, this_run: Tick) -> bool {
@@ -176,13 +434,42 @@ impl<Q: WorldQuery, F: ReadOnlyWorldQuery> QueryState<Q, F> {
         let archetypes = world.archetypes();
         let new_generation = archetypes.generation();
         let old_generation = std::mem::replace(&mut self.archetype_generation, new_generation);
-        let archetype_index_range = old_generation.value()..new_generation.value();
+        if new_generation.is_reclaimed_since(old_generation) {
+            self.forget_archetypes();
+        }
 
-        for archetype_index in archetype_index_range {
+        for archetype_index in new_generation.archetypes_since(old_generation) {
             self.new_archetype(&archetypes[ArchetypeId::new(archetype_index)]);
         }
     }
 
+    /// Forgets the matched archetypes and tables, after their ids were changed by
+    /// [`World::reclaim_empty_archetypes`].
+    ///
+    /// The archetype component access is kept, as the ids of archetype components don't change.
+    pub(crate) fn forget_archetypes(&mut self) {
+        self.matched_tables.clear();
+        self.matched_archetypes.clear();
+        self.matched_table_ids.clear();
+        self.matched_archetype_ids.clear();
+    }
+
+    /// Matches the archetypes again from scratch if empty archetypes were reclaimed since they were
+    /// last matched by [`QueryState::update_archetypes`].
+    ///
+    /// This keeps stale archetype ids from being used when the query is fed archetypes through a
+    /// [`SystemParam`](crate::system::SystemParam) which doesn't forward
+    /// [`forget_archetypes`](crate::system::SystemParam::forget_archetypes).
+    pub(crate) fn rematch_reclaimed_archetypes(&mut self, world: UnsafeWorldCell) {
+        if world
+            .archetypes()
+            .generation()
+            .is_reclaimed_since(self.archetype_generation)
+        {
+            self.update_archetypes_unsafe_world_cell(world);
+        }
+    }
+
     /// # Panics
     ///
     /// If `world_id` does not match the [`World`] used to call `QueryState::new` for this instance.
@@ -198,6 +485,25 @@ impl<Q: WorldQuery, F: ReadOnlyWorldQuery> QueryState<Q, F> {
         );
     }
 
+    /// # Panics
+    ///
+    /// If empty archetypes were reclaimed by [`World::reclaim_empty_archetypes`] since the archetypes
+    /// were last updated, as the ids of the matched archetypes and tables no longer point to them.
+    ///
+    /// The `manual` methods don't update the archetypes, so they call this function to avoid reading
+    /// the wrong tables.
+    #[inline]
+    pub(crate) fn validate_archetypes(&self, world: UnsafeWorldCell) {
+        assert!(
+            !world
+                .archetypes()
+                .generation()
+                .is_reclaimed_since(self.archetype_generation),
+            "Attempted to use {} after empty archetypes were reclaimed. Call `QueryState::update_archetypes` first.",
+            std::any::type_name::<Self>(),
+        );
+    }
+
     /// Update the current [`QueryState`] with information from the provided [`Archetype`]
     /// (if applicable, i.e. if the archetype has any intersecting [`ComponentId`] with the current [`QueryState`]).
     pub fn new_archetype(&mut self, archetype: &Archetype) {
@@ -393,6 +699,11 @@ impl<Q: WorldQuery, F: ReadOnlyWorldQuery> QueryState<Q, F> {
     /// access to `self`.
     ///
     /// This can only be called for read-only queries, see [`Self::get_mut`] for mutable queries.
+    ///
+    /// # Panics
+    ///
+    /// If empty archetypes were reclaimed by [`World::reclaim_empty_archetypes`] since the
+    /// archetypes were last updated.
     #[inline]
     pub fn get_manual<'w>(
         &self,
@@ -444,6 +755,7 @@ impl<Q: WorldQuery, F: ReadOnlyWorldQuery> QueryState<Q, F> {
         last_run: Tick,
         this_run: Tick,
     ) -> Result<Q::Item<'w>, QueryEntityError> {
+        self.validate_archetypes(world);
         let location = world
             .entities()
             .get(entity)
@@ -581,6 +893,11 @@ impl<Q: WorldQuery, F: ReadOnlyWorldQuery> QueryState<Q, F> {
     /// Archetypes must be manually updated before by using [`Self::update_archetypes`].
     ///
     /// This can only be called for read-only queries.
+    ///
+    /// # Panics
+    ///
+    /// If empty archetypes were reclaimed by [`World::reclaim_empty_archetypes`] since the
+    /// archetypes were last updated.
     #[inline]
     pub fn iter_manual<'w, 's>(
         &'s self,
@@ -708,6 +1025,11 @@ impl<Q: WorldQuery, F: ReadOnlyWorldQuery> QueryState<Q, F> {
     ///
     /// This can only be called for read-only queries.
     ///
+    /// # Panics
+    ///
+    /// If empty archetypes were reclaimed by [`World::reclaim_empty_archetypes`] since the
+    /// archetypes were last updated.
+    ///
     /// # See also
     ///
     /// - [`iter_many`](Self::iter_many) to update archetypes.
@@ -812,6 +1134,7 @@ impl<Q: WorldQuery, F: ReadOnlyWorldQuery> QueryState<Q, F> {
         last_run: Tick,
         this_run: Tick,
     ) -> QueryIter<'w, 's, Q, F> {
+        self.validate_archetypes(world);
         QueryIter::new(world, self, last_run, this_run)
     }
 
@@ -836,6 +1159,7 @@ impl<Q: WorldQuery, F: ReadOnlyWorldQuery> QueryState<Q, F> {
     where
         EntityList::Item: Borrow<Entity>,
     {
+        self.validate_archetypes(world);
         QueryManyIter::new(world, self, entities, last_run, this_run)
     }
 
@@ -856,6 +1180,7 @@ impl<Q: WorldQuery, F: ReadOnlyWorldQuery> QueryState<Q, F> {
         last_run: Tick,
         this_run: Tick,
     ) -> QueryCombinationIter<'w, 's, Q, F, K> {
+        self.validate_archetypes(world);
         QueryCombinationIter::new(world, self, last_run, this_run)
     }
 
@@ -968,6 +1293,7 @@ impl<Q: WorldQuery, F: ReadOnlyWorldQuery> QueryState<Q, F> {
         last_run: Tick,
         this_run: Tick,
     ) {
+        self.validate_archetypes(world);
         // NOTE: If you are changing query iteration code, remember to update the following places, where relevant:
         // QueryIter, QueryIterationCursor, QueryManyIter, QueryCombinationIter, QueryState::for_each_unchecked_manual, QueryState::par_for_each_unchecked_manual
         let mut fetch = Q::init_fetch(world, &self.fetch_state, last_run, this_run);
@@ -1043,6 +1369,7 @@ impl<Q: WorldQuery, F: ReadOnlyWorldQuery> QueryState<Q, F> {
         last_run: Tick,
         this_run: Tick,
     ) {
+        self.validate_archetypes(world);
         // NOTE: If you are changing query iteration code, remember to update the following places, where relevant:
         // QueryIter, QueryIterationCursor, QueryManyIter, QueryCombinationIter, QueryState::for_each_unchecked_manual, QueryState::par_for_each_unchecked_manual
         ComputeTaskPool::get().scope(|scope| {
@@ -1313,6 +1640,7 @@ impl fmt::Display for QueryEntityError {
 
 #[cfg(test)]
 mod tests {
+    use crate as bevy_ecs;
     use crate::{prelude::*, query::QueryEntityError};
 
     #[test]
@@ -1418,6 +1746,91 @@ mod tests {
         let mut query_state = world_1.query::<Entity>();
         let _panics = query_state.get_many_mut(&mut world_2, []);
     }
+
+    #[derive(Component, Debug, PartialEq)]
+    struct A(usize);
+
+    #[derive(Component, Debug, PartialEq)]
+    struct B(usize);
+
+    #[test]
+    fn join() {
+        let mut world = World::new();
+        world.spawn(A(0));
+        let both = world.spawn((A(1), B(1))).id();
+        world.spawn(B(2));
+
+        let query_a = world.query::<&A>();
+        let query_b = world.query::<(Entity, &mut B)>();
+        let mut joined = query_a.join(&world, &query_b);
+
+        for (a, (_, mut b)) in joined.iter_mut(&mut world) {
+            b.0 += a.0 * 10;
+        }
+        assert_eq!(world.get::<B>(both), Some(&B(11)));
+
+        // Archetypes created after the join are picked up as usual.
+        let late = world.spawn((A(3), B(3))).id();
+        let mut entities: Vec<_> = joined.iter(&world).map(|(_, (e, _))| e).collect();
+        entities.sort();
+        assert_eq!(entities, vec![both, late]);
+    }
+
+    #[derive(Component)]
+    struct C;
+
+    #[test]
+    fn join_with_filters() {
+        let mut world = World::new();
+        world.spawn((A(0), B(0)));
+        let both = world.spawn((A(1), B(1), C)).id();
+
+        let query_a = world.query_filtered::<Entity, With<C>>();
+        let query_b = world.query::<&B>();
+        let mut joined = query_a.join(&world, &query_b);
+
+        let items: Vec<_> = joined.iter(&world).collect();
+        assert_eq!(items, vec![(both, &B(1))]);
+    }
+
+    #[test]
+    fn join_par_iter() {
+        let mut world = World::new();
+        for i in 0..100 {
+            world.spawn((A(i), B(0)));
+        }
+        world.spawn(B(0));
+
+        let query_a = world.query::<&A>();
+        let query_b = world.query::<&mut B>();
+        let mut joined = query_a.join(&world, &query_b);
+        bevy_tasks::ComputeTaskPool::init(bevy_tasks::TaskPool::default);
+        joined
+            .par_iter_mut(&mut world)
+            .for_each_mut(|(a, mut b)| b.0 = a.0);
+
+        let mut query = world.query::<(&A, &B)>();
+        assert!(query.iter(&world).all(|(a, b)| a.0 == b.0));
+    }
+
+    #[test]
+    #[should_panic]
+    fn join_conflicting_access() {
+        let mut world = World::new();
+        let query_a = world.query::<&mut A>();
+        let query_b = world.query::<&A>();
+        let _panics = query_a.join(&world, &query_b);
+    }
+
+    #[test]
+    #[should_panic]
+    fn join_wrong_world() {
+        let mut world_1 = World::new();
+        let world_2 = World::new();
+        let query_a = world_1.query::<&A>();
+        let query_b = world_1.query::<&B>();
+        let _panics = query_a.join(&world_2, &query_b);
+    }
 }
 
 /// An error that occurs when evaluating a [`Query`](crate::system::Query) or [`QueryState`] as a single expected result via
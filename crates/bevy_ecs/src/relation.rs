@@ -0,0 +1,768 @@
+//! Typed, directed edges between entities.
+//!
+//! A relation connects a *source* entity to one or more *target* entities. The kind of the
+//! relation is described by a type implementing [`RelationKind`], so that a single entity can
+//! take part in many independent relation graphs at once (ownership, targeting, inventories…).
+//!
+//! Relations are stored as two ordinary components:
+//!
+//! - [`Relation<R>`] lives on the source and lists the targets of the source.
+//! - [`RelatedBy<R>`] lives on the target and lists the sources pointing at it, enabling
+//!   reverse lookups.
+//!
+//! Relations are created with [`World::relate`], [`World::unrelate`] and the matching
+//! [`EntityCommands`](crate::system::EntityCommands) methods. Component hooks keep both sides in
+//! sync whichever way a side is inserted or removed: when either endpoint of a relation is
+//! despawned, or one of its components is removed, the relation is removed from the other endpoint.
+//!
+//! Relations can be used in queries: [`Targets<R>`] and [`Sources<R>`] fetch the related entities,
+//! [`WithTargets<R>`] and [`WithSources<R>`] filter on the entities that have relations of a given
+//! kind, and [`Query::iter_many`](crate::system::Query::iter_many) joins a query on the related
+//! entities.
+//!
+//! ```
+//! # use bevy_ecs::prelude::*;
+//! use bevy_ecs::relation::{RelationKind, Targets};
+//!
+//! struct Targeting;
+//! impl RelationKind for Targeting {}
+//!
+//! #[derive(Component)]
+//! struct Health(u32);
+//!
+//! fn damage_targets(
+//!     attackers: Query<Targets<Targeting>>,
+//!     mut targets: Query<&mut Health>,
+//! ) {
+//!     for targeting in &attackers {
+//!         let mut iter = targets.iter_many_mut(targeting);
+//!         while let Some(mut health) = iter.fetch_next() {
+//!             health.0 = health.0.saturating_sub(1);
+//!         }
+//!     }
+//! }
+//! # bevy_ecs::system::assert_is_system(damage_targets);
+//! ```
+
+use crate::{
+    archetype::{Archetype, ArchetypeComponentId},
+    component::{Component, ComponentHooks, ComponentId, TableStorage, Tick},
+    entity::{Entity, EntityMapper, MapEntities},
+    query::{Access, FilteredAccess, ReadOnlyWorldQuery, With, WorldQuery},
+    storage::{Table, TableRow},
+    world::{unsafe_world_cell::UnsafeWorldCell, DeferredWorld, World},
+};
+use std::{fmt, marker::PhantomData, slice};
+
+/// A kind of [`Relation`] between two entities.
+///
+/// Types implementing this trait are only used as markers and are never instantiated.
+///
+/// ```
+/// use bevy_ecs::relation::RelationKind;
+///
+/// /// The source entity is held in the inventory of the target entity.
+/// struct InInventory;
+///
+/// impl RelationKind for InInventory {}
+/// ```
+pub trait RelationKind: Send + Sync + 'static {}
+
+/// Lists the targets of a relation of kind `R` that the entity holding this component is the source of.
+///
+/// This component can only be created and modified through [`World::relate`], [`World::unrelate`]
+/// and their [`Commands`](crate::system::Commands) counterparts. Its hooks keep the matching
+/// [`RelatedBy<R>`] components on the targets up to date when it is inserted or removed.
+pub struct Relation<R: RelationKind> {
+    targets: Vec<Entity>,
+    marker: PhantomData<R>,
+}
+
+impl<R: RelationKind> Relation<R> {
+    /// Returns the targets of this relation, in the order they were related.
+    #[inline]
+    pub fn targets(&self) -> &[Entity] {
+        &self.targets
+    }
+
+    /// Returns an iterator over the targets of this relation.
+    #[inline]
+    pub fn iter(&self) -> slice::Iter<'_, Entity> {
+        self.targets.iter()
+    }
+
+    /// Returns `true` if `target` is one of the targets of this relation.
+    #[inline]
+    pub fn contains(&self, target: Entity) -> bool {
+        self.targets.contains(&target)
+    }
+
+    /// Returns the number of targets of this relation.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.targets.len()
+    }
+
+    /// Returns `true` if this relation has no targets.
+    ///
+    /// Empty relations are removed from their entity, so this will usually return `false`.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.targets.is_empty()
+    }
+}
+
+impl<R: RelationKind> Component for Relation<R> {
+    type Storage = TableStorage;
+
+    fn register_component_hooks(hooks: &mut ComponentHooks) {
+        hooks
+            .on_insert(on_insert_relation::<R>)
+            .on_remove(on_remove_relation::<R>);
+    }
+}
+
+impl<R: RelationKind> fmt::Debug for Relation<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Relation").field(&self.targets).finish()
+    }
+}
+
+impl<'a, R: RelationKind> IntoIterator for &'a Relation<R> {
+    type Item = &'a Entity;
+    type IntoIter = slice::Iter<'a, Entity>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<R: RelationKind> MapEntities for Relation<R> {
+    fn map_entities(&mut self, entity_mapper: &mut EntityMapper) {
+        for entity in &mut self.targets {
+            *entity = entity_mapper.get_or_reserve(*entity);
+        }
+    }
+}
+
+/// Lists the sources of the relations of kind `R` that target the entity holding this component.
+///
+/// This is the reverse side of [`Relation<R>`], and is maintained automatically. Removing it
+/// removes the relations targeting the entity.
+pub struct RelatedBy<R: RelationKind> {
+    sources: Vec<Entity>,
+    marker: PhantomData<R>,
+}
+
+impl<R: RelationKind> RelatedBy<R> {
+    /// Returns the sources of the relations targeting this entity, in the order they were related.
+    #[inline]
+    pub fn sources(&self) -> &[Entity] {
+        &self.sources
+    }
+
+    /// Returns an iterator over the sources of the relations targeting this entity.
+    #[inline]
+    pub fn iter(&self) -> slice::Iter<'_, Entity> {
+        self.sources.iter()
+    }
+
+    /// Returns `true` if `source` has a relation targeting this entity.
+    #[inline]
+    pub fn contains(&self, source: Entity) -> bool {
+        self.sources.contains(&source)
+    }
+
+    /// Returns the number of sources of the relations targeting this entity.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.sources.len()
+    }
+
+    /// Returns `true` if no relation targets this entity.
+    ///
+    /// Empty reverse relations are removed from their entity, so this will usually return `false`.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.sources.is_empty()
+    }
+}
+
+impl<R: RelationKind> Component for RelatedBy<R> {
+    type Storage = TableStorage;
+
+    fn register_component_hooks(hooks: &mut ComponentHooks) {
+        hooks
+            .on_insert(on_insert_related_by::<R>)
+            .on_remove(on_remove_related_by::<R>);
+    }
+}
+
+impl<R: RelationKind> fmt::Debug for RelatedBy<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("RelatedBy").field(&self.sources).finish()
+    }
+}
+
+impl<'a, R: RelationKind> IntoIterator for &'a RelatedBy<R> {
+    type Item = &'a Entity;
+    type IntoIter = slice::Iter<'a, Entity>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<R: RelationKind> MapEntities for RelatedBy<R> {
+    fn map_entities(&mut self, entity_mapper: &mut EntityMapper) {
+        for entity in &mut self.sources {
+            *entity = entity_mapper.get_or_reserve(*entity);
+        }
+    }
+}
+
+/// A [`WorldQuery`] term fetching the targets of the [`Relation<R>`] of an entity.
+///
+/// Entities without a relation of kind `R` are matched too, with no targets. Combined with
+/// [`Query::iter_many`](crate::system::Query::iter_many), this joins a query on the targets of a
+/// relation, and [`WithTargets<R>`] filters on the entities that are the source of one.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// use bevy_ecs::relation::{RelationKind, Targets, WithTargets};
+///
+/// struct Targeting;
+/// impl RelationKind for Targeting {}
+///
+/// #[derive(Component)]
+/// struct Name(&'static str);
+///
+/// fn print_targets(attackers: Query<(&Name, Targets<Targeting>), WithTargets<Targeting>>, names: Query<&Name>) {
+///     for (attacker, targets) in &attackers {
+///         for target in names.iter_many(targets) {
+///             println!("{} targets {}", attacker.0, target.0);
+///         }
+///     }
+/// }
+/// # bevy_ecs::system::assert_is_system(print_targets);
+/// ```
+pub struct Targets<R: RelationKind>(PhantomData<R>);
+
+/// A [`WorldQuery`] term fetching the sources of the relations of kind `R` targeting an entity,
+/// listed by its [`RelatedBy<R>`].
+///
+/// Entities that are not targeted are matched too, with no sources. [`WithSources<R>`] filters on
+/// the entities that are targeted.
+pub struct Sources<R: RelationKind>(PhantomData<R>);
+
+/// A [`WorldQuery`] filter matching the entities that are the source of a relation of kind `R`.
+pub type WithTargets<R> = With<Relation<R>>;
+
+/// A [`WorldQuery`] filter matching the entities that are the target of a relation of kind `R`.
+pub type WithSources<R> = With<RelatedBy<R>>;
+
+macro_rules! impl_relation_query {
+    ($query:ident, $component:ident, $field:ident) => {
+        // SAFETY: every method delegates to `Option<&$component<R>>`, which only reads the
+        // component, and the fetched item borrows from it
+        unsafe impl<R: RelationKind> WorldQuery for $query<R> {
+            type Item<'w> = &'w [Entity];
+            type Fetch<'w> = <Option<&'static $component<R>> as WorldQuery>::Fetch<'w>;
+            type ReadOnly = Self;
+            type State = ComponentId;
+
+            fn shrink<'wlong: 'wshort, 'wshort>(item: &'wlong [Entity]) -> &'wshort [Entity] {
+                item
+            }
+
+            const IS_DENSE: bool = <Option<&'static $component<R>> as WorldQuery>::IS_DENSE;
+
+            const IS_ARCHETYPAL: bool =
+                <Option<&'static $component<R>> as WorldQuery>::IS_ARCHETYPAL;
+
+            #[inline]
+            unsafe fn init_fetch<'w>(
+                world: UnsafeWorldCell<'w>,
+                state: &ComponentId,
+                last_run: Tick,
+                this_run: Tick,
+            ) -> Self::Fetch<'w> {
+                <Option<&'static $component<R>> as WorldQuery>::init_fetch(
+                    world, state, last_run, this_run,
+                )
+            }
+
+            unsafe fn clone_fetch<'w>(fetch: &Self::Fetch<'w>) -> Self::Fetch<'w> {
+                <Option<&'static $component<R>> as WorldQuery>::clone_fetch(fetch)
+            }
+
+            #[inline]
+            unsafe fn set_archetype<'w>(
+                fetch: &mut Self::Fetch<'w>,
+                state: &ComponentId,
+                archetype: &'w Archetype,
+                table: &'w Table,
+            ) {
+                <Option<&'static $component<R>> as WorldQuery>::set_archetype(
+                    fetch, state, archetype, table,
+                );
+            }
+
+            #[inline]
+            unsafe fn set_table<'w>(
+                fetch: &mut Self::Fetch<'w>,
+                state: &ComponentId,
+                table: &'w Table,
+            ) {
+                <Option<&'static $component<R>> as WorldQuery>::set_table(fetch, state, table);
+            }
+
+            #[inline(always)]
+            unsafe fn fetch<'w>(
+                fetch: &mut Self::Fetch<'w>,
+                entity: Entity,
+                table_row: TableRow,
+            ) -> &'w [Entity] {
+                <Option<&'static $component<R>> as WorldQuery>::fetch(fetch, entity, table_row)
+                    .map_or(&[], |component| component.$field.as_slice())
+            }
+
+            fn update_component_access(
+                state: &ComponentId,
+                access: &mut FilteredAccess<ComponentId>,
+            ) {
+                <Option<&'static $component<R>> as WorldQuery>::update_component_access(
+                    state, access,
+                );
+            }
+
+            fn update_archetype_component_access(
+                state: &ComponentId,
+                archetype: &Archetype,
+                access: &mut Access<ArchetypeComponentId>,
+            ) {
+                <Option<&'static $component<R>> as WorldQuery>::update_archetype_component_access(
+                    state, archetype, access,
+                );
+            }
+
+            fn init_state(world: &mut World) -> ComponentId {
+                <Option<&'static $component<R>> as WorldQuery>::init_state(world)
+            }
+
+            fn matches_component_set(
+                state: &ComponentId,
+                set_contains_id: &impl Fn(ComponentId) -> bool,
+            ) -> bool {
+                <Option<&'static $component<R>> as WorldQuery>::matches_component_set(
+                    state,
+                    set_contains_id,
+                )
+            }
+        }
+
+        // SAFETY: the query only reads the component
+        unsafe impl<R: RelationKind> ReadOnlyWorldQuery for $query<R> {}
+    };
+}
+
+impl_relation_query!(Targets, Relation, targets);
+impl_relation_query!(Sources, RelatedBy, sources);
+
+// Both sides of a relation are kept in sync by hooks, whichever way the components are inserted
+// or removed: through `World::relate`, directly on the entity, or when an endpoint is despawned.
+// Hooks can't make structural changes, so inserting or removing the other side is deferred to
+// commands, which check that the change is still needed when they are applied.
+
+fn on_insert_relation<R: RelationKind>(mut world: DeferredWorld, source: Entity, _: ComponentId) {
+    let targets = world.get::<Relation<R>>(source).unwrap().targets.clone();
+    for target in targets {
+        match world.get::<RelatedBy<R>>(target) {
+            Some(related_by) if related_by.sources.contains(&source) => {}
+            Some(_) => world
+                .get_mut::<RelatedBy<R>>(target)
+                .unwrap()
+                .sources
+                .push(source),
+            None => world
+                .commands()
+                .add(move |world: &mut World| add_source::<R>(world, target, source)),
+        }
+    }
+}
+
+fn on_remove_relation<R: RelationKind>(mut world: DeferredWorld, source: Entity, _: ComponentId) {
+    let targets = world.get::<Relation<R>>(source).unwrap().targets.clone();
+    for target in targets {
+        let Some(mut related_by) = world.get_mut::<RelatedBy<R>>(target) else {
+            continue;
+        };
+        related_by.sources.retain(|entity| *entity != source);
+        if related_by.sources.is_empty() {
+            world
+                .commands()
+                .add(move |world: &mut World| remove_if_empty::<RelatedBy<R>>(world, target));
+        }
+    }
+}
+
+fn on_insert_related_by<R: RelationKind>(mut world: DeferredWorld, target: Entity, _: ComponentId) {
+    let sources = world.get::<RelatedBy<R>>(target).unwrap().sources.clone();
+    for source in sources {
+        match world.get::<Relation<R>>(source) {
+            Some(relation) if relation.targets.contains(&target) => {}
+            Some(_) => world
+                .get_mut::<Relation<R>>(source)
+                .unwrap()
+                .targets
+                .push(target),
+            None => world
+                .commands()
+                .add(move |world: &mut World| add_target::<R>(world, source, target)),
+        }
+    }
+}
+
+fn on_remove_related_by<R: RelationKind>(mut world: DeferredWorld, target: Entity, _: ComponentId) {
+    let sources = world.get::<RelatedBy<R>>(target).unwrap().sources.clone();
+    for source in sources {
+        let Some(mut relation) = world.get_mut::<Relation<R>>(source) else {
+            continue;
+        };
+        relation.targets.retain(|entity| *entity != target);
+        if relation.targets.is_empty() {
+            world
+                .commands()
+                .add(move |world: &mut World| remove_if_empty::<Relation<R>>(world, source));
+        }
+    }
+}
+
+/// The sides of a relation, which are removed from their entity once they are empty.
+trait RelationSide: Component {
+    fn is_empty(&self) -> bool;
+}
+
+impl<R: RelationKind> RelationSide for Relation<R> {
+    fn is_empty(&self) -> bool {
+        self.targets.is_empty()
+    }
+}
+
+impl<R: RelationKind> RelationSide for RelatedBy<R> {
+    fn is_empty(&self) -> bool {
+        self.sources.is_empty()
+    }
+}
+
+fn remove_if_empty<C: RelationSide>(world: &mut World, entity: Entity) {
+    let Some(mut entity) = world.get_entity_mut(entity) else {
+        return;
+    };
+    if entity.get::<C>().is_some_and(C::is_empty) {
+        entity.remove::<C>();
+    }
+}
+
+/// Adds `source` to the [`RelatedBy<R>`] of `target`, if `source` is still related to `target`.
+fn add_source<R: RelationKind>(world: &mut World, target: Entity, source: Entity) {
+    let related = world
+        .get::<Relation<R>>(source)
+        .is_some_and(|relation| relation.targets.contains(&target));
+    if !related {
+        return;
+    }
+    let Some(mut target_mut) = world.get_entity_mut(target) else {
+        // the target was despawned, so the relation is gone
+        remove_target::<R>(world, source, target);
+        return;
+    };
+    if let Some(mut related_by) = target_mut.get_mut::<RelatedBy<R>>() {
+        if !related_by.sources.contains(&source) {
+            related_by.sources.push(source);
+        }
+    } else {
+        target_mut.insert(RelatedBy::<R> {
+            sources: vec![source],
+            marker: PhantomData,
+        });
+    }
+}
+
+/// Adds `target` to the [`Relation<R>`] of `source`, if `target` is still related by `source`.
+fn add_target<R: RelationKind>(world: &mut World, source: Entity, target: Entity) {
+    let related = world
+        .get::<RelatedBy<R>>(target)
+        .is_some_and(|related_by| related_by.sources.contains(&source));
+    if !related {
+        return;
+    }
+    let Some(mut source_mut) = world.get_entity_mut(source) else {
+        // the source was despawned, so the relation is gone
+        remove_source::<R>(world, target, source);
+        return;
+    };
+    if let Some(mut relation) = source_mut.get_mut::<Relation<R>>() {
+        if !relation.targets.contains(&target) {
+            relation.targets.push(target);
+        }
+    } else {
+        source_mut.insert(Relation::<R> {
+            targets: vec![target],
+            marker: PhantomData,
+        });
+    }
+}
+
+/// Removes `source` from the [`RelatedBy<R>`] of `target`, removing the component once it is empty.
+fn remove_source<R: RelationKind>(world: &mut World, target: Entity, source: Entity) {
+    let Some(mut target) = world.get_entity_mut(target) else {
+        return;
+    };
+    let Some(mut related_by) = target.get_mut::<RelatedBy<R>>() else {
+        return;
+    };
+    related_by.sources.retain(|entity| *entity != source);
+    if related_by.sources.is_empty() {
+        target.remove::<RelatedBy<R>>();
+    }
+}
+
+/// Removes `target` from the [`Relation<R>`] of `source`, removing the component once it is empty.
+///
+/// Returns `true` if `target` was a target of `source`.
+fn remove_target<R: RelationKind>(world: &mut World, source: Entity, target: Entity) -> bool {
+    let Some(mut source) = world.get_entity_mut(source) else {
+        return false;
+    };
+    let Some(mut relation) = source.get_mut::<Relation<R>>() else {
+        return false;
+    };
+    let Some(index) = relation.targets.iter().position(|entity| *entity == target) else {
+        return false;
+    };
+    relation.targets.remove(index);
+    if relation.targets.is_empty() {
+        source.remove::<Relation<R>>();
+    }
+    true
+}
+
+pub(crate) fn relate<R: RelationKind>(world: &mut World, source: Entity, target: Entity) -> bool {
+    assert!(
+        world.entities.contains(target),
+        "Could not relate entity {source:?} to {target:?} because {target:?} does not exist."
+    );
+    let Some(mut source_mut) = world.get_entity_mut(source) else {
+        panic!(
+            "Could not relate entity {source:?} to {target:?} because {source:?} does not exist."
+        );
+    };
+    if let Some(mut relation) = source_mut.get_mut::<Relation<R>>() {
+        if relation.targets.contains(&target) {
+            return false;
+        }
+        relation.targets.push(target);
+        add_source::<R>(world, target, source);
+    } else {
+        // the insertion hook relates the target back
+        source_mut.insert(Relation::<R> {
+            targets: vec![target],
+            marker: PhantomData,
+        });
+    }
+    true
+}
+
+pub(crate) fn unrelate<R: RelationKind>(world: &mut World, source: Entity, target: Entity) -> bool {
+    if remove_target::<R>(world, source, target) {
+        remove_source::<R>(world, target, source);
+        true
+    } else {
+        false
+    }
+}
+
+pub(crate) fn unrelate_all<R: RelationKind>(world: &mut World, source: Entity) {
+    // the removal hook unrelates the targets
+    if let Some(mut source) = world.get_entity_mut(source) {
+        source.remove::<Relation<R>>();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{RelatedBy, Relation, RelationKind, Sources, Targets, WithSources, WithTargets};
+    use crate::{
+        self as bevy_ecs,
+        component::Component,
+        system::{CommandQueue, Commands},
+        world::World,
+    };
+
+    struct Owns;
+    impl RelationKind for Owns {}
+
+    struct Targeting;
+    impl RelationKind for Targeting {}
+
+    #[derive(Component, PartialEq, Debug)]
+    struct Name(&'static str);
+
+    #[test]
+    fn relate_and_reverse_lookup() {
+        let mut world = World::new();
+        let a = world.spawn_empty().id();
+        let b = world.spawn_empty().id();
+        let c = world.spawn_empty().id();
+
+        assert!(world.relate::<Owns>(a, b));
+        assert!(world.relate::<Owns>(a, c));
+        assert!(world.relate::<Owns>(b, c));
+        assert!(!world.relate::<Owns>(a, b));
+        world.relate::<Targeting>(c, a);
+
+        assert_eq!(world.get::<Relation<Owns>>(a).unwrap().targets(), &[b, c]);
+        assert_eq!(world.get::<RelatedBy<Owns>>(c).unwrap().sources(), &[a, b]);
+        assert!(world.get::<RelatedBy<Owns>>(a).is_none());
+        assert_eq!(world.get::<Relation<Targeting>>(c).unwrap().targets(), &[a]);
+        assert_eq!(
+            world.get::<RelatedBy<Targeting>>(a).unwrap().sources(),
+            &[c]
+        );
+    }
+
+    #[test]
+    fn unrelate_removes_both_sides() {
+        let mut world = World::new();
+        let a = world.spawn_empty().id();
+        let b = world.spawn_empty().id();
+        let c = world.spawn_empty().id();
+        world.relate::<Owns>(a, b);
+        world.relate::<Owns>(a, c);
+
+        assert!(world.unrelate::<Owns>(a, b));
+        assert!(!world.unrelate::<Owns>(a, b));
+        assert_eq!(world.get::<Relation<Owns>>(a).unwrap().targets(), &[c]);
+        assert!(world.get::<RelatedBy<Owns>>(b).is_none());
+
+        world.unrelate_all::<Owns>(a);
+        assert!(world.get::<Relation<Owns>>(a).is_none());
+        assert!(world.get::<RelatedBy<Owns>>(c).is_none());
+    }
+
+    #[test]
+    fn despawn_cleans_up_relations() {
+        let mut world = World::new();
+        let a = world.spawn_empty().id();
+        let b = world.spawn_empty().id();
+        let c = world.spawn_empty().id();
+        world.relate::<Owns>(a, b);
+        world.relate::<Owns>(a, c);
+        world.relate::<Owns>(c, a);
+        world.relate::<Targeting>(b, b);
+
+        world.despawn(c);
+        assert_eq!(world.get::<Relation<Owns>>(a).unwrap().targets(), &[b]);
+        assert!(world.get::<RelatedBy<Owns>>(a).is_none());
+
+        world.despawn(b);
+        assert!(world.get::<Relation<Owns>>(a).is_none());
+        assert_eq!(world.entities().len(), 1);
+    }
+
+    #[test]
+    fn query_relation_targets() {
+        let mut world = World::new();
+        let a = world.spawn(Name("a")).id();
+        let b = world.spawn(Name("b")).id();
+        let c = world.spawn(Name("c")).id();
+        world.relate::<Targeting>(a, b);
+        world.relate::<Targeting>(a, c);
+
+        let mut sources = world.query_filtered::<&Name, WithTargets<Targeting>>();
+        assert_eq!(sources.iter(&world).collect::<Vec<_>>(), vec![&Name("a")]);
+        let mut targeted = world.query_filtered::<&Name, WithSources<Targeting>>();
+        assert_eq!(targeted.iter(&world).count(), 2);
+
+        let mut names = world.query::<&Name>();
+        let relation = world.get::<Relation<Targeting>>(a).unwrap();
+        let targets = names.iter_many(&world, relation).collect::<Vec<_>>();
+        assert_eq!(targets, vec![&Name("b"), &Name("c")]);
+    }
+
+    #[test]
+    fn query_terms() {
+        let mut world = World::new();
+        let a = world.spawn(Name("a")).id();
+        let b = world.spawn(Name("b")).id();
+        let c = world.spawn(Name("c")).id();
+        world.relate::<Targeting>(a, b);
+        world.relate::<Targeting>(a, c);
+        world.relate::<Targeting>(b, c);
+
+        let mut query = world.query::<(&Name, Targets<Targeting>, Sources<Targeting>)>();
+        let mut items = query
+            .iter(&world)
+            .map(|(name, targets, sources)| (name.0, targets.to_vec(), sources.to_vec()))
+            .collect::<Vec<_>>();
+        items.sort_by_key(|(name, ..)| *name);
+        assert_eq!(
+            items,
+            vec![
+                ("a", vec![b, c], vec![]),
+                ("b", vec![c], vec![a]),
+                ("c", vec![], vec![a, b]),
+            ]
+        );
+    }
+
+    #[test]
+    fn direct_changes_keep_sides_in_sync() {
+        let mut world = World::new();
+        let a = world.spawn_empty().id();
+        let b = world.spawn_empty().id();
+        let c = world.spawn_empty().id();
+        world.relate::<Owns>(a, b);
+        world.relate::<Owns>(a, c);
+        world.relate::<Owns>(b, c);
+
+        // removing the reverse side removes the relations targeting the entity
+        world.entity_mut(c).remove::<RelatedBy<Owns>>();
+        assert_eq!(world.get::<Relation<Owns>>(a).unwrap().targets(), &[b]);
+        assert!(world.get::<Relation<Owns>>(b).is_none());
+
+        // moving a relation to another entity moves its reverse side too
+        let relation = world.entity_mut(a).take::<Relation<Owns>>().unwrap();
+        assert!(world.get::<RelatedBy<Owns>>(b).is_none());
+        world.entity_mut(c).insert(relation);
+        assert_eq!(world.get::<RelatedBy<Owns>>(b).unwrap().sources(), &[c]);
+
+        // inserting a relation targeting a despawned entity drops the target
+        world.relate::<Owns>(a, c);
+        let relation = world.entity_mut(a).take::<Relation<Owns>>().unwrap();
+        world.despawn(c);
+        world.entity_mut(b).insert(relation);
+        assert!(world.get::<Relation<Owns>>(b).is_none());
+        assert_eq!(world.entities().len(), 2);
+    }
+
+    #[test]
+    fn relate_with_commands() {
+        let mut world = World::new();
+        let a = world.spawn_empty().id();
+        let b = world.spawn_empty().id();
+
+        let mut queue = CommandQueue::default();
+        let mut commands = Commands::new(&mut queue, &world);
+        commands.entity(a).relate::<Owns>(b);
+        queue.apply(&mut world);
+        assert_eq!(world.get::<Relation<Owns>>(a).unwrap().targets(), &[b]);
+
+        let mut commands = Commands::new(&mut queue, &world);
+        commands.entity(a).unrelate::<Owns>(b);
+        queue.apply(&mut world);
+        assert!(world.get::<Relation<Owns>>(a).is_none());
+        assert!(world.get::<RelatedBy<Owns>>(b).is_none());
+    }
+}
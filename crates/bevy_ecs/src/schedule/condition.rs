@@ -189,7 +189,7 @@ pub mod common_conditions {
         event::{Event, EventReader},
         prelude::{Component, Query, With},
         removal_detection::RemovedComponents,
-        schedule::{State, States},
+        schedule::{State, StateStack, States},
         system::{IntoSystem, Res, Resource, System},
     };
 
@@ -802,6 +802,56 @@ pub mod common_conditions {
         }
     }
 
+    /// Generates a [`Condition`](super::Condition)-satisfying closure that returns `true`
+    /// if the state machine is currently in `state`, or if `state` is covered by the current
+    /// state in its [`StateStack`].
+    ///
+    /// Will return `false` if the state does not exist.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # #[derive(Resource, Default)]
+    /// # struct Counter(u8);
+    /// # let mut app = Schedule::new();
+    /// # let mut world = World::new();
+    /// # world.init_resource::<Counter>();
+    /// #[derive(Clone, Copy, Default, Eq, PartialEq, Hash, Debug, States)]
+    /// enum GameState {
+    ///     #[default]
+    ///     Playing,
+    ///     Paused,
+    /// }
+    ///
+    /// world.init_resource::<State<GameState>>();
+    /// world.init_resource::<StateStack<GameState>>();
+    ///
+    /// app.add_systems(
+    ///     // `in_state_stack` keeps returning true while `Playing` is covered by `Paused`
+    ///     my_system.run_if(in_state_stack(GameState::Playing)),
+    /// );
+    ///
+    /// fn my_system(mut counter: ResMut<Counter>) {
+    ///     counter.0 += 1;
+    /// }
+    ///
+    /// app.run(&mut world);
+    /// assert_eq!(world.resource::<Counter>().0, 1);
+    /// ```
+    pub fn in_state_stack<S: States>(
+        state: S,
+    ) -> impl FnMut(Option<Res<State<S>>>, Option<Res<StateStack<S>>>) -> bool + Clone {
+        move |current_state: Option<Res<State<S>>>, stack: Option<Res<StateStack<S>>>| {
+            match current_state {
+                Some(current_state) => {
+                    *current_state == state || stack.is_some_and(|stack| stack.is_covered(&state))
+                }
+                None => false,
+            }
+        }
+    }
+
     /// Generates a [`Condition`](super::Condition)-satisfying closure that returns `true`
     /// if the state machine changed state.
     ///
@@ -1262,6 +1312,7 @@ mod tests {
                 .distributive_run_if(resource_removed::<State<TestState>>())
                 .distributive_run_if(state_exists::<TestState>())
                 .distributive_run_if(in_state(TestState::A))
+                .distributive_run_if(in_state_stack(TestState::A))
                 .distributive_run_if(state_changed::<TestState>())
                 .distributive_run_if(on_event::<TestEvent>())
                 .distributive_run_if(any_with_component::<TestComponent>())
@@ -7,7 +7,7 @@ use crate::{
         set::{BoxedSystemSet, IntoSystemSet, SystemSet},
         ScheduleLabel,
     },
-    system::{BoxedSystem, IntoSystem, System},
+    system::{BoxedSystem, FallibleSystem, IntoSystem, System, SystemError},
 };
 
 fn new_condition<M>(condition: impl Condition<M>) -> BoxedCondition {
@@ -33,7 +33,15 @@ fn ambiguous_with(graph_info: &mut GraphInfo, set: BoxedSystemSet) {
     }
 }
 
-impl<Marker, F> IntoSystemConfigs<Marker> for F
+/// A marker type used to distinguish systems returning `()` from systems returning a `Result`.
+#[doc(hidden)]
+pub struct InfallibleSystemMarker;
+
+/// A marker type used to distinguish systems returning a `Result` from systems returning `()`.
+#[doc(hidden)]
+pub struct FallibleSystemMarker;
+
+impl<Marker, F> IntoSystemConfigs<(InfallibleSystemMarker, Marker)> for F
 where
     F: IntoSystem<(), (), Marker>,
 {
@@ -42,6 +50,16 @@ where
     }
 }
 
+impl<Marker, F, E> IntoSystemConfigs<(FallibleSystemMarker, E, Marker)> for F
+where
+    F: IntoSystem<(), Result<(), E>, Marker>,
+    E: Into<SystemError> + 'static,
+{
+    fn into_configs(self) -> SystemConfigs {
+        SystemConfigs::new_system(Box::new(FallibleSystem::new(IntoSystem::into_system(self))))
+    }
+}
+
 impl IntoSystemConfigs<()> for BoxedSystem<(), ()> {
     fn into_configs(self) -> SystemConfigs {
         SystemConfigs::new_system(self)
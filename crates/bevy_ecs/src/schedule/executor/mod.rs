@@ -1,10 +1,12 @@
 mod multi_threaded;
 mod simple;
 mod single_threaded;
+mod timings;
 
 pub use self::multi_threaded::{MainThreadExecutor, MultiThreadedExecutor};
 pub use self::simple::SimpleExecutor;
 pub use self::single_threaded::SingleThreadedExecutor;
+pub use self::timings::{SystemTiming, SystemTimings};
 
 use fixedbitset::FixedBitSet;
 
@@ -4,10 +4,10 @@ use std::{
 };
 
 use bevy_tasks::{ComputeTaskPool, Scope, TaskPool, ThreadExecutor};
-use bevy_utils::default;
 use bevy_utils::syncunsafecell::SyncUnsafeCell;
 #[cfg(feature = "trace")]
 use bevy_utils::tracing::{info_span, Instrument};
+use bevy_utils::{default, Duration, Instant};
 use std::panic::AssertUnwindSafe;
 
 use async_channel::{Receiver, Sender};
@@ -24,6 +24,8 @@ use crate::{
 
 use crate as bevy_ecs;
 
+use super::timings::RunTimings;
+
 /// A funky borrow split of [`SystemSchedule`] required by the [`MultiThreadedExecutor`].
 struct SyncUnsafeSchedule<'a> {
     systems: &'a [SyncUnsafeCell<BoxedSystem>],
@@ -68,6 +70,8 @@ struct SystemTaskMetadata {
 struct SystemResult {
     system_index: usize,
     success: bool,
+    /// How long the system took, if timings are collected.
+    duration: Option<Duration>,
 }
 
 /// Runs the schedule using a thread pool. Non-conflicting systems can run in parallel.
@@ -112,6 +116,8 @@ pub struct MultiThreadedExecutor {
     panic_payload: Arc<Mutex<Option<Box<dyn Any + Send>>>>,
     /// When set, stops the executor from running any more systems.
     stop_spawning: bool,
+    /// Timings of the systems, collected if the `SystemTimings` resource exists.
+    timings: RunTimings,
 }
 
 impl Default for MultiThreadedExecutor {
@@ -165,6 +171,7 @@ impl SystemExecutor for MultiThreadedExecutor {
         if self.num_systems == 0 {
             return;
         }
+        self.timings.begin(world, self.num_systems);
         self.num_running_systems = 0;
         self.num_completed_systems = 0;
         self.num_dependencies_remaining.clear();
@@ -248,6 +255,8 @@ impl SystemExecutor for MultiThreadedExecutor {
 
         debug_assert!(self.ready_systems.is_clear());
         debug_assert!(self.running_systems.is_clear());
+        self.timings
+            .finish(world, |system_index| schedule.systems[system_index].name());
         self.active_access.clear();
         self.evaluated_sets.clear();
         self.skipped_systems.clear();
@@ -282,6 +291,7 @@ impl MultiThreadedExecutor {
             apply_final_deferred: true,
             panic_payload: Arc::new(Mutex::new(None)),
             stop_spawning: false,
+            timings: RunTimings::new(),
         }
     }
 
@@ -494,9 +504,11 @@ impl MultiThreadedExecutor {
 
         let sender = self.sender.clone();
         let panic_payload = self.panic_payload.clone();
+        let measure = self.timings.is_enabled();
         let task = async move {
             #[cfg(feature = "trace")]
             let system_guard = system_span.enter();
+            let start = measure.then(Instant::now);
             let res = std::panic::catch_unwind(AssertUnwindSafe(|| {
                 // SAFETY:
                 // - The caller ensures that we have permission to
@@ -504,6 +516,7 @@ impl MultiThreadedExecutor {
                 // - `update_archetype_component_access` has been called.
                 unsafe { system.run_unsafe((), world) };
             }));
+            let duration = start.map(|start| start.elapsed());
             #[cfg(feature = "trace")]
             drop(system_guard);
             // tell the executor that the system finished
@@ -511,6 +524,7 @@ impl MultiThreadedExecutor {
                 .try_send(SystemResult {
                     system_index,
                     success: res.is_ok(),
+                    duration,
                 })
                 .unwrap_or_else(|error| unreachable!("{}", error));
             if let Err(payload) = res {
@@ -557,6 +571,7 @@ impl MultiThreadedExecutor {
 
         let sender = self.sender.clone();
         let panic_payload = self.panic_payload.clone();
+        let measure = self.timings.is_enabled();
         if is_apply_deferred(system) {
             // TODO: avoid allocation
             let unapplied_systems = self.unapplied_systems.clone();
@@ -564,7 +579,9 @@ impl MultiThreadedExecutor {
             let task = async move {
                 #[cfg(feature = "trace")]
                 let system_guard = system_span.enter();
+                let start = measure.then(Instant::now);
                 let res = apply_deferred(&unapplied_systems, systems, world);
+                let duration = start.map(|start| start.elapsed());
                 #[cfg(feature = "trace")]
                 drop(system_guard);
                 // tell the executor that the system finished
@@ -572,6 +589,7 @@ impl MultiThreadedExecutor {
                     .try_send(SystemResult {
                         system_index,
                         success: res.is_ok(),
+                        duration,
                     })
                     .unwrap_or_else(|error| unreachable!("{}", error));
                 if let Err(payload) = res {
@@ -588,9 +606,11 @@ impl MultiThreadedExecutor {
             let task = async move {
                 #[cfg(feature = "trace")]
                 let system_guard = system_span.enter();
+                let start = measure.then(Instant::now);
                 let res = std::panic::catch_unwind(AssertUnwindSafe(|| {
                     system.run((), world);
                 }));
+                let duration = start.map(|start| start.elapsed());
                 #[cfg(feature = "trace")]
                 drop(system_guard);
                 // tell the executor that the system finished
@@ -598,6 +618,7 @@ impl MultiThreadedExecutor {
                     .try_send(SystemResult {
                         system_index,
                         success: res.is_ok(),
+                        duration,
                     })
                     .unwrap_or_else(|error| unreachable!("{}", error));
                 if let Err(payload) = res {
@@ -624,7 +645,9 @@ impl MultiThreadedExecutor {
         let SystemResult {
             system_index,
             success,
+            duration,
         } = result;
+        self.timings.record_run(system_index, duration);
 
         if self.system_task_metadata[system_index].is_exclusive {
             self.exclusive_running = false;
@@ -649,6 +672,7 @@ impl MultiThreadedExecutor {
     }
 
     fn skip_system_and_signal_dependents(&mut self, system_index: usize) {
+        self.timings.record_skip(system_index);
         self.num_completed_systems += 1;
         self.completed_systems.insert(system_index);
         self.signal_dependents(system_index);
@@ -8,6 +8,8 @@ use crate::{
     world::World,
 };
 
+use super::timings::RunTimings;
+
 /// A variant of [`SingleThreadedExecutor`](crate::schedule::SingleThreadedExecutor) that calls
 /// [`apply_deferred`](crate::system::System::apply_deferred) immediately after running each system.
 #[derive(Default)]
@@ -16,6 +18,8 @@ pub struct SimpleExecutor {
     evaluated_sets: FixedBitSet,
     /// Systems that have run or been skipped.
     completed_systems: FixedBitSet,
+    /// Timings of the systems, collected if the `SystemTimings` resource exists.
+    timings: RunTimings,
 }
 
 impl SystemExecutor for SimpleExecutor {
@@ -35,6 +39,7 @@ impl SystemExecutor for SimpleExecutor {
     }
 
     fn run(&mut self, schedule: &mut SystemSchedule, world: &mut World) {
+        self.timings.begin(world, schedule.systems.len());
         for system_index in 0..schedule.systems.len() {
             #[cfg(feature = "trace")]
             let name = schedule.systems[system_index].name();
@@ -73,15 +78,19 @@ impl SystemExecutor for SimpleExecutor {
             self.completed_systems.insert(system_index);
 
             if !should_run {
+                self.timings.record_skip(system_index);
                 continue;
             }
 
             let system = &mut schedule.systems[system_index];
             #[cfg(feature = "trace")]
             let system_span = info_span!("system", name = &*name).entered();
+            let start = self.timings.start();
             let res = std::panic::catch_unwind(AssertUnwindSafe(|| {
                 system.run((), world);
             }));
+            self.timings
+                .record_run(system_index, start.map(|start| start.elapsed()));
             #[cfg(feature = "trace")]
             system_span.exit();
             if let Err(payload) = res {
@@ -92,6 +101,8 @@ impl SystemExecutor for SimpleExecutor {
             system.apply_deferred(world);
         }
 
+        self.timings
+            .finish(world, |system_index| schedule.systems[system_index].name());
         self.evaluated_sets.clear();
         self.completed_systems.clear();
     }
@@ -104,6 +115,7 @@ impl SimpleExecutor {
         Self {
             evaluated_sets: FixedBitSet::new(),
             completed_systems: FixedBitSet::new(),
+            timings: RunTimings::new(),
         }
     }
 }
@@ -8,6 +8,8 @@ use crate::{
     world::World,
 };
 
+use super::timings::RunTimings;
+
 /// Runs the schedule using a single thread.
 ///
 /// Useful if you're dealing with a single-threaded environment, saving your threads for
@@ -22,6 +24,8 @@ pub struct SingleThreadedExecutor {
     unapplied_systems: FixedBitSet,
     /// Setting when true applies deferred system buffers after all systems have run
     apply_final_deferred: bool,
+    /// Timings of the systems, collected if the `SystemTimings` resource exists.
+    timings: RunTimings,
 }
 
 impl SystemExecutor for SingleThreadedExecutor {
@@ -43,6 +47,7 @@ impl SystemExecutor for SingleThreadedExecutor {
     }
 
     fn run(&mut self, schedule: &mut SystemSchedule, world: &mut World) {
+        self.timings.begin(world, schedule.systems.len());
         for system_index in 0..schedule.systems.len() {
             #[cfg(feature = "trace")]
             let name = schedule.systems[system_index].name();
@@ -81,22 +86,28 @@ impl SystemExecutor for SingleThreadedExecutor {
             self.completed_systems.insert(system_index);
 
             if !should_run {
+                self.timings.record_skip(system_index);
                 continue;
             }
 
             let system = &mut schedule.systems[system_index];
+            let start = self.timings.start();
             if is_apply_deferred(system) {
                 #[cfg(feature = "trace")]
                 let system_span = info_span!("system", name = &*name).entered();
                 self.apply_deferred(schedule, world);
                 #[cfg(feature = "trace")]
                 system_span.exit();
+                self.timings
+                    .record_run(system_index, start.map(|start| start.elapsed()));
             } else {
                 #[cfg(feature = "trace")]
                 let system_span = info_span!("system", name = &*name).entered();
                 let res = std::panic::catch_unwind(AssertUnwindSafe(|| {
                     system.run((), world);
                 }));
+                self.timings
+                    .record_run(system_index, start.map(|start| start.elapsed()));
                 #[cfg(feature = "trace")]
                 system_span.exit();
                 if let Err(payload) = res {
@@ -110,6 +121,8 @@ impl SystemExecutor for SingleThreadedExecutor {
         if self.apply_final_deferred {
             self.apply_deferred(schedule, world);
         }
+        self.timings
+            .finish(world, |system_index| schedule.systems[system_index].name());
         self.evaluated_sets.clear();
         self.completed_systems.clear();
     }
@@ -125,6 +138,7 @@ impl SingleThreadedExecutor {
             completed_systems: FixedBitSet::new(),
             unapplied_systems: FixedBitSet::new(),
             apply_final_deferred: true,
+            timings: RunTimings::new(),
         }
     }
 
@@ -0,0 +1,209 @@
+use std::borrow::Cow;
+
+use bevy_utils::{Duration, HashMap, Instant};
+use fixedbitset::FixedBitSet;
+
+use crate::{self as bevy_ecs, system::Resource, world::World};
+
+/// Execution statistics of every system run by a [`Schedule`](crate::schedule::Schedule),
+/// keyed by system name.
+///
+/// Timings are opt-in: executors only measure systems while this resource exists in the [`World`].
+/// They are collected during the schedule run and written to this resource once it completes,
+/// so systems reading this resource see the timings of previous runs.
+///
+/// Systems sharing a name, like the same function added to several schedules, share their statistics.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::schedule::SystemTimings;
+/// fn physics() {}
+///
+/// let mut world = World::new();
+/// world.init_resource::<SystemTimings>();
+/// let mut schedule = Schedule::new();
+/// schedule.add_systems(physics);
+/// schedule.run(&mut world);
+///
+/// let timings = world.resource::<SystemTimings>();
+/// let (_, physics) = timings
+///     .iter()
+///     .find(|(name, _)| name.ends_with("physics"))
+///     .unwrap();
+/// assert_eq!(physics.run_count(), 1);
+/// ```
+#[derive(Resource, Debug, Default)]
+pub struct SystemTimings {
+    timings: HashMap<Cow<'static, str>, SystemTiming>,
+}
+
+/// Execution statistics of a system, stored in [`SystemTimings`].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SystemTiming {
+    last: Duration,
+    total: Duration,
+    max: Duration,
+    run_count: u64,
+    skipped_count: u64,
+}
+
+impl SystemTiming {
+    /// Returns how long the last run of the system took.
+    #[inline]
+    pub fn last(&self) -> Duration {
+        self.last
+    }
+
+    /// Returns the average duration of the runs of the system.
+    pub fn mean(&self) -> Duration {
+        if self.run_count == 0 {
+            return Duration::ZERO;
+        }
+        // the average of durations that fit in a `Duration` also does
+        Duration::from_nanos((self.total.as_nanos() / self.run_count as u128) as u64)
+    }
+
+    /// Returns the longest run of the system.
+    #[inline]
+    pub fn max(&self) -> Duration {
+        self.max
+    }
+
+    /// Returns the total time spent running the system.
+    #[inline]
+    pub fn total(&self) -> Duration {
+        self.total
+    }
+
+    /// Returns how many times the system ran.
+    #[inline]
+    pub fn run_count(&self) -> u64 {
+        self.run_count
+    }
+
+    /// Returns how many times the system was skipped because of its run conditions,
+    /// or the run conditions of one of its sets.
+    #[inline]
+    pub fn skipped_count(&self) -> u64 {
+        self.skipped_count
+    }
+
+    fn record_run(&mut self, duration: Duration) {
+        self.last = duration;
+        self.total += duration;
+        self.max = self.max.max(duration);
+        self.run_count += 1;
+    }
+}
+
+impl SystemTimings {
+    /// Returns the statistics of the system with the given name, if it was measured.
+    pub fn get(&self, name: &str) -> Option<&SystemTiming> {
+        self.timings.get(name)
+    }
+
+    /// Returns an iterator over the names and statistics of all measured systems.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &SystemTiming)> {
+        self.timings
+            .iter()
+            .map(|(name, timing)| (name.as_ref(), timing))
+    }
+
+    /// Returns the number of measured systems.
+    pub fn len(&self) -> usize {
+        self.timings.len()
+    }
+
+    /// Returns `true` if no system was measured.
+    pub fn is_empty(&self) -> bool {
+        self.timings.is_empty()
+    }
+
+    /// Forgets the statistics of all systems.
+    pub fn clear(&mut self) {
+        self.timings.clear();
+    }
+}
+
+/// The timings of the systems in a single schedule run, collected by an executor.
+#[derive(Default)]
+pub(super) struct RunTimings {
+    enabled: bool,
+    /// The duration of each system that ran.
+    durations: Vec<Option<Duration>>,
+    /// Systems skipped by their run conditions or the run conditions of their sets.
+    skipped: FixedBitSet,
+}
+
+impl RunTimings {
+    pub(super) const fn new() -> Self {
+        Self {
+            enabled: false,
+            durations: Vec::new(),
+            skipped: FixedBitSet::new(),
+        }
+    }
+
+    /// Starts collecting timings if the [`SystemTimings`] resource exists.
+    pub(super) fn begin(&mut self, world: &World, system_count: usize) {
+        self.enabled = world.contains_resource::<SystemTimings>();
+        if self.enabled {
+            self.durations.clear();
+            self.durations.resize(system_count, None);
+            self.skipped.clear();
+            self.skipped.grow(system_count);
+        }
+    }
+
+    #[inline]
+    pub(super) fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Returns the start time of a system run, if timings are collected.
+    #[inline]
+    pub(super) fn start(&self) -> Option<Instant> {
+        self.enabled.then(Instant::now)
+    }
+
+    #[inline]
+    pub(super) fn record_run(&mut self, system_index: usize, duration: Option<Duration>) {
+        if let Some(duration) = duration {
+            self.durations[system_index] = Some(duration);
+        }
+    }
+
+    #[inline]
+    pub(super) fn record_skip(&mut self, system_index: usize) {
+        if self.enabled {
+            self.skipped.insert(system_index);
+        }
+    }
+
+    /// Writes the collected timings to the [`SystemTimings`] resource.
+    pub(super) fn finish(
+        &mut self,
+        world: &mut World,
+        mut name: impl FnMut(usize) -> Cow<'static, str>,
+    ) {
+        if !std::mem::take(&mut self.enabled) {
+            return;
+        }
+        let Some(mut timings) = world.get_resource_mut::<SystemTimings>() else {
+            return;
+        };
+        for (system_index, duration) in self.durations.iter().enumerate() {
+            let skipped = self.skipped.contains(system_index);
+            if duration.is_none() && !skipped {
+                continue;
+            }
+            let timing = timings.timings.entry(name(system_index)).or_default();
+            if let Some(duration) = duration {
+                timing.record_run(*duration);
+            }
+            if skipped {
+                timing.skipped_count += 1;
+            }
+        }
+    }
+}
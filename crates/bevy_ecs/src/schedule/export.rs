@@ -0,0 +1,489 @@
+use std::{collections::BTreeSet, fmt::Write};
+
+use bevy_utils::{petgraph::Direction, HashMap};
+
+use crate::{
+    component::Components,
+    schedule::{BoxedCondition, NodeId, ScheduleGraph, SystemSchedule},
+    system::System,
+};
+
+/// A description of the systems, system sets and orderings of a [`Schedule`](crate::schedule::Schedule),
+/// created with [`Schedule::export`](crate::schedule::Schedule::export).
+///
+/// It can be rendered to Graphviz DOT with [`ScheduleExport::to_dot`], or to JSON with
+/// [`ScheduleExport::to_json`]. Nodes and edges are sorted, so that exporting the same schedule
+/// twice gives the same output.
+///
+/// Each system is implicitly part of a set named after its type, which is what ordering against a
+/// system function refers to. When such a set only contains one system, it is merged into that system.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScheduleExport {
+    /// The systems and system sets of the schedule, sorted by id.
+    pub nodes: Vec<ScheduleExportNode>,
+    /// The `(set, member)` pairs of the hierarchy, where `member` is a system or set directly in `set`.
+    pub hierarchy: Vec<(NodeId, NodeId)>,
+    /// The `(before, after)` pairs of the ordering constraints between systems and sets.
+    pub dependencies: Vec<(NodeId, NodeId)>,
+    /// The pairs of systems with conflicting data access and no ordering between them.
+    pub ambiguities: Vec<ScheduleAmbiguity>,
+}
+
+/// A system or system set in a [`ScheduleExport`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScheduleExportNode {
+    /// The id of the system or set in its [`ScheduleGraph`].
+    pub id: NodeId,
+    /// The name of the system or set.
+    pub name: String,
+    /// The names of the run conditions of the system or set.
+    pub conditions: Vec<String>,
+}
+
+/// Two systems with conflicting data access and no ordering between them.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ScheduleAmbiguity {
+    /// The system with the lowest id.
+    pub system_a: NodeId,
+    /// The system with the highest id.
+    pub system_b: NodeId,
+    /// The names of the components and resources both systems access, with at least one of them mutably.
+    ///
+    /// If empty, the systems conflict on [`World`](crate::world::World) access.
+    pub conflicts: Vec<String>,
+}
+
+impl ScheduleGraph {
+    /// Returns the pairs of systems with conflicting data access and no ordering between them,
+    /// sorted by id.
+    ///
+    /// Must be called after [`ScheduleGraph::build_schedule`] to be non-empty.
+    pub fn ambiguities(&self, components: &Components) -> Vec<ScheduleAmbiguity> {
+        let mut ambiguities: Vec<ScheduleAmbiguity> = self
+            .conflicting_systems()
+            .iter()
+            .map(|(a, b, conflicts)| {
+                let mut conflicts: Vec<String> = conflicts
+                    .iter()
+                    .filter_map(|&id| components.get_name(id))
+                    .map(|name| shorten(self, name.to_string()))
+                    .collect();
+                conflicts.sort();
+                ScheduleAmbiguity {
+                    system_a: *a.min(b),
+                    system_b: *a.max(b),
+                    conflicts,
+                }
+            })
+            .collect();
+        ambiguities.sort();
+        ambiguities
+    }
+}
+
+impl ScheduleExport {
+    pub(super) fn new(
+        graph: &ScheduleGraph,
+        executable: &SystemSchedule,
+        components: &Components,
+    ) -> Self {
+        // once built, the systems and run conditions are moved from the graph to the executable schedule
+        let mut systems: HashMap<NodeId, &dyn System<In = (), Out = ()>> = graph
+            .systems()
+            .map(|(id, system, _)| (id, system))
+            .collect();
+        systems.extend(
+            executable
+                .system_ids
+                .iter()
+                .zip(&executable.systems)
+                .map(|(&id, system)| (id, &**system)),
+        );
+        let mut conditions: HashMap<NodeId, &[BoxedCondition]> = graph
+            .systems()
+            .map(|(id, _, conditions)| (id, conditions))
+            .chain(
+                graph
+                    .system_sets()
+                    .map(|(id, _, conditions)| (id, conditions)),
+            )
+            .collect();
+        conditions.extend(
+            executable
+                .system_ids
+                .iter()
+                .zip(&executable.system_conditions)
+                .chain(executable.set_ids.iter().zip(&executable.set_conditions))
+                .map(|(&id, conditions)| (id, conditions.as_slice())),
+        );
+
+        // system type sets with a single instance are merged into their system
+        let mut merged = HashMap::new();
+        for (id, set, _) in graph.system_sets() {
+            if set.system_type().is_none() {
+                continue;
+            }
+            let mut members = graph
+                .hierarchy()
+                .graph()
+                .neighbors_directed(id, Direction::Outgoing);
+            if let (Some(system), None) = (members.next(), members.next()) {
+                merged.insert(id, system);
+            }
+        }
+        let resolve = |id: NodeId| merged.get(&id).copied().unwrap_or(id);
+
+        let mut nodes: Vec<ScheduleExportNode> = systems
+            .keys()
+            .copied()
+            .chain(
+                graph
+                    .system_sets()
+                    .map(|(id, _, _)| id)
+                    .filter(|id| !merged.contains_key(id)),
+            )
+            .map(|id| ScheduleExportNode {
+                id,
+                name: node_name(graph, &systems, id),
+                conditions: conditions
+                    .get(&id)
+                    .map(|conditions| {
+                        conditions
+                            .iter()
+                            .map(|condition| shorten(graph, condition.name().into_owned()))
+                            .collect()
+                    })
+                    .unwrap_or_default(),
+            })
+            .collect();
+        nodes.sort_by_key(|node| node.id);
+
+        let hierarchy: BTreeSet<(NodeId, NodeId)> = graph
+            .hierarchy()
+            .graph()
+            .all_edges()
+            .filter(|(set, _, _)| !merged.contains_key(set))
+            .map(|(set, member, _)| (set, resolve(member)))
+            .collect();
+        let dependencies: BTreeSet<(NodeId, NodeId)> = graph
+            .dependency()
+            .graph()
+            .all_edges()
+            .map(|(before, after, _)| (resolve(before), resolve(after)))
+            .collect();
+
+        ScheduleExport {
+            nodes,
+            hierarchy: hierarchy.into_iter().collect(),
+            dependencies: dependencies.into_iter().collect(),
+            ambiguities: graph.ambiguities(components),
+        }
+    }
+
+    /// Renders the schedule as a Graphviz DOT graph.
+    ///
+    /// Systems are drawn as boxes and sets as ellipses. Solid edges point from a system or set to the ones
+    /// that must run after it, dashed edges point from a set to its members, and red undirected edges
+    /// join ambiguous systems.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph schedule {\n");
+        for node in &self.nodes {
+            let shape = if node.id.is_system() {
+                "box"
+            } else {
+                "ellipse"
+            };
+            let mut label = node.name.clone();
+            for condition in &node.conditions {
+                write!(label, "\nif {condition}").unwrap();
+            }
+            writeln!(
+                dot,
+                "    \"{}\" [label={}, shape={shape}];",
+                node_key(node.id),
+                dot_string(&label),
+            )
+            .unwrap();
+        }
+        for &(set, member) in &self.hierarchy {
+            writeln!(
+                dot,
+                "    \"{}\" -> \"{}\" [style=dashed];",
+                node_key(set),
+                node_key(member),
+            )
+            .unwrap();
+        }
+        for &(before, after) in &self.dependencies {
+            writeln!(
+                dot,
+                "    \"{}\" -> \"{}\";",
+                node_key(before),
+                node_key(after)
+            )
+            .unwrap();
+        }
+        for ambiguity in &self.ambiguities {
+            let conflicts = if ambiguity.conflicts.is_empty() {
+                String::from("World")
+            } else {
+                ambiguity.conflicts.join(", ")
+            };
+            writeln!(
+                dot,
+                "    \"{}\" -> \"{}\" [dir=none, color=red, label={}];",
+                node_key(ambiguity.system_a),
+                node_key(ambiguity.system_b),
+                dot_string(&conflicts),
+            )
+            .unwrap();
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Renders the schedule as JSON.
+    ///
+    /// Nodes are identified by strings like `"system:0"` or `"set:3"`:
+    ///
+    /// ```json
+    /// {
+    ///   "nodes": [
+    ///     {"id": "system:0", "name": "physics", "conditions": ["paused"]}
+    ///   ],
+    ///   "hierarchy": [
+    ///     {"set": "set:1", "member": "system:0"}
+    ///   ],
+    ///   "dependencies": [
+    ///     {"before": "system:0", "after": "system:2"}
+    ///   ],
+    ///   "ambiguities": [
+    ///     {"system_a": "system:0", "system_b": "system:3", "conflicts": ["Transform"]}
+    ///   ]
+    /// }
+    /// ```
+    ///
+    /// Each node, edge and ambiguity is written on its own line, which keeps diffs between exports small.
+    pub fn to_json(&self) -> String {
+        let nodes = self.nodes.iter().map(|node| {
+            format!(
+                "{{\"id\": \"{}\", \"name\": {}, \"conditions\": {}}}",
+                node_key(node.id),
+                json_string(&node.name),
+                json_strings(&node.conditions),
+            )
+        });
+        let hierarchy = self.hierarchy.iter().map(|&(set, member)| {
+            format!(
+                "{{\"set\": \"{}\", \"member\": \"{}\"}}",
+                node_key(set),
+                node_key(member),
+            )
+        });
+        let dependencies = self.dependencies.iter().map(|&(before, after)| {
+            format!(
+                "{{\"before\": \"{}\", \"after\": \"{}\"}}",
+                node_key(before),
+                node_key(after),
+            )
+        });
+        let ambiguities = self.ambiguities.iter().map(|ambiguity| {
+            format!(
+                "{{\"system_a\": \"{}\", \"system_b\": \"{}\", \"conflicts\": {}}}",
+                node_key(ambiguity.system_a),
+                node_key(ambiguity.system_b),
+                json_strings(&ambiguity.conflicts),
+            )
+        });
+
+        let mut json = String::from("{\n");
+        write_json_array(&mut json, "nodes", nodes);
+        json.push_str(",\n");
+        write_json_array(&mut json, "hierarchy", hierarchy);
+        json.push_str(",\n");
+        write_json_array(&mut json, "dependencies", dependencies);
+        json.push_str(",\n");
+        write_json_array(&mut json, "ambiguities", ambiguities);
+        json.push_str("\n}\n");
+        json
+    }
+}
+
+fn node_name(
+    graph: &ScheduleGraph,
+    systems: &HashMap<NodeId, &dyn System<In = (), Out = ()>>,
+    id: NodeId,
+) -> String {
+    if let Some(system) = systems.get(&id) {
+        return shorten(graph, system.name().into_owned());
+    }
+    let set = graph.set_at(id);
+    if !set.is_anonymous() {
+        return shorten(graph, format!("{set:?}"));
+    }
+    let members: Vec<String> = graph
+        .hierarchy()
+        .graph()
+        .neighbors_directed(id, Direction::Outgoing)
+        .map(|member| node_name(graph, systems, member))
+        .collect();
+    format!("({})", members.join(", "))
+}
+
+fn shorten(graph: &ScheduleGraph, name: String) -> String {
+    if graph.settings().use_shortnames {
+        bevy_utils::get_short_name(&name)
+    } else {
+        name
+    }
+}
+
+fn node_key(id: NodeId) -> String {
+    match id {
+        NodeId::System(index) => format!("system:{index}"),
+        NodeId::Set(index) => format!("set:{index}"),
+    }
+}
+
+fn dot_string(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len() + 2);
+    escaped.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+fn json_string(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len() + 2);
+    escaped.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if c.is_control() => write!(escaped, "\\u{:04x}", c as u32).unwrap(),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+fn json_strings(values: &[String]) -> String {
+    let values: Vec<String> = values.iter().map(|value| json_string(value)).collect();
+    format!("[{}]", values.join(", "))
+}
+
+fn write_json_array(json: &mut String, key: &str, items: impl Iterator<Item = String>) {
+    let items: Vec<String> = items.map(|item| format!("    {item}")).collect();
+    if items.is_empty() {
+        write!(json, "  \"{key}\": []").unwrap();
+    } else {
+        write!(json, "  \"{key}\": [\n{}\n  ]", items.join(",\n")).unwrap();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        self as bevy_ecs,
+        schedule::{IntoSystemConfigs, IntoSystemSetConfig, NodeId, Schedule, SystemSet},
+        system::{Res, ResMut, Resource},
+        world::World,
+    };
+
+    #[derive(Resource, Default)]
+    struct R;
+
+    #[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+    struct Physics;
+
+    fn read(_: Res<R>) {}
+    fn write(_: ResMut<R>) {}
+    fn exclusive(_: &mut World) {}
+    fn paused() -> bool {
+        false
+    }
+
+    fn schedule(world: &mut World) -> Schedule {
+        let mut schedule = Schedule::new();
+        schedule
+            .configure_set(Physics.run_if(paused))
+            .add_systems((read, write).in_set(Physics))
+            .add_systems(exclusive.before(read));
+        schedule.initialize(world).unwrap();
+        schedule
+    }
+
+    #[test]
+    fn export() {
+        let mut world = World::new();
+        world.init_resource::<R>();
+        let schedule = schedule(&mut world);
+        let export = schedule.export(world.components());
+
+        let names: Vec<&str> = export.nodes.iter().map(|node| node.name.as_str()).collect();
+        assert_eq!(names, ["read", "write", "exclusive", "Physics"]);
+        let physics = export.nodes[3].id;
+        assert!(physics.is_set());
+        assert_eq!(export.nodes[3].conditions, ["paused"]);
+
+        let (read, write, exclusive) = (NodeId::System(0), NodeId::System(1), NodeId::System(2));
+        assert_eq!(export.hierarchy, [(physics, read), (physics, write)]);
+        assert_eq!(export.dependencies, [(exclusive, read)]);
+        assert_eq!(export.ambiguities.len(), 2);
+        assert_eq!(export.ambiguities[0].system_a, read);
+        assert_eq!(export.ambiguities[0].system_b, write);
+        assert_eq!(export.ambiguities[0].conflicts, ["R"]);
+        assert_eq!(export.ambiguities[1].system_a, write);
+        assert_eq!(export.ambiguities[1].system_b, exclusive);
+        assert!(export.ambiguities[1].conflicts.is_empty());
+    }
+
+    #[test]
+    fn export_is_stable() {
+        let mut world = World::new();
+        world.init_resource::<R>();
+        let first = schedule(&mut world).export(world.components());
+        let second = schedule(&mut world).export(world.components());
+
+        assert_eq!(first, second);
+        assert_eq!(first.to_json(), second.to_json());
+        assert_eq!(first.to_dot(), second.to_dot());
+    }
+
+    #[test]
+    fn to_json() {
+        let mut world = World::new();
+        world.init_resource::<R>();
+        let json = schedule(&mut world).export(world.components()).to_json();
+
+        assert!(json.starts_with("{\n  \"nodes\": [\n"));
+        assert!(json.contains(r#"{"id": "system:0", "name": "read", "conditions": []}"#));
+        assert!(json.contains(r#"{"before": "system:2", "after": "system:0"}"#));
+        assert!(json
+            .contains(r#"{"system_a": "system:0", "system_b": "system:1", "conflicts": ["R"]}"#));
+    }
+
+    #[test]
+    fn to_dot() {
+        let mut world = World::new();
+        world.init_resource::<R>();
+        let dot = schedule(&mut world).export(world.components()).to_dot();
+
+        assert!(dot.starts_with("digraph schedule {\n"));
+        assert!(dot.contains("\"system:0\" [label=\"read\", shape=box];"));
+        assert!(dot.contains("[label=\"Physics\\nif paused\", shape=ellipse];"));
+        assert!(dot.contains("\"system:2\" -> \"system:0\";"));
+        assert!(dot.ends_with("}\n"));
+    }
+}
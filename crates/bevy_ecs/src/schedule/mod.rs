@@ -3,6 +3,7 @@
 mod condition;
 mod config;
 mod executor;
+mod export;
 mod graph_utils;
 #[allow(clippy::module_inception)]
 mod schedule;
@@ -12,6 +13,7 @@ mod state;
 pub use self::condition::*;
 pub use self::config::*;
 pub use self::executor::*;
+pub use self::export::*;
 use self::graph_utils::*;
 pub use self::schedule::*;
 pub use self::set::*;
@@ -119,6 +121,94 @@ mod tests {
         }
     }
 
+    mod system_timings {
+        use super::*;
+
+        fn timing<'a>(timings: &'a SystemTimings, name: &str) -> &'a SystemTiming {
+            timings
+                .iter()
+                .find(|(system, _)| system.ends_with(name))
+                .unwrap()
+                .1
+        }
+
+        #[test]
+        fn collect_timings() {
+            for kind in [
+                ExecutorKind::Simple,
+                ExecutorKind::SingleThreaded,
+                ExecutorKind::MultiThreaded,
+            ] {
+                let mut world = World::default();
+                let mut schedule = Schedule::default();
+                schedule.set_executor_kind(kind);
+
+                world.init_resource::<SystemOrder>();
+                world.init_resource::<Counter>();
+                world.init_resource::<RunConditionBool>();
+                world.init_resource::<SystemTimings>();
+
+                schedule.add_systems((
+                    named_system,
+                    named_exclusive_system,
+                    counting_system.run_if(|condition: Res<RunConditionBool>| condition.0),
+                ));
+                schedule.run(&mut world);
+                schedule.run(&mut world);
+
+                let timings = world.resource::<SystemTimings>();
+                for name in ["::named_system", "::named_exclusive_system"] {
+                    let timing = timing(timings, name);
+                    assert_eq!(timing.run_count(), 2);
+                    assert_eq!(timing.skipped_count(), 0);
+                    assert!(timing.max() >= timing.mean());
+                    assert!(timing.total() >= timing.max());
+                }
+                let counting = timing(timings, "::counting_system");
+                assert_eq!(counting.run_count(), 0);
+                assert_eq!(counting.skipped_count(), 2);
+                assert_eq!(counting.mean(), bevy_utils::Duration::ZERO);
+            }
+        }
+
+        #[test]
+        fn skipped_by_set_condition() {
+            let mut world = World::default();
+            let mut schedule = Schedule::default();
+
+            world.init_resource::<Counter>();
+            world.init_resource::<RunConditionBool>();
+            world.init_resource::<SystemTimings>();
+
+            schedule
+                .configure_set(TestSet::A.run_if(|condition: Res<RunConditionBool>| condition.0));
+            schedule.add_systems(counting_system.in_set(TestSet::A));
+            schedule.run(&mut world);
+            world.resource_mut::<RunConditionBool>().0 = true;
+            schedule.run(&mut world);
+
+            let counting = timing(world.resource::<SystemTimings>(), "::counting_system");
+            assert_eq!(counting.run_count(), 1);
+            assert_eq!(counting.skipped_count(), 1);
+            assert_eq!(world.resource::<Counter>().0.load(Ordering::Relaxed), 1);
+        }
+
+        #[test]
+        fn timings_are_opt_in() {
+            let mut world = World::default();
+            let mut schedule = Schedule::default();
+
+            world.init_resource::<Counter>();
+            schedule.add_systems(counting_system);
+            schedule.run(&mut world);
+
+            world.init_resource::<SystemTimings>();
+            assert!(world.resource::<SystemTimings>().is_empty());
+            schedule.run(&mut world);
+            assert_eq!(world.resource::<SystemTimings>().len(), 1);
+        }
+    }
+
     mod system_ordering {
         use super::*;
 
@@ -19,7 +19,7 @@ use crate::{
     self as bevy_ecs,
     component::{ComponentId, Components, Tick},
     schedule::*,
-    system::{BoxedSystem, Resource, System},
+    system::{BoxedSystem, ErrorHandler, Resource, ScheduleErrorHandler, System},
     world::World,
 };
 
@@ -155,6 +155,7 @@ pub struct Schedule {
     executable: SystemSchedule,
     executor: Box<dyn SystemExecutor>,
     executor_initialized: bool,
+    error_handler: Option<ErrorHandler>,
 }
 
 impl Default for Schedule {
@@ -171,6 +172,7 @@ impl Schedule {
             executable: SystemSchedule::new(),
             executor: make_executor(ExecutorKind::default()),
             executor_initialized: false,
+            error_handler: None,
         }
     }
 
@@ -228,11 +230,28 @@ impl Schedule {
         self
     }
 
+    /// Returns the handler of the errors returned by the fallible systems of this schedule, if it has one.
+    pub fn get_error_handler(&self) -> Option<ErrorHandler> {
+        self.error_handler
+    }
+
+    /// Sets the handler of the errors returned by the fallible systems of this schedule,
+    /// instead of the [`DefaultErrorHandler`](crate::system::DefaultErrorHandler).
+    pub fn set_error_handler(&mut self, error_handler: ErrorHandler) -> &mut Self {
+        self.error_handler = Some(error_handler);
+        self
+    }
+
     /// Runs all systems in this schedule on the `world`, using its current execution strategy.
     pub fn run(&mut self, world: &mut World) {
         world.check_change_ticks();
         self.initialize(world).unwrap_or_else(|e| panic!("{e}"));
+        let outer_error_handler = ScheduleErrorHandler::enter(world, self.error_handler);
         self.executor.run(&mut self.executable, world);
+        for system in &mut self.executable.systems {
+            system.report_errors(world);
+        }
+        ScheduleErrorHandler::exit(world, outer_error_handler);
     }
 
     /// Initializes any newly-added systems and conditions, rebuilds the executable schedule,
@@ -266,6 +285,31 @@ impl Schedule {
         &mut self.graph
     }
 
+    /// Exports the systems, sets, ordering constraints and ambiguities of this schedule,
+    /// which can then be rendered to Graphviz DOT or JSON.
+    ///
+    /// Ambiguities are only known once the schedule has been built with [`Schedule::initialize`] or
+    /// [`Schedule::run`].
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// fn physics() {}
+    /// fn render() {}
+    ///
+    /// let mut world = World::new();
+    /// let mut schedule = Schedule::new();
+    /// schedule.add_systems((physics, render).chain());
+    /// schedule.initialize(&mut world).unwrap();
+    ///
+    /// let export = schedule.export(world.components());
+    /// assert_eq!(export.nodes.len(), 2);
+    /// assert_eq!(export.dependencies.len(), 1);
+    /// let dot = export.to_dot();
+    /// ```
+    pub fn export(&self, components: &Components) -> ScheduleExport {
+        ScheduleExport::new(&self.graph, &self.executable, components)
+    }
+
     /// Iterates the change ticks of all systems in the schedule and clamps any older than
     /// [`MAX_CHANGE_AGE`](crate::change_detection::MAX_CHANGE_AGE).
     /// This prevents overflow and thus prevents false positives.
@@ -498,6 +542,11 @@ impl ScheduleGraph {
         &self.dependency
     }
 
+    /// Returns the settings used to build this graph.
+    pub fn settings(&self) -> &ScheduleBuildSettings {
+        &self.settings
+    }
+
     /// Returns the list of systems that conflict with each other, i.e. have ambiguities in their access.
     ///
     /// If the `Vec<ComponentId>` is empty, the systems conflict on [`World`] access.
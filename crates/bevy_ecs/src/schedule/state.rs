@@ -1,12 +1,16 @@
+use std::collections::VecDeque;
 use std::fmt::Debug;
-use std::hash::Hash;
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
 use std::mem;
 use std::ops::Deref;
 
+use bevy_utils::{all_tuples, tracing::warn};
+
 use crate as bevy_ecs;
 use crate::change_detection::DetectChangesMut;
-use crate::schedule::ScheduleLabel;
-use crate::system::Resource;
+use crate::schedule::{IntoSystemSetConfig, Schedule, ScheduleLabel, SystemSet};
+use crate::system::{Local, Resource};
 use crate::world::World;
 
 pub use bevy_ecs_macros::States;
@@ -20,7 +24,7 @@ pub use bevy_ecs_macros::States;
 /// and the queued state with the [`NextState<T>`] resource.
 ///
 /// State transitions typically occur in the [`OnEnter<T::Variant>`] and [`OnExit<T:Variant>`] schedules,
-/// which can be run via the [`apply_state_transition::<T>`] system.
+/// which are run by the systems of the [`StateTransitionSteps`], starting with [`apply_next_state::<T>`].
 ///
 /// # Example
 ///
@@ -54,6 +58,16 @@ pub struct OnEnter<S: States>(pub S);
 #[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct OnExit<S: States>(pub S);
 
+/// The label of a [`Schedule`](super::Schedule) that runs whenever [`State<S>`]
+/// is covered by another state pushed on its [`StateStack<S>`].
+#[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct OnPause<S: States>(pub S);
+
+/// The label of a [`Schedule`](super::Schedule) that runs whenever [`State<S>`]
+/// returns to this state, when the state covering it is popped from its [`StateStack<S>`].
+#[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct OnResume<S: States>(pub S);
+
 /// The label of a [`Schedule`](super::Schedule) that **only** runs whenever [`State<S>`]
 /// exits the `from` state, AND enters the `to` state.
 ///
@@ -71,7 +85,7 @@ pub struct OnTransition<S: States> {
 ///
 /// The current state value can be accessed through this resource. To *change* the state,
 /// queue a transition in the [`NextState<S>`] resource, and it will be applied by the next
-/// [`apply_state_transition::<S>`] system.
+/// [`apply_next_state::<S>`] system, in the [`StateTransitionSteps::UpdateStates`] step.
 ///
 /// The starting state is defined via the [`Default`] implementation for `S`.
 #[derive(Resource, Default, Debug)]
@@ -109,7 +123,7 @@ impl<S: States> Deref for State<S> {
 ///
 /// To queue a transition, just set the contained value to `Some(next_state)`.
 /// Note that these transitions can be overridden by other systems:
-/// only the actual value of this resource at the time of [`apply_state_transition`] matters.
+/// only the actual value of this resource at the time of [`apply_next_state`] matters.
 #[derive(Resource, Default, Debug)]
 pub struct NextState<S: States>(pub Option<S>);
 
@@ -121,6 +135,10 @@ impl<S: States> NextState<S> {
 }
 
 /// Run the enter schedule (if it exists) for the current state.
+#[deprecated(
+    since = "0.11.0",
+    note = "The initial state is entered by `apply_next_state`, with the other systems of the `StateTransitionSteps`."
+)]
 pub fn run_enter_schedule<S: States>(world: &mut World) {
     world
         .try_run_schedule(OnEnter(world.resource::<State<S>>().0.clone()))
@@ -132,6 +150,13 @@ pub fn run_enter_schedule<S: States>(world: &mut World) {
 /// - Runs the [`OnExit(exited_state)`] schedule, if it exists.
 /// - Runs the [`OnTransition { from: exited_state, to: entered_state }`](OnTransition), if it exists.
 /// - Runs the [`OnEnter(entered_state)`] schedule, if it exists.
+///
+/// This skips the [`StateStack<S>`], and the ordering of the transitions of the computed states
+/// and sub-states depending on `S`.
+#[deprecated(
+    since = "0.11.0",
+    note = "Use `apply_next_state` and the other systems of the `StateTransitionSteps`, which `App::add_state` adds to the `StateTransition` schedule."
+)]
 pub fn apply_state_transition<S: States>(world: &mut World) {
     // We want to take the `NextState` resource,
     // but only mark it as changed if it wasn't empty.
@@ -154,3 +179,498 @@ pub fn apply_state_transition<S: States>(world: &mut World) {
         }
     }
 }
+
+/// A [`States`] type whose value is computed from other states, its [`SourceStates`](ComputedStates::SourceStates).
+///
+/// The value is recomputed whenever a source state changes. [`State<S>`] only exists while
+/// [`compute`](ComputedStates::compute) returns `Some`, and [`NextState<S>`] is not used.
+///
+/// Computed states are added to an app with `add_computed_state`, after their source states.
+///
+/// # Example
+///
+/// ```rust
+/// use bevy_ecs::prelude::*;
+///
+/// #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, States)]
+/// enum AppState {
+///     #[default]
+///     Menu,
+///     Playing,
+///     Paused,
+/// }
+///
+/// // Exists while the game is playing or paused.
+/// #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, States)]
+/// enum InGame {
+///     #[default]
+///     Running,
+/// }
+///
+/// impl ComputedStates for InGame {
+///     type SourceStates = AppState;
+///
+///     fn compute(app_state: AppState) -> Option<Self> {
+///         match app_state {
+///             AppState::Playing | AppState::Paused => Some(InGame::Running),
+///             AppState::Menu => None,
+///         }
+///     }
+/// }
+/// ```
+pub trait ComputedStates: States {
+    /// The states this state is computed from: a single [`States`] type, or a tuple of them.
+    type SourceStates: StateSet;
+
+    /// Computes the value of this state from the current values of its source states.
+    /// Returns `None` if this state shouldn't exist.
+    fn compute(sources: <Self::SourceStates as StateSet>::Values) -> Option<Self>;
+}
+
+/// A [`States`] type that only exists while its [`SourceStates`](SubStates::SourceStates) have given values.
+///
+/// When [`should_exist`](SubStates::should_exist) starts returning `true`, [`State<S>`] is created with
+/// the [`Default`] value, or the value queued in [`NextState<S>`]. While it exists, it is changed with
+/// [`NextState<S>`] like any other state. It is removed when `should_exist` returns `false`.
+///
+/// Sub-states are added to an app with `add_sub_state`, after their source states.
+///
+/// # Example
+///
+/// ```rust
+/// use bevy_ecs::prelude::*;
+///
+/// #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, States)]
+/// enum AppState {
+///     #[default]
+///     Menu,
+///     InGame,
+/// }
+///
+/// #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, States)]
+/// enum MenuScreen {
+///     #[default]
+///     Main,
+///     Settings,
+/// }
+///
+/// impl SubStates for MenuScreen {
+///     type SourceStates = AppState;
+///
+///     fn should_exist(app_state: AppState) -> bool {
+///         app_state == AppState::Menu
+///     }
+/// }
+/// ```
+pub trait SubStates: States {
+    /// The states this state depends on: a single [`States`] type, or a tuple of them.
+    type SourceStates: StateSet;
+
+    /// Returns `true` if this state should exist, given the current values of its source states.
+    fn should_exist(sources: <Self::SourceStates as StateSet>::Values) -> bool;
+}
+
+/// A set of [`States`] that [`ComputedStates`] and [`SubStates`] depend on.
+///
+/// This is implemented for every [`States`] type, and for tuples of them.
+pub trait StateSet: 'static {
+    /// The current values of the states in the set.
+    ///
+    /// For a single state, this is the value of the state. For a tuple, each state
+    /// is `None` if it doesn't exist.
+    type Values;
+
+    /// Returns the current values of the states in the set, or `None` if a single state doesn't exist.
+    fn values(world: &World) -> Option<Self::Values>;
+
+    /// Orders the transition systems of the dependent state `S` relative to the states in this set.
+    ///
+    /// `S` is updated after its sources, exited before them, and transitioned and entered after them.
+    fn configure_dependent<S: States>(schedule: &mut Schedule);
+}
+
+impl<S: States> StateSet for S {
+    type Values = S;
+
+    fn values(world: &World) -> Option<Self::Values> {
+        world
+            .get_resource::<State<S>>()
+            .map(|state| state.get().clone())
+    }
+
+    fn configure_dependent<D: States>(schedule: &mut Schedule) {
+        use StateTransitionSteps::*;
+        schedule.configure_sets((
+            StateTransitionSet::<D>::new(UpdateStates)
+                .after(StateTransitionSet::<S>::new(UpdateStates)),
+            StateTransitionSet::<D>::new(ExitSchedules)
+                .before(StateTransitionSet::<S>::new(ExitSchedules)),
+            StateTransitionSet::<D>::new(TransitionSchedules)
+                .after(StateTransitionSet::<S>::new(TransitionSchedules)),
+            StateTransitionSet::<D>::new(EnterSchedules)
+                .after(StateTransitionSet::<S>::new(EnterSchedules)),
+        ));
+    }
+}
+
+macro_rules! impl_state_set {
+    ($($state: ident),*) => {
+        impl<$($state: States),*> StateSet for ($($state,)*) {
+            type Values = ($(Option<$state>,)*);
+
+            fn values(world: &World) -> Option<Self::Values> {
+                Some(($(<$state as StateSet>::values(world),)*))
+            }
+
+            #[allow(unused_variables)]
+            fn configure_dependent<D: States>(schedule: &mut Schedule) {
+                $(<$state as StateSet>::configure_dependent::<D>(schedule);)*
+            }
+        }
+    };
+}
+
+all_tuples!(impl_state_set, 1, 8, S);
+
+/// The steps of a state transition pass, run in order.
+///
+/// All states are updated first, then the [`OnExit`] schedules of every exited state run, starting with
+/// the states that depend on others. The [`OnTransition`] schedules and the [`OnEnter`] schedules
+/// then run, starting with the states others depend on.
+#[derive(SystemSet, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum StateTransitionSteps {
+    /// Updates the values of the states, with [`apply_next_state`], [`compute_state`] and [`update_sub_state`].
+    UpdateStates,
+    /// Runs the [`OnExit`] schedules of the exited states, with [`exit_state`].
+    ExitSchedules,
+    /// Runs the [`OnTransition`] schedules of the changed states, with [`transition_state`].
+    TransitionSchedules,
+    /// Runs the [`OnEnter`] schedules of the entered states, with [`enter_state`].
+    EnterSchedules,
+}
+
+/// The [`SystemSet`] of the system running a [`StateTransitionSteps`] for the state `S`.
+pub struct StateTransitionSet<S: States> {
+    step: StateTransitionSteps,
+    _marker: PhantomData<fn() -> S>,
+}
+
+impl<S: States> StateTransitionSet<S> {
+    /// Creates the set of the system running `step` for the state `S`.
+    pub fn new(step: StateTransitionSteps) -> Self {
+        Self {
+            step,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<S: States> Debug for StateTransitionSet<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("StateTransitionSet")
+            .field(&format_args!("{}", std::any::type_name::<S>()))
+            .field(&self.step)
+            .finish()
+    }
+}
+
+impl<S: States> Hash for StateTransitionSet<S> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.step.hash(state);
+    }
+}
+
+impl<S: States> Clone for StateTransitionSet<S> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<S: States> Copy for StateTransitionSet<S> {}
+
+impl<S: States> PartialEq for StateTransitionSet<S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.step == other.step
+    }
+}
+
+impl<S: States> Eq for StateTransitionSet<S> {}
+
+impl<S: States> SystemSet for StateTransitionSet<S> {
+    fn dyn_clone(&self) -> Box<dyn SystemSet> {
+        Box::new(*self)
+    }
+}
+
+/// The states covered by the current [`State<S>`], which can be returned to.
+///
+/// Operations queued on this resource are applied in the order they were queued, one per transition
+/// pass of an app. A state queued in [`NextState<S>`] while operations are pending is applied
+/// after them, as a [`replace`](StateStack::replace):
+/// - [`push`](StateStack::push) covers the current state with a new one. The [`OnPause`] schedule
+///   of the covered state runs, followed by the [`OnEnter`] schedule of the new state.
+/// - [`pop`](StateStack::pop) returns to the last covered state. The [`OnExit`] schedule of the
+///   current state runs, followed by the [`OnResume`] schedule of the covered state.
+/// - [`replace`](StateStack::replace) changes the current state, like [`NextState::set`].
+///
+/// The [`OnTransition`] schedules run for each of these operations.
+///
+/// # Example
+///
+/// ```rust
+/// use bevy_ecs::prelude::*;
+///
+/// #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, States)]
+/// enum GameState {
+///     #[default]
+///     Playing,
+///     Paused,
+/// }
+///
+/// fn pause(mut stack: ResMut<StateStack<GameState>>) {
+///     stack.push(GameState::Paused);
+/// }
+///
+/// fn unpause(mut stack: ResMut<StateStack<GameState>>) {
+///     stack.pop();
+/// }
+/// # bevy_ecs::system::assert_is_system(pause);
+/// # bevy_ecs::system::assert_is_system(unpause);
+/// ```
+#[derive(Resource, Debug, Default)]
+pub struct StateStack<S: States> {
+    covered: Vec<S>,
+    queued: VecDeque<StackOperation<S>>,
+}
+
+#[derive(Debug)]
+enum StackOperation<S: States> {
+    Push(S),
+    Pop,
+    Replace(S),
+}
+
+impl<S: States> StateStack<S> {
+    /// Queues covering the current state with `state`.
+    pub fn push(&mut self, state: S) {
+        self.queued.push_back(StackOperation::Push(state));
+    }
+
+    /// Queues returning to the last covered state.
+    ///
+    /// Nothing happens if no state is covered when the operation is applied.
+    pub fn pop(&mut self) {
+        self.queued.push_back(StackOperation::Pop);
+    }
+
+    /// Queues replacing the current state with `state`, without changing the covered states.
+    pub fn replace(&mut self, state: S) {
+        self.queued.push_back(StackOperation::Replace(state));
+    }
+
+    /// Returns the covered states, from the first one covered to the last one.
+    pub fn covered(&self) -> &[S] {
+        &self.covered
+    }
+
+    /// Returns `true` if `state` is one of the covered states.
+    pub fn is_covered(&self, state: &S) -> bool {
+        self.covered.contains(state)
+    }
+}
+
+/// How [`State<S>`] was changed by a [`PendingTransition`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TransitionKind {
+    Replace,
+    Push,
+    Pop,
+}
+
+/// A change of [`State<S>`] made during the [`StateTransitionSteps::UpdateStates`] step,
+/// whose schedules have not all run yet.
+///
+/// `exited` is `None` if the state didn't exist before, and `entered` is `None` if it was removed.
+#[derive(Resource, Debug)]
+struct PendingTransition<S: States> {
+    exited: Option<S>,
+    entered: Option<S>,
+    kind: TransitionKind,
+}
+
+fn set_state<S: States>(world: &mut World, exited: Option<S>, entered: Option<S>) {
+    set_state_with(world, exited, entered, TransitionKind::Replace);
+}
+
+fn set_state_with<S: States>(
+    world: &mut World,
+    exited: Option<S>,
+    entered: Option<S>,
+    kind: TransitionKind,
+) {
+    // pushing or popping a state is a transition even if the value stays the same
+    if kind == TransitionKind::Replace && exited == entered {
+        return;
+    }
+    match &entered {
+        Some(entered) => match world.get_resource_mut::<State<S>>() {
+            Some(mut state) => state.0 = entered.clone(),
+            None => world.insert_resource(State(entered.clone())),
+        },
+        None => {
+            world.remove_resource::<State<S>>();
+        }
+    }
+    world.insert_resource(PendingTransition {
+        exited,
+        entered,
+        kind,
+    });
+}
+
+/// Applies the first operation queued in [`StateStack<S>`], or else the state queued in [`NextState<S>`],
+/// to [`State<S>`], during the [`StateTransitionSteps::UpdateStates`] step.
+///
+/// The first time it runs, the current state is entered. If a state is already queued in
+/// [`NextState<S>`] by then, the [`OnEnter`] schedule of the current state runs right away,
+/// and the queued state is then applied as a regular transition.
+pub fn apply_next_state<S: States>(world: &mut World, mut entered_initial_state: Local<bool>) {
+    // We want to take the `NextState` resource,
+    // but only mark it as changed if it wasn't empty.
+    let mut next_state_resource = world.resource_mut::<NextState<S>>();
+    let next_state = next_state_resource.bypass_change_detection().0.take();
+    if next_state.is_some() {
+        next_state_resource.set_changed();
+    }
+    let current = world.resource::<State<S>>().0.clone();
+
+    if !*entered_initial_state {
+        *entered_initial_state = true;
+        let Some(entered) = next_state else {
+            set_state(world, None, Some(current));
+            return;
+        };
+        world.try_run_schedule(OnEnter(current.clone())).ok();
+        set_state(world, Some(current), Some(entered));
+        return;
+    }
+
+    let Some(mut stack) = world.get_resource_mut::<StateStack<S>>() else {
+        if let Some(entered) = next_state {
+            set_state(world, Some(current), Some(entered));
+        }
+        return;
+    };
+    let queued = &mut stack.bypass_change_detection().queued;
+    if let Some(entered) = next_state {
+        if queued.is_empty() {
+            set_state(world, Some(current), Some(entered));
+            return;
+        }
+        // applied once the operations queued before it are
+        queued.push_back(StackOperation::Replace(entered));
+    }
+    let Some(operation) = queued.pop_front() else {
+        return;
+    };
+    match operation {
+        StackOperation::Push(entered) => {
+            stack.covered.push(current.clone());
+            set_state_with(world, Some(current), Some(entered), TransitionKind::Push);
+        }
+        StackOperation::Pop => {
+            let Some(resumed) = stack.covered.pop() else {
+                warn!("Cannot pop state {current:?}: no state is covered by it.");
+                return;
+            };
+            set_state_with(world, Some(current), Some(resumed), TransitionKind::Pop);
+        }
+        StackOperation::Replace(entered) => {
+            set_state(world, Some(current), Some(entered));
+        }
+    }
+}
+
+/// Recomputes the [`ComputedStates`] `S` from its source states, during the
+/// [`StateTransitionSteps::UpdateStates`] step.
+pub fn compute_state<S: ComputedStates>(world: &mut World) {
+    let entered = S::SourceStates::values(world).and_then(S::compute);
+    let exited = world
+        .get_resource::<State<S>>()
+        .map(|state| state.0.clone());
+    set_state(world, exited, entered);
+}
+
+/// Creates, removes or applies the state queued in [`NextState<S>`] to the [`SubStates`] `S`,
+/// during the [`StateTransitionSteps::UpdateStates`] step.
+pub fn update_sub_state<S: SubStates>(world: &mut World) {
+    let should_exist = S::SourceStates::values(world).is_some_and(S::should_exist);
+    let next_state = world
+        .get_resource_mut::<NextState<S>>()
+        .and_then(|mut next_state| next_state.0.take());
+    let exited = world
+        .get_resource::<State<S>>()
+        .map(|state| state.0.clone());
+
+    let entered = match (should_exist, &exited) {
+        (false, _) => {
+            if let Some(dropped) = next_state {
+                warn!(
+                    "Ignoring the queued state {dropped:?}: the sub-state {} doesn't exist in the current source states.",
+                    std::any::type_name::<S>()
+                );
+            }
+            None
+        }
+        (true, None) => Some(next_state.unwrap_or_default()),
+        (true, Some(exited)) => Some(next_state.unwrap_or_else(|| exited.clone())),
+    };
+    set_state(world, exited, entered);
+}
+
+/// Runs the [`OnExit`] schedule of the state exited during this transition pass, if any,
+/// or its [`OnPause`] schedule if it was covered by a pushed state.
+pub fn exit_state<S: States>(world: &mut World) {
+    let Some((exited, kind)) = world
+        .get_resource::<PendingTransition<S>>()
+        .and_then(|transition| Some((transition.exited.clone()?, transition.kind)))
+    else {
+        return;
+    };
+    match kind {
+        TransitionKind::Push => world.try_run_schedule(OnPause(exited)).ok(),
+        _ => world.try_run_schedule(OnExit(exited)).ok(),
+    };
+}
+
+/// Runs the [`OnTransition`] schedule of the state changed during this transition pass, if any.
+pub fn transition_state<S: States>(world: &mut World) {
+    let Some(PendingTransition {
+        exited: Some(from),
+        entered: Some(to),
+        ..
+    }) = world.get_resource::<PendingTransition<S>>()
+    else {
+        return;
+    };
+    let transition = OnTransition {
+        from: from.clone(),
+        to: to.clone(),
+    };
+    world.try_run_schedule(transition).ok();
+}
+
+/// Runs the [`OnEnter`] schedule of the state entered during this transition pass, if any,
+/// or its [`OnResume`] schedule if it was returned to by popping a state, and completes the transition.
+pub fn enter_state<S: States>(world: &mut World) {
+    let Some(transition) = world.remove_resource::<PendingTransition<S>>() else {
+        return;
+    };
+    let Some(entered) = transition.entered else {
+        return;
+    };
+    match transition.kind {
+        TransitionKind::Pop => world.try_run_schedule(OnResume(entered)).ok(),
+        _ => world.try_run_schedule(OnEnter(entered)).ok(),
+    };
+}
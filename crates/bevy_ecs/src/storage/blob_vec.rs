@@ -13,7 +13,7 @@ use bevy_utils::OnDrop;
 /// Used to densely store homogeneous ECS data. A blob is usually just an arbitrary block of contiguous memory without any identity, and
 /// could be used to represent any arbitrary data (i.e. string, arrays, etc). This type is an extendable and re-allocatable blob, which makes
 /// it a blobby Vec, a `BlobVec`.
-pub(super) struct BlobVec {
+pub(crate) struct BlobVec {
     item_layout: Layout,
     capacity: usize,
     /// Number of elements, not bytes
@@ -120,6 +120,35 @@ impl BlobVec {
         }
     }
 
+    /// Shrinks the capacity of the vector as much as possible, down to its length.
+    pub fn shrink_to_fit(&mut self) {
+        if self.item_layout.size() == 0 || self.capacity == self.len {
+            return;
+        }
+        let old_layout =
+            array_layout(&self.item_layout, self.capacity).expect("array layout should be valid");
+        if self.len == 0 {
+            // SAFETY:
+            // - ptr was allocated via this allocator, with the layout `old_layout`
+            // - `item_layout.size() > 0` and `capacity > len`, so the layout size is non-zero
+            unsafe { std::alloc::dealloc(self.get_ptr_mut().as_ptr(), old_layout) };
+            let align = NonZeroUsize::new(self.item_layout.align()).expect("alignment must be > 0");
+            self.data = bevy_ptr::dangling_with_align(align);
+        } else {
+            let new_layout =
+                array_layout(&self.item_layout, self.len).expect("array layout should be valid");
+            // SAFETY:
+            // - ptr was allocated via this allocator, with the layout `old_layout`
+            // - `item_layout.size() > 0` and `len > 0`, so the new layout size is non-zero
+            // - the new size is smaller than the size of `old_layout`, so it can't overflow
+            let new_data = unsafe {
+                std::alloc::realloc(self.get_ptr_mut().as_ptr(), old_layout, new_layout.size())
+            };
+            self.data = NonNull::new(new_data).unwrap_or_else(|| handle_alloc_error(new_layout));
+        }
+        self.capacity = self.len;
+    }
+
     // SAFETY: must not be called for a ZST item layout
     #[warn(unsafe_op_in_unsafe_fn)] // to allow unsafe blocks in unsafe fn
     unsafe fn grow_exact(&mut self, increment: NonZeroUsize) {
@@ -523,6 +552,34 @@ mod tests {
         assert_eq!(blob_vec.capacity(), 1_000);
     }
 
+    #[test]
+    fn shrink_to_fit() {
+        let item_layout = Layout::new::<usize>();
+        // SAFETY: `drop` fn is `None`, usize doesn't need dropping
+        let mut blob_vec = unsafe { BlobVec::new(item_layout, None, 64) };
+        // SAFETY: `i` is a usize, i.e. the type corresponding to `item_layout`
+        unsafe {
+            for i in 0..10 {
+                push(&mut blob_vec, i as usize);
+            }
+        }
+
+        blob_vec.shrink_to_fit();
+        assert_eq!(blob_vec.capacity(), 10);
+        // SAFETY: there are 10 usize elements in the vector
+        unsafe {
+            assert_eq!(*get_mut::<usize>(&mut blob_vec, 9), 9);
+            assert_eq!(swap_remove::<usize>(&mut blob_vec, 0), 0);
+        }
+
+        blob_vec.clear();
+        blob_vec.shrink_to_fit();
+        assert_eq!(blob_vec.capacity(), 0);
+        // SAFETY: `1` is a usize, i.e. the type corresponding to `item_layout`
+        unsafe { push(&mut blob_vec, 1_usize) };
+        assert_eq!(blob_vec.len(), 1);
+    }
+
     #[derive(Debug, Eq, PartialEq, Clone)]
     struct Foo {
         a: u8,
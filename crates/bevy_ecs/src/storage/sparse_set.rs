@@ -102,6 +102,17 @@ impl<I: SparseSetIndex, V> SparseArray<I, V> {
         self.values.clear();
     }
 
+    /// Drops the empty slots after the last value, and shrinks the capacity as much as possible.
+    pub fn shrink_to_fit(&mut self) {
+        let len = self
+            .values
+            .iter()
+            .rposition(Option::is_some)
+            .map_or(0, |last| last + 1);
+        self.values.truncate(len);
+        self.values.shrink_to_fit();
+    }
+
     /// Converts the [`SparseArray`] into an immutable variant.
     pub(crate) fn into_immutable(self) -> ImmutableSparseArray<I, V> {
         ImmutableSparseArray {
@@ -145,6 +156,13 @@ impl ComponentSparseSet {
         self.sparse.clear();
     }
 
+    /// Shrinks the capacity of the sparse set as much as possible.
+    pub(crate) fn shrink_to_fit(&mut self) {
+        self.dense.shrink_to_fit();
+        self.entities.shrink_to_fit();
+        self.sparse.shrink_to_fit();
+    }
+
     /// Returns the number of component values in the sparse set.
     #[inline]
     pub fn len(&self) -> usize {
@@ -157,6 +175,27 @@ impl ComponentSparseSet {
         self.dense.len() == 0
     }
 
+    /// Returns the [`Column`] storing the component values of the sparse set.
+    #[inline]
+    pub(crate) fn dense(&self) -> &Column {
+        &self.dense
+    }
+
+    /// Returns the [`Column`] storing the component values of the sparse set, mutably.
+    #[inline]
+    pub(crate) fn dense_mut(&mut self) -> &mut Column {
+        &mut self.dense
+    }
+
+    /// Returns the row of the component value of `entity` in the [`dense`](Self::dense) column.
+    #[inline]
+    pub(crate) fn dense_row(&self, entity: Entity) -> Option<TableRow> {
+        let dense_index = *self.sparse.get(entity.index())?;
+        #[cfg(debug_assertions)]
+        assert_eq!(entity, self.entities[dense_index as usize]);
+        Some(TableRow::new(dense_index as usize))
+    }
+
     /// Inserts the `entity` key and component `value` pair into this sparse
     /// set.
     ///
@@ -233,6 +272,8 @@ impl ComponentSparseSet {
                 TickCells {
                     added: self.dense.get_added_ticks_unchecked(dense_index),
                     changed: self.dense.get_changed_ticks_unchecked(dense_index),
+                    #[cfg(feature = "track_change_detection")]
+                    changed_by: self.dense.get_changed_by_unchecked(dense_index),
                 },
             ))
         }
@@ -629,6 +670,12 @@ impl SparseSets {
             set.check_change_ticks(change_tick);
         }
     }
+
+    pub(crate) fn shrink_to_fit(&mut self) {
+        for set in self.sets.values_mut() {
+            set.shrink_to_fit();
+        }
+    }
 }
 
 #[cfg(test)]
@@ -1,3 +1,5 @@
+#[cfg(feature = "track_change_detection")]
+use crate::change_detection::ChangedBy;
 use crate::{
     component::{ComponentId, ComponentInfo, ComponentTicks, Components, Tick, TickCells},
     entity::Entity,
@@ -98,7 +100,8 @@ impl TableRow {
 ///
 /// Conceptually, a [`Column`] is very similar to a type-erased `Vec<T>`.
 /// It also stores the change detection ticks for its components, kept in two separate
-/// contiguous buffers internally. An element shares its data across these buffers by using the
+/// contiguous buffers internally, and with the `track_change_detection` feature, where each
+/// component was last changed in a third one. An element shares its data across these buffers by using the
 /// same index (i.e. the entity at row 3 has it's data at index 3 and its change detection ticks at
 /// index 3). A slice to these contiguous blocks of memory can be fetched
 /// via [`Column::get_data_slice`], [`Column::get_added_ticks_slice`], and
@@ -112,6 +115,8 @@ pub struct Column {
     data: BlobVec,
     added_ticks: Vec<UnsafeCell<Tick>>,
     changed_ticks: Vec<UnsafeCell<Tick>>,
+    #[cfg(feature = "track_change_detection")]
+    changed_by: Vec<UnsafeCell<Option<ChangedBy>>>,
 }
 
 impl Column {
@@ -123,6 +128,8 @@ impl Column {
             data: unsafe { BlobVec::new(component_info.layout(), component_info.drop(), capacity) },
             added_ticks: Vec::with_capacity(capacity),
             changed_ticks: Vec::with_capacity(capacity),
+            #[cfg(feature = "track_change_detection")]
+            changed_by: Vec::with_capacity(capacity),
         }
     }
 
@@ -144,6 +151,10 @@ impl Column {
         self.data.initialize_unchecked(row.index(), data);
         *self.added_ticks.get_unchecked_mut(row.index()).get_mut() = tick;
         *self.changed_ticks.get_unchecked_mut(row.index()).get_mut() = tick;
+        #[cfg(feature = "track_change_detection")]
+        {
+            *self.changed_by.get_unchecked_mut(row.index()).get_mut() = None;
+        }
     }
 
     /// Writes component data to the column at given row.
@@ -156,6 +167,10 @@ impl Column {
         debug_assert!(row.index() < self.len());
         self.data.replace_unchecked(row.index(), data);
         *self.changed_ticks.get_unchecked_mut(row.index()).get_mut() = change_tick;
+        #[cfg(feature = "track_change_detection")]
+        {
+            *self.changed_by.get_unchecked_mut(row.index()).get_mut() = None;
+        }
     }
 
     /// Writes component data to the column at given row.
@@ -198,6 +213,8 @@ impl Column {
         self.data.swap_remove_and_drop_unchecked(row.index());
         self.added_ticks.swap_remove(row.index());
         self.changed_ticks.swap_remove(row.index());
+        #[cfg(feature = "track_change_detection")]
+        self.changed_by.swap_remove(row.index());
     }
 
     /// Removes an element from the [`Column`] and returns it and its change detection ticks.
@@ -221,6 +238,8 @@ impl Column {
             let data = unsafe { self.data.swap_remove_and_forget_unchecked(row.index()) };
             let added = self.added_ticks.swap_remove(row.index()).into_inner();
             let changed = self.changed_ticks.swap_remove(row.index()).into_inner();
+            #[cfg(feature = "track_change_detection")]
+            self.changed_by.swap_remove(row.index());
             (data, ComponentTicks { added, changed })
         })
     }
@@ -246,6 +265,8 @@ impl Column {
         let data = self.data.swap_remove_and_forget_unchecked(row.index());
         let added = self.added_ticks.swap_remove(row.index()).into_inner();
         let changed = self.changed_ticks.swap_remove(row.index()).into_inner();
+        #[cfg(feature = "track_change_detection")]
+        self.changed_by.swap_remove(row.index());
         (data, ComponentTicks { added, changed })
     }
 
@@ -274,6 +295,11 @@ impl Column {
             other.added_ticks.swap_remove(src_row.index());
         *self.changed_ticks.get_unchecked_mut(dst_row.index()) =
             other.changed_ticks.swap_remove(src_row.index());
+        #[cfg(feature = "track_change_detection")]
+        {
+            *self.changed_by.get_unchecked_mut(dst_row.index()) =
+                other.changed_by.swap_remove(src_row.index());
+        }
     }
 
     /// Pushes a new value onto the end of the [`Column`].
@@ -284,6 +310,8 @@ impl Column {
         self.data.push(ptr);
         self.added_ticks.push(UnsafeCell::new(ticks.added));
         self.changed_ticks.push(UnsafeCell::new(ticks.changed));
+        #[cfg(feature = "track_change_detection")]
+        self.changed_by.push(UnsafeCell::new(None));
     }
 
     #[inline]
@@ -291,6 +319,8 @@ impl Column {
         self.data.reserve_exact(additional);
         self.added_ticks.reserve_exact(additional);
         self.changed_ticks.reserve_exact(additional);
+        #[cfg(feature = "track_change_detection")]
+        self.changed_by.reserve_exact(additional);
     }
 
     /// Fetches the data pointer to the first element of the [`Column`].
@@ -341,6 +371,19 @@ impl Column {
         &self.changed_ticks
     }
 
+    /// Fetches the slice to the [`Column`]'s "changed by" locations.
+    ///
+    /// Note: The values stored within are [`UnsafeCell`].
+    /// Users of this API must ensure that accesses to each individual element
+    /// adhere to the safety invariants of [`UnsafeCell`].
+    ///
+    /// [`UnsafeCell`]: std::cell::UnsafeCell
+    #[cfg(feature = "track_change_detection")]
+    #[inline]
+    pub fn get_changed_by_slice(&self) -> &[UnsafeCell<Option<ChangedBy>>] {
+        &self.changed_by
+    }
+
     /// Fetches a reference to the data and change detection ticks at `row`.
     ///
     /// Returns `None` if `row` is out of bounds.
@@ -355,6 +398,8 @@ impl Column {
                     TickCells {
                         added: self.added_ticks.get_unchecked(row.index()),
                         changed: self.changed_ticks.get_unchecked(row.index()),
+                        #[cfg(feature = "track_change_detection")]
+                        changed_by: self.changed_by.get_unchecked(row.index()),
                     },
                 )
             })
@@ -467,6 +512,18 @@ impl Column {
         self.changed_ticks.get_unchecked(row.index())
     }
 
+    /// Fetches where the value at `row` was last changed. Unlike [`Column::get_changed_by_slice`]
+    /// this function does not do any bounds checking.
+    ///
+    /// # Safety
+    /// `row` must be within the range `[0, self.len())`.
+    #[cfg(feature = "track_change_detection")]
+    #[inline]
+    pub unsafe fn get_changed_by_unchecked(&self, row: TableRow) -> &UnsafeCell<Option<ChangedBy>> {
+        debug_assert!(row.index() < self.changed_by.len());
+        self.changed_by.get_unchecked(row.index())
+    }
+
     /// Fetches the change detection ticks for the value at `row`. Unlike [`Column::get_ticks`]
     /// this function does not do any bounds checking.
     ///
@@ -489,6 +546,42 @@ impl Column {
         self.data.clear();
         self.added_ticks.clear();
         self.changed_ticks.clear();
+        #[cfg(feature = "track_change_detection")]
+        self.changed_by.clear();
+    }
+
+    /// Shrinks the capacity of the column as much as possible, down to its length.
+    pub(crate) fn shrink_to_fit(&mut self) {
+        self.data.shrink_to_fit();
+        self.added_ticks.shrink_to_fit();
+        self.changed_ticks.shrink_to_fit();
+        #[cfg(feature = "track_change_detection")]
+        self.changed_by.shrink_to_fit();
+    }
+
+    /// Returns the number of bytes used by the values stored in the column and their change ticks.
+    pub fn used_bytes(&self) -> usize {
+        #[allow(unused_mut)]
+        let mut row_size = self.item_layout().size() + 2 * std::mem::size_of::<Tick>();
+        #[cfg(feature = "track_change_detection")]
+        {
+            row_size += std::mem::size_of::<Option<ChangedBy>>();
+        }
+        self.len() * row_size
+    }
+
+    /// Returns the number of bytes allocated by the column for its values and their change ticks,
+    /// including its unused capacity.
+    pub fn allocated_bytes(&self) -> usize {
+        #[allow(unused_mut)]
+        let mut bytes = self.data.capacity() * self.item_layout().size()
+            + (self.added_ticks.capacity() + self.changed_ticks.capacity())
+                * std::mem::size_of::<Tick>();
+        #[cfg(feature = "track_change_detection")]
+        {
+            bytes += self.changed_by.capacity() * std::mem::size_of::<Option<ChangedBy>>();
+        }
+        bytes
     }
 
     #[inline]
@@ -736,6 +829,8 @@ impl Table {
             column.data.set_len(self.entities.len());
             column.added_ticks.push(UnsafeCell::new(Tick::new(0)));
             column.changed_ticks.push(UnsafeCell::new(Tick::new(0)));
+            #[cfg(feature = "track_change_detection")]
+            column.changed_by.push(UnsafeCell::new(None));
         }
         TableRow::new(index)
     }
@@ -778,6 +873,11 @@ impl Table {
         self.columns.values()
     }
 
+    /// Iterates over the [`Column`]s of the [`Table`], along with the id of the component they store.
+    pub(crate) fn iter_with_ids(&self) -> impl Iterator<Item = (ComponentId, &Column)> {
+        self.columns.iter().map(|(id, column)| (*id, column))
+    }
+
     /// Clears all of the stored components in the [`Table`].
     pub(crate) fn clear(&mut self) {
         self.entities.clear();
@@ -785,6 +885,14 @@ impl Table {
             column.clear();
         }
     }
+
+    /// Shrinks the capacity of the table as much as possible, down to its number of entities.
+    pub(crate) fn shrink_to_fit(&mut self) {
+        self.entities.shrink_to_fit();
+        for column in self.columns.values_mut() {
+            column.shrink_to_fit();
+        }
+    }
 }
 
 /// A collection of [`Table`] storages, indexed by [`TableId`]
@@ -891,6 +999,46 @@ impl Tables {
             table.check_change_ticks(change_tick);
         }
     }
+
+    pub(crate) fn shrink_to_fit(&mut self) {
+        for table in &mut self.tables {
+            table.shrink_to_fit();
+        }
+    }
+
+    /// Removes the tables for which `is_used` returns `false`, except the [empty table](TableId::empty),
+    /// and gives consecutive ids to the remaining tables, in the same order.
+    ///
+    /// Returns the new id of each table, indexed by its old id.
+    pub(crate) fn remove_unused(
+        &mut self,
+        mut is_used: impl FnMut(TableId) -> bool,
+    ) -> Vec<Option<TableId>> {
+        let mut len = 0;
+        let new_ids: Vec<_> = (0..self.tables.len())
+            .map(|index| {
+                let id = TableId::new(index);
+                (id == TableId::empty() || is_used(id)).then(|| {
+                    len += 1;
+                    TableId::new(len - 1)
+                })
+            })
+            .collect();
+
+        let mut index = 0;
+        self.tables.retain(|_| {
+            index += 1;
+            new_ids[index - 1].is_some()
+        });
+        self.table_ids.retain(|_, id| match new_ids[id.index()] {
+            Some(new_id) => {
+                *id = new_id;
+                true
+            }
+            None => false,
+        });
+        new_ids
+    }
 }
 
 impl Index<TableId> for Tables {
@@ -0,0 +1,246 @@
+use bevy_utils::all_tuples;
+
+use crate::{
+    query::{DynamicQuery, QueryBuilder},
+    system::{
+        init_query_param, FunctionSystem, Query, SystemMeta, SystemParam, SystemParamFunction,
+    },
+    world::World,
+};
+
+/// A [`SystemParam`] whose state can be configured at runtime when building a system with a [`SystemBuilder`].
+pub trait BuildableSystemParam: SystemParam {
+    /// The builder used to configure this parameter.
+    type Builder<'b>;
+
+    /// Creates the state of this parameter after `func` has configured it,
+    /// registering its access with `meta` like [`SystemParam::init_state`] does.
+    fn build(
+        world: &mut World,
+        meta: &mut SystemMeta,
+        func: impl FnOnce(&mut Self::Builder<'_>),
+    ) -> Self::State;
+}
+
+impl BuildableSystemParam for Query<'_, '_, DynamicQuery> {
+    type Builder<'b> = QueryBuilder<'b>;
+
+    fn build(
+        world: &mut World,
+        meta: &mut SystemMeta,
+        func: impl FnOnce(&mut QueryBuilder<'_>),
+    ) -> Self::State {
+        let mut builder = QueryBuilder::new(world);
+        func(&mut builder);
+        let state = builder
+            .build()
+            .unwrap_or_else(|error| panic!("Invalid dynamic query in {}: {error}", meta.name()));
+        init_query_param(world, meta, &state);
+        state
+    }
+}
+
+/// Builds a system one parameter at a time, so that parameters like dynamic [`Query`]s can be
+/// configured at runtime.
+///
+/// The parameters must be added in the same order as they appear in the system function.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::query::DynamicQuery;
+/// # use bevy_ecs::system::SystemBuilder;
+/// #[derive(Component)]
+/// struct Health(u32);
+///
+/// #[derive(Resource)]
+/// struct Damage(u32);
+///
+/// let mut world = World::new();
+/// world.spawn(Health(10));
+/// world.insert_resource(Damage(3));
+/// let health = world.init_component::<Health>();
+///
+/// let system = SystemBuilder::new(&mut world)
+///     .builder::<Query<DynamicQuery>>(|query| {
+///         query.mut_id(health);
+///     })
+///     .param::<Res<Damage>>()
+///     .build(|mut query: Query<DynamicQuery>, damage: Res<Damage>| {
+///         for mut row in &mut query {
+///             let mut health = row.get_mut(0).unwrap();
+///             // SAFETY: the term was built from the id of `Health`.
+///             unsafe { health.as_mut().deref_mut::<Health>().0 -= damage.0 };
+///         }
+///     });
+///
+/// let mut schedule = Schedule::default();
+/// schedule.add_systems(system);
+/// schedule.run(&mut world);
+/// # let mut query = world.query::<&Health>();
+/// # assert_eq!(query.single(&world).0, 7);
+/// ```
+pub struct SystemBuilder<'w, T: SystemParam = ()> {
+    meta: SystemMeta,
+    state: T::State,
+    world: &'w mut World,
+}
+
+impl<'w> SystemBuilder<'w, ()> {
+    /// Creates a builder for a system without any parameters.
+    pub fn new(world: &'w mut World) -> Self {
+        Self {
+            meta: SystemMeta::new::<()>(),
+            state: (),
+            world,
+        }
+    }
+}
+
+impl<'w, T: SystemParam> SystemBuilder<'w, T> {
+    /// Creates a [`FunctionSystem`] running `func` with the parameters added so far.
+    ///
+    /// The system can only be added to the [`World`] the builder was created with.
+    pub fn build<F, Marker>(self, func: F) -> FunctionSystem<Marker, F>
+    where
+        F: SystemParamFunction<Marker, Param = T>,
+    {
+        FunctionSystem::from_builder(func, self.world.id(), self.meta, self.state)
+    }
+}
+
+macro_rules! impl_system_builder {
+    ($($curr: ident),*) => {
+        impl<'w, $($curr: SystemParam,)*> SystemBuilder<'w, ($($curr,)*)> {
+            /// Adds a parameter initialized like it would be for a regular system.
+            pub fn param<P: SystemParam>(mut self) -> SystemBuilder<'w, ($($curr,)* P,)> {
+                #[allow(non_snake_case)]
+                let ($($curr,)*) = self.state;
+                let state = P::init_state(self.world, &mut self.meta);
+                SystemBuilder {
+                    meta: self.meta,
+                    state: ($($curr,)* state,),
+                    world: self.world,
+                }
+            }
+
+            /// Adds a parameter configured by `func`.
+            pub fn builder<P: BuildableSystemParam>(
+                mut self,
+                func: impl FnOnce(&mut P::Builder<'_>),
+            ) -> SystemBuilder<'w, ($($curr,)* P,)> {
+                #[allow(non_snake_case)]
+                let ($($curr,)*) = self.state;
+                let state = P::build(self.world, &mut self.meta, func);
+                SystemBuilder {
+                    meta: self.meta,
+                    state: ($($curr,)* state,),
+                    world: self.world,
+                }
+            }
+        }
+    };
+}
+
+all_tuples!(impl_system_builder, 0, 15, P);
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        self as bevy_ecs,
+        prelude::*,
+        query::DynamicQuery,
+        system::{System, SystemBuilder},
+    };
+
+    #[derive(Component)]
+    struct A(usize);
+
+    #[derive(Component)]
+    struct B;
+
+    #[derive(Resource, Default)]
+    struct Count(usize);
+
+    #[test]
+    fn dynamic_query_system() {
+        let mut world = World::new();
+        world.spawn(A(1));
+        world.spawn((A(2), B));
+        world.init_resource::<Count>();
+        let a = world.init_component::<A>();
+        let b = world.init_component::<B>();
+
+        let system = SystemBuilder::new(&mut world)
+            .builder::<Query<DynamicQuery>>(|query| {
+                query.ref_id(a).without_id(b);
+            })
+            .param::<ResMut<Count>>()
+            .build(|query: Query<DynamicQuery>, mut count: ResMut<Count>| {
+                for row in &query {
+                    // SAFETY: the term was built from the id of `A`.
+                    count.0 += unsafe { row.get(0).unwrap().deref::<A>().0 };
+                }
+            });
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(system);
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<Count>().0, 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn dynamic_query_system_conflict() {
+        let mut world = World::new();
+        let a = world.init_component::<A>();
+
+        let _ = SystemBuilder::new(&mut world)
+            .builder::<Query<DynamicQuery>>(|query| {
+                query.mut_id(a);
+            })
+            .param::<Query<&A>>();
+    }
+
+    #[test]
+    fn dynamic_query_system_disjoint() {
+        let mut world = World::new();
+        let a = world.init_component::<A>();
+        let b = world.init_component::<B>();
+
+        // Filters make the two queries disjoint, just like `Query<&mut A, With<B>>` and `Query<&mut A, Without<B>>`.
+        let _ = SystemBuilder::new(&mut world)
+            .builder::<Query<DynamicQuery>>(|query| {
+                query.mut_id(a).with_id(b);
+            })
+            .builder::<Query<DynamicQuery>>(|query| {
+                query.mut_id(a).without_id(b);
+            });
+    }
+
+    #[test]
+    fn dynamic_query_system_access() {
+        let mut world = World::new();
+        let a = world.init_component::<A>();
+
+        let writer = SystemBuilder::new(&mut world)
+            .builder::<Query<DynamicQuery>>(|query| {
+                query.mut_id(a);
+            })
+            .build(|_: Query<DynamicQuery>| {});
+        let reader = SystemBuilder::new(&mut world)
+            .builder::<Query<DynamicQuery>>(|query| {
+                query.ref_id(a);
+            })
+            .build(|_: Query<DynamicQuery>| {});
+        let other_reader = SystemBuilder::new(&mut world)
+            .param::<Query<&A>>()
+            .build(|_: Query<&A>| {});
+
+        assert!(!writer
+            .component_access()
+            .is_compatible(reader.component_access()));
+        assert!(reader
+            .component_access()
+            .is_compatible(other_reader.component_access()));
+    }
+}
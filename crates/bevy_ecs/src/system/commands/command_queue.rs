@@ -90,6 +90,12 @@ impl CommandQueue {
         }
     }
 
+    /// Returns `true` if there are no queued commands.
+    #[inline]
+    pub(crate) fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
     /// Execute the queued [`Command`]s in the world.
     /// This clears the queue.
     #[inline]
@@ -4,7 +4,8 @@ mod parallel_scope;
 use crate::{
     self as bevy_ecs,
     bundle::Bundle,
-    entity::{Entities, Entity},
+    entity::{Entities, Entity, EntityCloner, EntityMap},
+    relation::RelationKind,
     world::{FromWorld, World},
 };
 use bevy_ecs_macros::SystemParam;
@@ -13,7 +14,7 @@ pub use command_queue::CommandQueue;
 pub use parallel_scope::*;
 use std::marker::PhantomData;
 
-use super::{Deferred, Resource, SystemBuffer, SystemMeta};
+use super::{Deferred, Resource, RunSystem, SystemBuffer, SystemId, SystemMeta};
 
 /// A [`World`] mutation.
 ///
@@ -519,6 +520,35 @@ impl<'w, 's> Commands<'w, 's> {
         self.queue.push(RemoveResource::<R>::new());
     }
 
+    /// Pushes a [`Command`] to the queue for running a system registered with [`World::register_system`].
+    ///
+    /// The system runs with exclusive access to the world when the command is applied,
+    /// and its own deferred buffers are applied right after.
+    ///
+    /// See [`World::run_system`] for more details.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_ecs::system::SystemId;
+    /// #
+    /// #[derive(Component)]
+    /// struct OnClick(SystemId);
+    ///
+    /// # #[derive(Component)]
+    /// # struct Clicked;
+    /// fn handle_clicks(mut commands: Commands, buttons: Query<&OnClick, With<Clicked>>) {
+    ///     for on_click in &buttons {
+    ///         commands.run_system(on_click.0);
+    ///     }
+    /// }
+    /// # bevy_ecs::system::assert_is_system(handle_clicks);
+    /// ```
+    pub fn run_system(&mut self, id: SystemId) {
+        self.queue.push(RunSystem { system_id: id });
+    }
+
     /// Pushes a generic [`Command`] to the command queue.
     ///
     /// `command` can be a built-in command, custom struct that implements [`Command`] or a closure
@@ -768,6 +798,84 @@ impl<'w, 's, 'a> EntityCommands<'w, 's, 'a> {
         self
     }
 
+    /// Relates this entity to `target` with a [relation](crate::relation) of kind `R`.
+    ///
+    /// See [`World::relate`] for more details.
+    ///
+    /// # Panics
+    ///
+    /// The command will panic when applied if either entity does not exist.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// use bevy_ecs::relation::RelationKind;
+    ///
+    /// struct Targeting;
+    /// impl RelationKind for Targeting {}
+    ///
+    /// # #[derive(Component)]
+    /// # struct Enemy;
+    /// #
+    /// fn acquire_target(mut commands: Commands, player: Query<Entity, Without<Enemy>>, enemy: Query<Entity, With<Enemy>>) {
+    ///     commands.entity(player.single()).relate::<Targeting>(enemy.single());
+    /// }
+    /// # bevy_ecs::system::assert_is_system(acquire_target);
+    /// ```
+    pub fn relate<R: RelationKind>(&mut self, target: Entity) -> &mut Self {
+        self.commands.add(Relate::<R>::new(self.entity, target));
+        self
+    }
+
+    /// Removes the [relation](crate::relation) of kind `R` from this entity to `target`, if it exists.
+    ///
+    /// See [`World::unrelate`] for more details.
+    pub fn unrelate<R: RelationKind>(&mut self, target: Entity) -> &mut Self {
+        self.commands.add(Unrelate::<R>::new(self.entity, target));
+        self
+    }
+
+    /// Spawns a clone of this entity and returns the [`EntityCommands`] of the clone.
+    ///
+    /// See [`World::clone_entity`] for more details.
+    ///
+    /// # Panics
+    ///
+    /// The command will panic when applied if the associated entity does not exist.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #
+    /// # #[derive(Component, Clone)]
+    /// # struct Enemy;
+    /// #
+    /// # #[derive(Resource)]
+    /// # struct Boss(Entity);
+    /// #
+    /// fn split_boss(mut commands: Commands, boss: Res<Boss>) {
+    ///     commands.entity(boss.0).clone_and_spawn().insert(Enemy);
+    /// }
+    /// # bevy_ecs::system::assert_is_system(split_boss);
+    /// ```
+    pub fn clone_and_spawn(&mut self) -> EntityCommands<'w, 's, '_> {
+        self.clone_and_spawn_with(EntityCloner::default())
+    }
+
+    /// Spawns a clone of this entity using the given [`EntityCloner`], and returns the [`EntityCommands`] of the clone.
+    ///
+    /// # Panics
+    ///
+    /// The command will panic when applied if the associated entity does not exist.
+    pub fn clone_and_spawn_with(&mut self, cloner: EntityCloner) -> EntityCommands<'w, 's, '_> {
+        let source = self.entity;
+        let mut clone = self.commands.spawn_empty();
+        clone.add(CloneEntity { source, cloner });
+        clone
+    }
+
     /// Despawns the entity.
     ///
     /// See [`World::despawn`] for more details.
@@ -938,6 +1046,32 @@ impl Command for Despawn {
     }
 }
 
+/// An [`EntityCommand`] that clones the components of `source` into the entity it is applied to.
+/// See [`EntityCommands::clone_and_spawn`].
+#[derive(Debug)]
+pub struct CloneEntity {
+    /// The entity to clone.
+    pub source: Entity,
+    /// Chooses which components are cloned.
+    pub cloner: EntityCloner,
+}
+
+impl EntityCommand for CloneEntity {
+    fn apply(self, id: Entity, world: &mut World) {
+        if world.get_entity(self.source).is_none() {
+            // Don't leave the clone spawned by `clone_and_spawn` behind.
+            world.despawn(id);
+            panic!(
+                "Could not clone entity {:?} because it doesn't exist in this World.",
+                self.source
+            );
+        }
+        let mut map = EntityMap::default();
+        map.insert(self.source, id);
+        self.cloner.clone_mapped(world, &mut map);
+    }
+}
+
 /// A [`Command`] that adds the components in a [`Bundle`] to an entity.
 pub struct Insert<T> {
     /// The entity to which the components will be added.
@@ -990,6 +1124,60 @@ impl<T> Remove<T> {
     }
 }
 
+/// A [`Command`] that relates an entity to another with a [relation](crate::relation) of kind `R`.
+#[derive(Debug)]
+pub struct Relate<R> {
+    /// The source of the relation.
+    pub source: Entity,
+    /// The target of the relation.
+    pub target: Entity,
+    _marker: PhantomData<R>,
+}
+
+impl<R: RelationKind> Command for Relate<R> {
+    fn apply(self, world: &mut World) {
+        world.relate::<R>(self.source, self.target);
+    }
+}
+
+impl<R> Relate<R> {
+    /// Creates a [`Command`] which will relate `source` to `target` when applied.
+    pub const fn new(source: Entity, target: Entity) -> Self {
+        Self {
+            source,
+            target,
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// A [`Command`] that removes a [relation](crate::relation) of kind `R` between two entities.
+#[derive(Debug)]
+pub struct Unrelate<R> {
+    /// The source of the relation.
+    pub source: Entity,
+    /// The target of the relation.
+    pub target: Entity,
+    _marker: PhantomData<R>,
+}
+
+impl<R: RelationKind> Command for Unrelate<R> {
+    fn apply(self, world: &mut World) {
+        world.unrelate::<R>(self.source, self.target);
+    }
+}
+
+impl<R> Unrelate<R> {
+    /// Creates a [`Command`] which will unrelate `source` from `target` when applied.
+    pub const fn new(source: Entity, target: Entity) -> Self {
+        Self {
+            source,
+            target,
+            _marker: PhantomData,
+        }
+    }
+}
+
 /// A [`Command`] that inserts a [`Resource`] into the world using a value
 /// created with the [`FromWorld`] trait.
 pub struct InitResource<R: Resource + FromWorld> {
@@ -1092,7 +1280,7 @@ mod tests {
         }
     }
 
-    #[derive(Component, Resource)]
+    #[derive(Component, Resource, Clone)]
     struct W<T>(T);
 
     fn simple_command(world: &mut World) {
@@ -1196,6 +1384,26 @@ mod tests {
         assert_eq!(results_after_u64, vec![]);
     }
 
+    #[test]
+    fn clone_and_spawn() {
+        let mut world = World::default();
+        world.register_component_clone::<W<u32>>();
+        let source = world.spawn((W(1u32), W(2u64))).id();
+
+        let mut queue = CommandQueue::default();
+        let clone = Commands::new(&mut queue, &world)
+            .entity(source)
+            .clone_and_spawn()
+            .insert(W(3u64))
+            .id();
+        queue.apply(&mut world);
+
+        assert_ne!(clone, source);
+        assert_eq!(world.get::<W<u32>>(clone).unwrap().0, 1);
+        assert_eq!(world.get::<W<u64>>(clone).unwrap().0, 3);
+        assert_eq!(world.get::<W<u64>>(source).unwrap().0, 2);
+    }
+
     #[test]
     fn remove_resources() {
         let mut world = World::default();
@@ -100,6 +100,9 @@ where
             self.param_state.as_mut().expect(PARAM_MESSAGE),
             &self.system_meta,
         );
+        #[cfg(feature = "track_change_detection")]
+        let _system =
+            crate::change_detection::ChangedBy::enter_system(self.system_meta.name.clone());
         let out = self.func.run(world, input, params);
 
         let change_tick = world.change_tick.get_mut();
@@ -0,0 +1,441 @@
+use std::{any::TypeId, borrow::Cow, error::Error};
+
+use crate::{
+    self as bevy_ecs,
+    archetype::ArchetypeComponentId,
+    change_detection::DetectChangesMut,
+    component::{ComponentId, Tick},
+    event::Event,
+    query::Access,
+    system::{Resource, System},
+    world::{unsafe_world_cell::UnsafeWorldCell, World},
+};
+
+/// An error returned by a fallible system.
+///
+/// Any error type implementing [`Error`], as well as [`String`] and `&str`, converts into it,
+/// so systems can use `?` on most fallible operations.
+pub type SystemError = Box<dyn Error + Send + Sync + 'static>;
+
+/// A function called with each error returned by a fallible system.
+///
+/// See [`error_handlers`] for the built-in handlers.
+pub type ErrorHandler = fn(SystemError, ErrorContext<'_>);
+
+/// The context in which a fallible system returned an error, passed to the [`ErrorHandler`].
+pub struct ErrorContext<'w> {
+    /// The name of the system that returned the error.
+    pub system: Cow<'static, str>,
+    /// The world the system runs in.
+    pub world: &'w mut World,
+    disabled: &'w mut bool,
+}
+
+impl<'w> ErrorContext<'w> {
+    /// Stops running the system that returned the error. It stays in its schedule,
+    /// but does nothing when it runs.
+    pub fn disable_system(&mut self) {
+        *self.disabled = true;
+    }
+}
+
+/// The [`ErrorHandler`] used for the errors of fallible systems, unless their
+/// [`Schedule`](crate::schedule::Schedule) has its own.
+///
+/// If this resource doesn't exist, errors are handled by [`error_handlers::panic`].
+#[derive(Resource, Clone, Copy)]
+pub struct DefaultErrorHandler(pub ErrorHandler);
+
+impl Default for DefaultErrorHandler {
+    fn default() -> Self {
+        Self(error_handlers::panic)
+    }
+}
+
+/// The [`ErrorHandler`] of the schedule that is currently running, if it has one.
+///
+/// The resource is kept once inserted, so that running a schedule doesn't move resources around.
+#[derive(Resource, Default, Clone, Copy)]
+pub(crate) struct ScheduleErrorHandler(pub(crate) Option<ErrorHandler>);
+
+impl ScheduleErrorHandler {
+    /// Makes `handler` the handler of the running schedule, and returns the handler it replaced.
+    pub(crate) fn enter(world: &mut World, handler: Option<ErrorHandler>) -> Option<ErrorHandler> {
+        let mut current = world.get_resource_or_insert_with(Self::default);
+        std::mem::replace(&mut current.bypass_change_detection().0, handler)
+    }
+
+    /// Restores the handler returned by [`ScheduleErrorHandler::enter`].
+    pub(crate) fn exit(world: &mut World, outer: Option<ErrorHandler>) {
+        if let Some(mut current) = world.get_resource_mut::<Self>() {
+            current.bypass_change_detection().0 = outer;
+        }
+    }
+}
+
+/// An [`Event`] sent by [`error_handlers::send_event`] for each error returned by a fallible system.
+///
+/// The event must be added to the app with `add_event`.
+#[derive(Event, Debug)]
+pub struct SystemErrorEvent {
+    /// The name of the system that returned the error.
+    pub system: Cow<'static, str>,
+    /// The error returned by the system.
+    pub error: SystemError,
+}
+
+/// Built-in [`ErrorHandler`]s.
+pub mod error_handlers {
+    use bevy_utils::tracing::error;
+
+    use super::{ErrorContext, SystemError, SystemErrorEvent};
+
+    /// Panics with the error. This is the default handler.
+    pub fn panic(error: SystemError, context: ErrorContext) {
+        panic!(
+            "Encountered an error in system `{}`: {error}",
+            context.system
+        );
+    }
+
+    /// Logs the error, and keeps running the system.
+    pub fn log(error: SystemError, context: ErrorContext) {
+        error!(
+            "Encountered an error in system `{}`: {error}",
+            context.system
+        );
+    }
+
+    /// Sends the error as a [`SystemErrorEvent`], and keeps running the system.
+    pub fn send_event(error: SystemError, context: ErrorContext) {
+        context.world.send_event(SystemErrorEvent {
+            system: context.system,
+            error,
+        });
+    }
+
+    /// Logs the error, and disables the system so that it doesn't run again.
+    pub fn disable(error: SystemError, mut context: ErrorContext) {
+        error!(
+            "Encountered an error in system `{}`, disabling it: {error}",
+            context.system
+        );
+        context.disable_system();
+    }
+}
+
+/// A [`System`] that runs a system returning a [`Result`], and passes its errors to an [`ErrorHandler`].
+///
+/// Systems returning `Result<(), E>` are wrapped in this adapter when added to a
+/// [`Schedule`](crate::schedule::Schedule). The errors are handled with the handler set by
+/// [`Schedule::set_error_handler`](crate::schedule::Schedule::set_error_handler), or else the
+/// [`DefaultErrorHandler`] resource.
+///
+/// Errors are handled as soon as the system has run when it is run with exclusive world access,
+/// and with its [deferred](System::apply_deferred) buffers otherwise. Errors are still handled
+/// at the end of the schedule run when the schedule doesn't apply the final deferred buffers.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::system::{error_handlers, DefaultErrorHandler, SystemError};
+/// #[derive(Component)]
+/// struct Player;
+///
+/// fn find_player(query: Query<Entity, With<Player>>) -> Result<(), SystemError> {
+///     let player = query.get_single()?;
+///     println!("Found {player:?}");
+///     Ok(())
+/// }
+///
+/// let mut world = World::new();
+/// world.insert_resource(DefaultErrorHandler(error_handlers::log));
+///
+/// let mut schedule = Schedule::new();
+/// schedule.add_systems(find_player);
+/// // There is no player: the error is logged.
+/// schedule.run(&mut world);
+/// ```
+pub struct FallibleSystem<S> {
+    system: S,
+    errors: Vec<SystemError>,
+    disabled: bool,
+}
+
+impl<S> FallibleSystem<S> {
+    /// Creates a system passing the errors of `system` to an [`ErrorHandler`].
+    pub fn new(system: S) -> Self {
+        Self {
+            system,
+            errors: Vec::new(),
+            disabled: false,
+        }
+    }
+
+    /// Returns `true` if the system was disabled by its [`ErrorHandler`].
+    pub fn is_disabled(&self) -> bool {
+        self.disabled
+    }
+
+    fn handle_errors(&mut self, name: Cow<'static, str>, world: &mut World) {
+        if self.errors.is_empty() {
+            return;
+        }
+        let handler = world
+            .get_resource::<ScheduleErrorHandler>()
+            .and_then(|handler| handler.0)
+            .or_else(|| {
+                world
+                    .get_resource::<DefaultErrorHandler>()
+                    .map(|handler| handler.0)
+            })
+            .unwrap_or(error_handlers::panic);
+        for error in std::mem::take(&mut self.errors) {
+            handler(
+                error,
+                ErrorContext {
+                    system: name.clone(),
+                    world,
+                    disabled: &mut self.disabled,
+                },
+            );
+        }
+    }
+}
+
+impl<S, E> System for FallibleSystem<S>
+where
+    S: System<In = (), Out = Result<(), E>>,
+    E: Into<SystemError> + 'static,
+{
+    type In = ();
+    type Out = ();
+
+    fn name(&self) -> Cow<'static, str> {
+        self.system.name()
+    }
+
+    fn type_id(&self) -> TypeId {
+        self.system.type_id()
+    }
+
+    fn component_access(&self) -> &Access<ComponentId> {
+        self.system.component_access()
+    }
+
+    fn archetype_component_access(&self) -> &Access<ArchetypeComponentId> {
+        self.system.archetype_component_access()
+    }
+
+    fn is_send(&self) -> bool {
+        self.system.is_send()
+    }
+
+    fn is_exclusive(&self) -> bool {
+        self.system.is_exclusive()
+    }
+
+    unsafe fn run_unsafe(&mut self, input: Self::In, world: UnsafeWorldCell) {
+        if self.disabled {
+            return;
+        }
+        // SAFETY: the caller upholds the safety requirements of the inner system,
+        // which has the same access as this one.
+        if let Err(error) = unsafe { self.system.run_unsafe(input, world) } {
+            self.errors.push(error.into());
+        }
+    }
+
+    fn run(&mut self, input: Self::In, world: &mut World) {
+        if self.disabled {
+            return;
+        }
+        if let Err(error) = self.system.run(input, world) {
+            self.errors.push(error.into());
+            self.handle_errors(self.system.name(), world);
+        }
+    }
+
+    fn apply_deferred(&mut self, world: &mut World) {
+        self.system.apply_deferred(world);
+        self.handle_errors(self.system.name(), world);
+    }
+
+    fn report_errors(&mut self, world: &mut World) {
+        self.handle_errors(self.system.name(), world);
+    }
+
+    fn initialize(&mut self, world: &mut World) {
+        self.system.initialize(world);
+    }
+
+    fn update_archetype_component_access(&mut self, world: UnsafeWorldCell) {
+        self.system.update_archetype_component_access(world);
+    }
+
+    fn check_change_tick(&mut self, change_tick: Tick) {
+        self.system.check_change_tick(change_tick);
+    }
+
+    fn default_system_sets(&self) -> Vec<Box<dyn crate::schedule::SystemSet>> {
+        self.system.default_system_sets()
+    }
+
+    fn get_last_run(&self) -> Tick {
+        self.system.get_last_run()
+    }
+
+    fn set_last_run(&mut self, last_run: Tick) {
+        self.system.set_last_run(last_run);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        self as bevy_ecs,
+        event::Events,
+        prelude::*,
+        schedule::ExecutorKind,
+        system::{error_handlers, DefaultErrorHandler, SystemError, SystemErrorEvent},
+    };
+
+    #[derive(Resource, Default)]
+    struct Runs(usize);
+
+    #[derive(Resource, Default)]
+    struct Handled(usize);
+
+    fn failing(mut runs: ResMut<Runs>) -> Result<(), SystemError> {
+        runs.0 += 1;
+        Err("failed".into())
+    }
+
+    fn failing_exclusive(world: &mut World) -> Result<(), String> {
+        world.resource_mut::<Runs>().0 += 1;
+        Err("failed".to_string())
+    }
+
+    fn succeeding(mut runs: ResMut<Runs>) -> Result<(), SystemError> {
+        runs.0 += 1;
+        Ok(())
+    }
+
+    fn count_handled(_: SystemError, context: crate::system::ErrorContext) {
+        context.world.resource_mut::<Handled>().0 += 1;
+    }
+
+    fn world() -> World {
+        let mut world = World::new();
+        world.init_resource::<Runs>();
+        world.init_resource::<Handled>();
+        world
+    }
+
+    #[test]
+    fn handle_errors() {
+        for kind in [
+            ExecutorKind::Simple,
+            ExecutorKind::SingleThreaded,
+            ExecutorKind::MultiThreaded,
+        ] {
+            let mut world = world();
+            world.insert_resource(DefaultErrorHandler(count_handled));
+            let mut schedule = Schedule::new();
+            schedule.set_executor_kind(kind);
+            schedule.add_systems((failing, failing_exclusive, succeeding).chain());
+
+            schedule.run(&mut world);
+            schedule.run(&mut world);
+
+            assert_eq!(world.resource::<Runs>().0, 6);
+            assert_eq!(world.resource::<Handled>().0, 4);
+        }
+    }
+
+    #[test]
+    fn schedule_error_handler() {
+        let mut world = world();
+        world.insert_resource(DefaultErrorHandler(error_handlers::panic));
+        let mut schedule = Schedule::new();
+        schedule.set_error_handler(count_handled);
+        schedule.add_systems(failing);
+
+        schedule.run(&mut world);
+
+        assert_eq!(world.resource::<Handled>().0, 1);
+        assert!(world.resource::<super::ScheduleErrorHandler>().0.is_none());
+    }
+
+    #[test]
+    fn report_errors_without_final_deferred() {
+        for kind in [ExecutorKind::SingleThreaded, ExecutorKind::MultiThreaded] {
+            let mut world = world();
+            world.insert_resource(DefaultErrorHandler(error_handlers::panic));
+            let mut schedule = Schedule::new();
+            schedule.set_executor_kind(kind);
+            schedule.set_apply_final_deferred(false);
+            schedule.set_error_handler(count_handled);
+            schedule.add_systems(failing);
+
+            schedule.run(&mut world);
+
+            // the error is handled by this run, with the handler of the schedule
+            assert_eq!(world.resource::<Handled>().0, 1);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "failed")]
+    fn panic_by_default() {
+        let mut world = world();
+        let mut schedule = Schedule::new();
+        schedule.add_systems(failing);
+
+        schedule.run(&mut world);
+    }
+
+    #[test]
+    fn disable_system() {
+        let mut world = world();
+        world.insert_resource(DefaultErrorHandler(error_handlers::disable));
+        let mut schedule = Schedule::new();
+        schedule.add_systems((failing, failing_exclusive));
+
+        schedule.run(&mut world);
+        schedule.run(&mut world);
+
+        assert_eq!(world.resource::<Runs>().0, 2);
+    }
+
+    #[test]
+    fn send_event() {
+        let mut world = world();
+        world.init_resource::<Events<SystemErrorEvent>>();
+        world.insert_resource(DefaultErrorHandler(error_handlers::send_event));
+        let mut schedule = Schedule::new();
+        schedule.add_systems(failing);
+
+        schedule.run(&mut world);
+
+        let events = world.resource::<Events<SystemErrorEvent>>();
+        let event = events.iter_current_update_events().next().unwrap();
+        assert!(event.system.ends_with("failing"));
+        assert_eq!(event.error.to_string(), "failed");
+    }
+
+    #[test]
+    fn order_fallible_systems() {
+        let mut world = world();
+        world.insert_resource(DefaultErrorHandler(error_handlers::log));
+        let mut schedule = Schedule::new();
+        schedule.add_systems((
+            succeeding,
+            failing.after(succeeding).before(failing_exclusive),
+            failing_exclusive,
+        ));
+
+        schedule.run(&mut world);
+
+        assert_eq!(world.resource::<Runs>().0, 3);
+    }
+}
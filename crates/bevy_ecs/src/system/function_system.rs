@@ -246,9 +246,11 @@ impl<Param: SystemParam> SystemState<Param> {
         let archetypes = world.archetypes();
         let new_generation = archetypes.generation();
         let old_generation = std::mem::replace(&mut self.archetype_generation, new_generation);
-        let archetype_index_range = old_generation.value()..new_generation.value();
+        if new_generation.is_reclaimed_since(old_generation) {
+            Param::forget_archetypes(&mut self.param_state);
+        }
 
-        for archetype_index in archetype_index_range {
+        for archetype_index in new_generation.archetypes_since(old_generation) {
             Param::new_archetype(
                 &mut self.param_state,
                 &archetypes[ArchetypeId::new(archetype_index)],
@@ -262,7 +264,8 @@ impl<Param: SystemParam> SystemState<Param> {
     /// world's change tick.
     ///
     /// For this to return accurate results, ensure [`SystemState::update_archetypes`] is called before this
-    /// function.
+    /// function. The archetypes are only matched again if empty archetypes were reclaimed by
+    /// [`World::reclaim_empty_archetypes`] since they were last updated.
     ///
     /// Users should strongly prefer to use [`SystemState::get`] over this function.
     #[inline]
@@ -281,7 +284,8 @@ impl<Param: SystemParam> SystemState<Param> {
     /// automatically nor increment the world's change tick.
     ///
     /// For this to return accurate results, ensure [`SystemState::update_archetypes`] is called before this
-    /// function.
+    /// function. The archetypes are only matched again if empty archetypes were reclaimed by
+    /// [`World::reclaim_empty_archetypes`] since they were last updated.
     ///
     /// Users should strongly prefer to use [`SystemState::get_mut`] over this function.
     #[inline]
@@ -320,6 +324,15 @@ impl<Param: SystemParam> SystemState<Param> {
         world: UnsafeWorldCell<'w>,
         change_tick: Tick,
     ) -> SystemParamItem<'w, 's, Param> {
+        // the ids of the archetypes matched by the parameters changed if empty archetypes were
+        // reclaimed, even if the archetypes aren't updated manually
+        if world
+            .archetypes()
+            .generation()
+            .is_reclaimed_since(self.archetype_generation)
+        {
+            self.update_archetypes_unsafe_world_cell(world);
+        }
         let param = Param::get_param(&mut self.param_state, &self.meta, world, change_tick);
         self.meta.last_run = change_tick;
         param
@@ -350,6 +363,9 @@ where
     param_state: Option<<F::Param as SystemParam>::State>,
     system_meta: SystemMeta,
     world_id: Option<WorldId>,
+    // Systems created by a `SystemBuilder` have a parameter state built for a given world, which
+    // can't be initialized again.
+    built: bool,
     archetype_generation: ArchetypeGeneration,
     // NOTE: PhantomData<fn()-> T> gives this safe Send/Sync impls
     marker: PhantomData<fn() -> Marker>,
@@ -366,6 +382,7 @@ where
             param_state: None,
             system_meta: SystemMeta::new::<F>(),
             world_id: None,
+            built: false,
             archetype_generation: ArchetypeGeneration::initial(),
             marker: PhantomData,
         }
@@ -388,6 +405,7 @@ where
             param_state: None,
             system_meta: SystemMeta::new::<F>(),
             world_id: None,
+            built: false,
             archetype_generation: ArchetypeGeneration::initial(),
             marker: PhantomData,
         }
@@ -402,6 +420,25 @@ where
     // When lines get too long, rustfmt can sometimes refuse to format them.
     // Work around this by storing the message separately.
     const PARAM_MESSAGE: &'static str = "System's param_state was not found. Did you forget to initialize this system before running it?";
+
+    /// Creates a system from parameter state that was already initialized by a [`SystemBuilder`](super::SystemBuilder).
+    pub(crate) fn from_builder(
+        func: F,
+        world_id: WorldId,
+        mut system_meta: SystemMeta,
+        param_state: <F::Param as SystemParam>::State,
+    ) -> Self {
+        system_meta.name = std::any::type_name::<F>().into();
+        Self {
+            func,
+            param_state: Some(param_state),
+            system_meta,
+            world_id: Some(world_id),
+            built: true,
+            archetype_generation: ArchetypeGeneration::initial(),
+            marker: PhantomData,
+        }
+    }
 }
 
 impl<Marker, F> System for FunctionSystem<Marker, F>
@@ -457,6 +494,9 @@ where
             world,
             change_tick,
         );
+        #[cfg(feature = "track_change_detection")]
+        let _system =
+            crate::change_detection::ChangedBy::enter_system(self.system_meta.name.clone());
         let out = self.func.run(input, params);
         self.system_meta.last_run = change_tick;
         out
@@ -478,6 +518,15 @@ where
 
     #[inline]
     fn initialize(&mut self, world: &mut World) {
+        if self.built {
+            assert_eq!(
+                self.world_id,
+                Some(world.id()),
+                "System built with a different world than the one it was added to.",
+            );
+            self.system_meta.last_run = world.change_tick().relative_to(Tick::MAX);
+            return;
+        }
         self.world_id = Some(world.id());
         self.system_meta.last_run = world.change_tick().relative_to(Tick::MAX);
         self.param_state = Some(F::Param::init_state(world, &mut self.system_meta));
@@ -488,9 +537,11 @@ where
         let archetypes = world.archetypes();
         let new_generation = archetypes.generation();
         let old_generation = std::mem::replace(&mut self.archetype_generation, new_generation);
-        let archetype_index_range = old_generation.value()..new_generation.value();
+        if new_generation.is_reclaimed_since(old_generation) {
+            F::Param::forget_archetypes(self.param_state.as_mut().unwrap());
+        }
 
-        for archetype_index in archetype_index_range {
+        for archetype_index in new_generation.archetypes_since(old_generation) {
             let param_state = self.param_state.as_mut().unwrap();
             F::Param::new_archetype(
                 param_state,
@@ -102,26 +102,32 @@
 //! - All tuples between 1 to 16 elements where each element implements [`SystemParam`]
 //! - [`()` (unit primitive type)](https://doc.rust-lang.org/stable/std/primitive.unit.html)
 
+mod builder;
 mod combinator;
 mod commands;
 mod exclusive_function_system;
 mod exclusive_system_param;
+mod fallible_system;
 mod function_system;
 mod query;
 #[allow(clippy::module_inception)]
 mod system;
 mod system_param;
+mod system_registry;
 
 use std::borrow::Cow;
 
+pub use builder::*;
 pub use combinator::*;
 pub use commands::*;
 pub use exclusive_function_system::*;
 pub use exclusive_system_param::*;
+pub use fallible_system::*;
 pub use function_system::*;
 pub use query::*;
 pub use system::*;
 pub use system_param::*;
+pub use system_registry::*;
 
 use crate::world::World;
 
@@ -1265,6 +1271,99 @@ mod tests {
         with_filter.run((), &mut world);
     }
 
+    #[test]
+    fn reinitialize_system_in_another_world() {
+        fn count(query: Query<&W<usize>>) -> usize {
+            query.iter().count()
+        }
+
+        let mut system = IntoSystem::into_system(count);
+        let mut world = World::default();
+        world.spawn(W(0usize));
+        system.initialize(&mut world);
+        assert_eq!(system.run((), &mut world), 1);
+
+        let mut other_world = World::default();
+        other_world.spawn_batch([W(0usize), W(1usize)]);
+        system.initialize(&mut other_world);
+        assert_eq!(system.run((), &mut other_world), 2);
+    }
+
+    #[test]
+    fn query_join() {
+        fn join_system(mut positions: Query<&mut W<usize>>, mut markers: Query<Entity, With<A>>) {
+            let mut joined = positions.join(&mut markers);
+            for (mut w, _) in &mut joined.query() {
+                w.0 += 1;
+            }
+        }
+
+        let mut world = World::default();
+        let marked = world.spawn((A, W(0usize))).id();
+        let unmarked = world.spawn(W(0usize)).id();
+        world.spawn(A);
+
+        run_system(&mut world, join_system);
+
+        assert_eq!(world.get::<W<usize>>(marked).unwrap().0, 1);
+        assert_eq!(world.get::<W<usize>>(unmarked).unwrap().0, 0);
+    }
+
+    #[test]
+    fn query_join_cached_across_runs() {
+        fn join_system(mut positions: Query<&mut W<usize>>, mut markers: Query<Entity, With<A>>) {
+            let mut joined = positions.join(&mut markers);
+            for (mut w, _) in &mut joined.query() {
+                w.0 += 1;
+            }
+        }
+
+        let mut world = World::default();
+        let marked = world.spawn((A, W(0usize))).id();
+        let mut system = IntoSystem::into_system(join_system);
+        system.initialize(&mut world);
+        system.run((), &mut world);
+
+        // the cached joined state picks up the archetypes created since the last run
+        let late = world.spawn((A, B, W(0usize))).id();
+        world.spawn((B, W(0usize)));
+        system.run((), &mut world);
+
+        assert_eq!(world.get::<W<usize>>(marked).unwrap().0, 2);
+        assert_eq!(world.get::<W<usize>>(late).unwrap().0, 1);
+    }
+
+    #[test]
+    fn query_iter_many_join() {
+        #[derive(Resource)]
+        struct Targets(Vec<Entity>);
+
+        fn join_system(
+            targets: Res<Targets>,
+            positions: Query<&W<usize>, With<A>>,
+            entities: Query<Entity, Without<B>>,
+            mut found: Local<Vec<(usize, Entity)>>,
+        ) {
+            found.extend(
+                positions
+                    .iter_many_join(&entities, &targets.0)
+                    .map(|(w, entity)| (w.0, entity)),
+            );
+            assert_eq!(found.len(), 2);
+        }
+
+        let mut world = World::default();
+        let a = world.spawn((A, W(1usize))).id();
+        let ab = world.spawn((A, B, W(2usize))).id();
+        let no_a = world.spawn(W(3usize)).id();
+        let c = world.spawn((A, W(4usize))).id();
+        world.insert_resource(Targets(vec![c, ab, no_a, a, Entity::PLACEHOLDER]));
+
+        let mut system = IntoSystem::into_system(join_system);
+        system.initialize(&mut world);
+        system.run((), &mut world);
+    }
+
     #[test]
     #[allow(clippy::too_many_arguments)]
     fn can_have_16_parameters() {
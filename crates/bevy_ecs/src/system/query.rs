@@ -1,9 +1,10 @@
 use crate::{
-    component::{Component, Tick},
+    component::{Component, ComponentId, Tick},
     entity::Entity,
     query::{
-        BatchingStrategy, QueryCombinationIter, QueryEntityError, QueryIter, QueryManyIter,
-        QueryParIter, QuerySingleError, QueryState, ROQueryItem, ReadOnlyWorldQuery, WorldQuery,
+        BatchingStrategy, FilteredAccess, JoinCache, QueryCombinationIter, QueryEntityError,
+        QueryIter, QueryManyIter, QueryManyJoinIter, QueryParIter, QuerySingleError, QueryState,
+        ROQueryItem, ReadOnlyWorldQuery, WorldQuery,
     },
     world::{unsafe_world_cell::UnsafeWorldCell, Mut},
 };
@@ -398,6 +399,131 @@ impl<'w, 's, Q: WorldQuery, F: ReadOnlyWorldQuery> Query<'w, 's, Q, F> {
         }
     }
 
+    /// Joins this query with `other`, allowing to iterate over the entities matched by both queries
+    /// and fetch the items of both in a single pass.
+    ///
+    /// This is much cheaper than iterating over one query and calling [`get`](Self::get) on the
+    /// other one for every entity, since only the archetypes matched by both queries are visited.
+    /// The returned [`QueryJoin`] gives access to the joined items through a regular [`Query`],
+    /// which supports every iteration method, including [`iter_many`](Self::iter_many) and
+    /// [`par_iter`](Self::par_iter).
+    ///
+    /// The archetypes matched by the joined state are cached by this query and reused by the next
+    /// join with the same query, so that only the archetypes created since then need to be matched.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # #[derive(Component)]
+    /// # struct Transform;
+    /// # #[derive(Component)]
+    /// # struct Health(u32);
+    /// # #[derive(Component)]
+    /// # struct Enemy;
+    /// fn damage_enemies(
+    ///     mut enemies: Query<&Transform, With<Enemy>>,
+    ///     mut health: Query<&mut Health>,
+    /// ) {
+    ///     let mut joined = enemies.join(&mut health);
+    ///     for (_transform, mut health) in &mut joined.query() {
+    ///         health.0 = health.0.saturating_sub(1);
+    ///     }
+    /// }
+    /// # bevy_ecs::system::assert_is_system(damage_enemies);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if both queries access the same component and at least one of them does so mutably,
+    /// since both items are fetched for the same entity.
+    pub fn join<'a, Q2: WorldQuery, F2: ReadOnlyWorldQuery>(
+        &'a mut self,
+        other: &'a mut Query<'_, '_, Q2, F2>,
+    ) -> QueryJoin<'a, (Q, Q2), (F, F2)>
+    where
+        Q::State: Clone,
+        F::State: Clone,
+        Q2::State: Clone,
+        F2::State: Clone,
+    {
+        QueryJoin {
+            world: self.world,
+            state: self.state.join_cached(self.world, other.state),
+            cache: self.state.join_cache(),
+            other_access: &other.state.component_access,
+            last_run: self.last_run,
+            this_run: self.this_run,
+            force_read_only_component_access: self.force_read_only_component_access
+                || other.force_read_only_component_access,
+        }
+    }
+
+    /// Returns an [`Iterator`] over the read-only items of this query and `other` for the entities
+    /// of an [`Entity`] list matched by both queries.
+    ///
+    /// Items are returned in the order of the list, and entities that don't match both queries are
+    /// skipped. The location of each entity is only looked up once for both queries, which is
+    /// cheaper than calling [`iter_many`](Self::iter_many) and [`get`](Self::get) on the other query.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # #[derive(Component)]
+    /// # struct Transform;
+    /// # #[derive(Component)]
+    /// # struct Health(u32);
+    /// # #[derive(Component)]
+    /// # struct Enemy;
+    /// #[derive(Component)]
+    /// struct Targets(Vec<Entity>);
+    ///
+    /// fn report_targets(
+    ///     turrets: Query<&Targets>,
+    ///     enemies: Query<&Transform, With<Enemy>>,
+    ///     health: Query<&Health>,
+    /// ) {
+    ///     for targets in &turrets {
+    ///         for (_transform, health) in enemies.iter_many_join(&health, &targets.0) {
+    ///             println!("Target health: {}", health.0);
+    ///         }
+    ///     }
+    /// }
+    /// # bevy_ecs::system::assert_is_system(report_targets);
+    /// ```
+    #[inline]
+    pub fn iter_many_join<'a, Q2: WorldQuery, F2: ReadOnlyWorldQuery, EntityList: IntoIterator>(
+        &'a self,
+        other: &'a Query<'_, 's, Q2, F2>,
+        entities: EntityList,
+    ) -> QueryManyJoinIter<
+        'a,
+        's,
+        Q::ReadOnly,
+        F::ReadOnly,
+        Q2::ReadOnly,
+        F2::ReadOnly,
+        EntityList::IntoIter,
+    >
+    where
+        EntityList::Item: Borrow<Entity>,
+    {
+        self.state.validate_world(other.world.id());
+        // SAFETY:
+        // - Both queries have permission to access their components in the same world.
+        // - Both queries are read-only, so they can be aliased even if they were originally mutable.
+        unsafe {
+            QueryManyJoinIter::new(
+                self.world,
+                (self.state.as_readonly(), other.state.as_readonly()),
+                entities,
+                self.last_run,
+                self.this_run,
+            )
+        }
+    }
+
     /// Returns an [`Iterator`] over the read-only query items.
     ///
     /// # Example
@@ -1426,6 +1552,50 @@ impl<'w, 's, Q: WorldQuery, F: ReadOnlyWorldQuery> IntoIterator for &'w mut Quer
     }
 }
 
+/// The result of joining two [`Query`]s with [`Query::join`].
+///
+/// This holds the joined [`QueryState`], and borrows both source queries for as long as it lives.
+/// The archetypes matched by the joined state are cached by the first source query when this is
+/// dropped.
+pub struct QueryJoin<'w, Q: WorldQuery, F: ReadOnlyWorldQuery> {
+    world: UnsafeWorldCell<'w>,
+    state: QueryState<Q, F>,
+    cache: &'w JoinCache,
+    other_access: &'w FilteredAccess<ComponentId>,
+    last_run: Tick,
+    this_run: Tick,
+    force_read_only_component_access: bool,
+}
+
+impl<'w, Q: WorldQuery, F: ReadOnlyWorldQuery> QueryJoin<'w, Q, F> {
+    /// Returns a [`Query`] over the joined items.
+    pub fn query(&mut self) -> Query<'_, '_, Q, F> {
+        // SAFETY: the joined state only accesses components that the two source queries could access,
+        // and both of them are borrowed mutably for as long as `self` lives.
+        unsafe {
+            Query::new(
+                self.world,
+                &self.state,
+                self.last_run,
+                self.this_run,
+                self.force_read_only_component_access,
+            )
+        }
+    }
+
+    /// Returns the joined [`QueryState`].
+    pub fn state(&self) -> &QueryState<Q, F> {
+        &self.state
+    }
+}
+
+impl<'w, Q: WorldQuery, F: ReadOnlyWorldQuery> Drop for QueryJoin<'w, Q, F> {
+    fn drop(&mut self) {
+        self.cache
+            .put(self.other_access, self.state.take_joined_archetypes());
+    }
+}
+
 /// An error that occurs when retrieving a specific [`Entity`]'s component from a [`Query`].
 #[derive(Debug, PartialEq, Eq)]
 pub enum QueryComponentError {
@@ -72,6 +72,13 @@ pub trait System: Send + Sync + 'static {
     /// This is where [`Commands`](crate::system::Commands) get applied.
     fn apply_deferred(&mut self, world: &mut World);
 
+    /// Reports the errors this system held back until its deferred buffers are applied,
+    /// without applying them.
+    ///
+    /// Called at the end of a [`Schedule`](crate::schedule::Schedule) run that leaves
+    /// deferred buffers unapplied, so that errors are reported by the run that caused them.
+    fn report_errors(&mut self, _world: &mut World) {}
+
     /// Initialize the system.
     fn initialize(&mut self, _world: &mut World);
 
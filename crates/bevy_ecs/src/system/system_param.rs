@@ -117,6 +117,15 @@ pub unsafe trait SystemParam: Sized {
     ) {
     }
 
+    /// Forgets the archetypes registered with [`new_archetype`](SystemParam::new_archetype), after their ids
+    /// were changed by [`World::reclaim_empty_archetypes`]. All the archetypes are registered again right after.
+    ///
+    /// Parameters wrapping other parameters must forward this call to them. A [`Query`] also checks
+    /// whether archetypes were reclaimed before it runs, and matches them again from scratch if so,
+    /// so it never uses stale archetype ids, but it is matched twice if this call isn't forwarded.
+    #[inline]
+    fn forget_archetypes(_state: &mut Self::State) {}
+
     /// Applies any deferred mutations stored in this [`SystemParam`]'s state.
     /// This is used to apply [`Commands`] during [`apply_deferred`](crate::prelude::apply_deferred).
     ///
@@ -167,20 +176,7 @@ unsafe impl<Q: WorldQuery + 'static, F: ReadOnlyWorldQuery + 'static> SystemPara
 
     fn init_state(world: &mut World, system_meta: &mut SystemMeta) -> Self::State {
         let state = QueryState::new(world);
-        assert_component_access_compatibility(
-            &system_meta.name,
-            std::any::type_name::<Q>(),
-            std::any::type_name::<F>(),
-            &system_meta.component_access_set,
-            &state.component_access,
-            world,
-        );
-        system_meta
-            .component_access_set
-            .add(state.component_access.clone());
-        system_meta
-            .archetype_component_access
-            .extend(&state.archetype_component_access);
+        init_query_param(world, system_meta, &state);
         state
     }
 
@@ -191,6 +187,10 @@ unsafe impl<Q: WorldQuery + 'static, F: ReadOnlyWorldQuery + 'static> SystemPara
             .extend(&state.archetype_component_access);
     }
 
+    fn forget_archetypes(state: &mut Self::State) {
+        state.forget_archetypes();
+    }
+
     #[inline]
     unsafe fn get_param<'w, 's>(
         state: &'s mut Self::State,
@@ -198,6 +198,7 @@ unsafe impl<Q: WorldQuery + 'static, F: ReadOnlyWorldQuery + 'static> SystemPara
         world: UnsafeWorldCell<'w>,
         change_tick: Tick,
     ) -> Self::Item<'w, 's> {
+        state.rematch_reclaimed_archetypes(world);
         // SAFETY: We have registered all of the query's world accesses,
         // so the caller ensures that `world` has permission to access any
         // world data that the query needs.
@@ -205,6 +206,32 @@ unsafe impl<Q: WorldQuery + 'static, F: ReadOnlyWorldQuery + 'static> SystemPara
     }
 }
 
+/// Registers the access of a [`Query`] parameter's `state` with `system_meta`.
+///
+/// # Panics
+///
+/// Panics if the access conflicts with any access previously registered by the system.
+pub(crate) fn init_query_param<Q: WorldQuery, F: ReadOnlyWorldQuery>(
+    world: &World,
+    system_meta: &mut SystemMeta,
+    state: &QueryState<Q, F>,
+) {
+    assert_component_access_compatibility(
+        &system_meta.name,
+        std::any::type_name::<Q>(),
+        std::any::type_name::<F>(),
+        &system_meta.component_access_set,
+        &state.component_access,
+        world,
+    );
+    system_meta
+        .component_access_set
+        .add(state.component_access.clone());
+    system_meta
+        .archetype_component_access
+        .extend(&state.archetype_component_access);
+}
+
 fn assert_component_access_compatibility(
     system_name: &str,
     query_type: &'static str,
@@ -459,6 +486,8 @@ unsafe impl<'a, T: Resource> SystemParam for Res<'a, T> {
             ticks: Ticks {
                 added: ticks.added.deref(),
                 changed: ticks.changed.deref(),
+                #[cfg(feature = "track_change_detection")]
+                changed_by: Some(ticks.changed_by.deref()),
                 last_run: system_meta.last_run,
                 this_run: change_tick,
             },
@@ -492,6 +521,8 @@ unsafe impl<'a, T: Resource> SystemParam for Option<Res<'a, T>> {
                 ticks: Ticks {
                     added: ticks.added.deref(),
                     changed: ticks.changed.deref(),
+                    #[cfg(feature = "track_change_detection")]
+                    changed_by: Some(ticks.changed_by.deref()),
                     last_run: system_meta.last_run,
                     this_run: change_tick,
                 },
@@ -552,6 +583,8 @@ unsafe impl<'a, T: Resource> SystemParam for ResMut<'a, T> {
             ticks: TicksMut {
                 added: value.ticks.added,
                 changed: value.ticks.changed,
+                #[cfg(feature = "track_change_detection")]
+                changed_by: value.ticks.changed_by,
                 last_run: system_meta.last_run,
                 this_run: change_tick,
             },
@@ -582,6 +615,8 @@ unsafe impl<'a, T: Resource> SystemParam for Option<ResMut<'a, T>> {
                 ticks: TicksMut {
                     added: value.ticks.added,
                     changed: value.ticks.changed,
+                    #[cfg(feature = "track_change_detection")]
+                    changed_by: value.ticks.changed_by,
                     last_run: system_meta.last_run,
                     this_run: change_tick,
                 },
@@ -1389,6 +1424,11 @@ macro_rules! impl_system_param_tuple {
                 $($param::new_archetype($param, _archetype, _system_meta);)*
             }
 
+            #[inline]
+            fn forget_archetypes(($($param,)*): &mut Self::State) {
+                $($param::forget_archetypes($param);)*
+            }
+
             #[inline]
             fn apply(($($param,)*): &mut Self::State, _system_meta: &SystemMeta, _world: &mut World) {
                 $($param::apply($param, _system_meta, _world);)*
@@ -1526,6 +1566,10 @@ unsafe impl<P: SystemParam + 'static> SystemParam for StaticSystemParam<'_, '_,
         P::new_archetype(state, archetype, system_meta);
     }
 
+    fn forget_archetypes(state: &mut Self::State) {
+        P::forget_archetypes(state);
+    }
+
     fn apply(state: &mut Self::State, system_meta: &SystemMeta, world: &mut World) {
         P::apply(state, system_meta, world);
     }
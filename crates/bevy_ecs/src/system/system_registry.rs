@@ -0,0 +1,301 @@
+use crate::{
+    self as bevy_ecs,
+    component::Component,
+    entity::Entity,
+    system::{BoxedSystem, Command, IntoSystem},
+    world::World,
+};
+use bevy_utils::tracing::warn;
+use thiserror::Error;
+
+/// A system stored on an entity by [`World::register_system`].
+#[derive(Component)]
+struct RegisteredSystem {
+    initialized: bool,
+    system: BoxedSystem,
+}
+
+/// Marks the entities of the systems registered with [`World::register_system`].
+///
+/// It stays on the entity while the [`RegisteredSystem`] is taken out to run, which tells a
+/// running system apart from an entity that was never a registered system.
+#[derive(Component)]
+struct SystemIdMarker;
+
+/// An identifier for a system registered with [`World::register_system`].
+///
+/// It can be stored in components and resources, to run the system later with
+/// [`World::run_system`] or [`Commands::run_system`](crate::system::Commands::run_system).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct SystemId(Entity);
+
+impl SystemId {
+    /// Returns the entity the system is stored on.
+    #[inline]
+    pub fn entity(self) -> Entity {
+        self.0
+    }
+}
+
+impl World {
+    /// Registers a system and returns its [`SystemId`], so it can later be run with [`World::run_system`].
+    ///
+    /// The system is stored on a new entity of this world. It keeps its state, such as its
+    /// [`Local`](crate::system::Local)s, between runs.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #[derive(Resource, Default)]
+    /// struct Counter(u8);
+    ///
+    /// fn increment(mut counter: ResMut<Counter>, mut runs: Local<u8>) {
+    ///     *runs += 1;
+    ///     counter.0 = *runs;
+    /// }
+    ///
+    /// let mut world = World::new();
+    /// world.init_resource::<Counter>();
+    /// let id = world.register_system(increment);
+    ///
+    /// world.run_system(id).unwrap();
+    /// world.run_system(id).unwrap();
+    /// assert_eq!(world.resource::<Counter>().0, 2);
+    /// ```
+    pub fn register_system<M, S: IntoSystem<(), (), M> + 'static>(
+        &mut self,
+        system: S,
+    ) -> SystemId {
+        self.register_boxed_system(Box::new(IntoSystem::into_system(system)))
+    }
+
+    /// Registers an already boxed system and returns its [`SystemId`].
+    ///
+    /// See [`World::register_system`] for more details.
+    pub fn register_boxed_system(&mut self, system: BoxedSystem) -> SystemId {
+        SystemId(
+            self.spawn((
+                RegisteredSystem {
+                    initialized: false,
+                    system,
+                },
+                SystemIdMarker,
+            ))
+            .id(),
+        )
+    }
+
+    /// Removes a system registered with [`World::register_system`], and returns it.
+    ///
+    /// The entity the system was stored on is despawned.
+    ///
+    /// Returns an error if `id` doesn't belong to a registered system, or if the system is currently running.
+    pub fn remove_system(&mut self, id: SystemId) -> Result<BoxedSystem, RegisteredSystemError> {
+        let mut entity = self
+            .get_entity_mut(id.0)
+            .filter(|entity| entity.contains::<SystemIdMarker>())
+            .ok_or(RegisteredSystemError::SystemIdNotRegistered(id))?;
+        let registered = entity
+            .take::<RegisteredSystem>()
+            .ok_or(RegisteredSystemError::Recursive(id))?;
+        entity.despawn();
+        Ok(registered.system)
+    }
+
+    /// Runs a system registered with [`World::register_system`], and applies its deferred
+    /// buffers, such as its [`Commands`](crate::system::Commands), right away.
+    ///
+    /// The system is initialized the first time it runs.
+    ///
+    /// Returns an error if `id` doesn't belong to a registered system, or if the system tries to run itself.
+    pub fn run_system(&mut self, id: SystemId) -> Result<(), RegisteredSystemError> {
+        let mut entity = self
+            .get_entity_mut(id.0)
+            .filter(|entity| entity.contains::<SystemIdMarker>())
+            .ok_or(RegisteredSystemError::SystemIdNotRegistered(id))?;
+        // take the system out of the world, so that it can run with exclusive access to the world
+        let RegisteredSystem {
+            mut initialized,
+            mut system,
+        } = entity
+            .take::<RegisteredSystem>()
+            .ok_or(RegisteredSystemError::Recursive(id))?;
+
+        if !initialized {
+            system.initialize(self);
+            initialized = true;
+        }
+        system.run((), self);
+        system.apply_deferred(self);
+
+        // the system may have removed itself while running
+        if let Some(mut entity) = self.get_entity_mut(id.0) {
+            entity.insert(RegisteredSystem {
+                initialized,
+                system,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// A [`Command`] that runs the system with the given [`SystemId`].
+///
+/// See [`Commands::run_system`](crate::system::Commands::run_system).
+#[derive(Debug, Clone, Copy)]
+pub struct RunSystem {
+    /// The system to run.
+    pub system_id: SystemId,
+}
+
+impl Command for RunSystem {
+    fn apply(self, world: &mut World) {
+        if let Err(error) = world.run_system(self.system_id) {
+            warn!("Could not run system: {error}");
+        }
+    }
+}
+
+/// An error returned by the methods running or removing systems registered with [`World::register_system`].
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RegisteredSystemError {
+    /// No system is registered with this [`SystemId`], or it was removed.
+    #[error("System {0:?} was not registered")]
+    SystemIdNotRegistered(SystemId),
+    /// The system is currently running, so it can't be run or removed.
+    #[error("System {0:?} tried to run itself recursively")]
+    Recursive(SystemId),
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        self as bevy_ecs,
+        prelude::*,
+        system::{CommandQueue, RegisteredSystemError, SystemId},
+    };
+
+    #[derive(Resource, Default, PartialEq, Debug)]
+    struct Counter(u8);
+
+    fn count_up(mut counter: ResMut<Counter>) {
+        counter.0 += 1;
+    }
+
+    #[test]
+    fn run_system_keeps_state() {
+        fn count_locally(mut counter: ResMut<Counter>, mut local: Local<u8>) {
+            *local += 1;
+            counter.0 = *local;
+        }
+
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+        let id = world.register_system(count_locally);
+
+        world.run_system(id).unwrap();
+        world.run_system(id).unwrap();
+        world.run_system(id).unwrap();
+
+        assert_eq!(*world.resource::<Counter>(), Counter(3));
+    }
+
+    #[test]
+    fn run_system_applies_commands() {
+        fn spawn(mut commands: Commands) {
+            commands.spawn_empty();
+        }
+
+        let mut world = World::new();
+        let id = world.register_system(spawn);
+        let entities = world.entities().len();
+
+        world.run_system(id).unwrap();
+
+        assert_eq!(world.entities().len(), entities + 1);
+    }
+
+    #[test]
+    fn run_system_from_commands() {
+        #[derive(Component)]
+        struct OnClick(SystemId);
+
+        fn click(mut commands: Commands, buttons: Query<&OnClick>) {
+            for on_click in &buttons {
+                commands.run_system(on_click.0);
+            }
+        }
+
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+        let id = world.register_system(count_up);
+        world.spawn(OnClick(id));
+        world.spawn(OnClick(id));
+
+        let mut schedule = Schedule::new();
+        schedule.add_systems(click);
+        schedule.run(&mut world);
+
+        assert_eq!(*world.resource::<Counter>(), Counter(2));
+    }
+
+    #[test]
+    fn remove_system() {
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+        let id = world.register_system(count_up);
+
+        let mut system = world.remove_system(id).unwrap();
+        assert!(world.get_entity(id.entity()).is_none());
+        assert_eq!(
+            world.run_system(id),
+            Err(RegisteredSystemError::SystemIdNotRegistered(id))
+        );
+
+        system.initialize(&mut world);
+        system.run((), &mut world);
+        assert_eq!(*world.resource::<Counter>(), Counter(1));
+    }
+
+    #[test]
+    fn run_unregistered_system() {
+        let mut world = World::new();
+        let id = World::new().register_system(count_up);
+        // an entity which isn't a registered system, with the same index as `id`
+        let entity = world.spawn_empty().id();
+        assert_eq!(entity, id.entity());
+
+        assert_eq!(
+            world.run_system(id),
+            Err(RegisteredSystemError::SystemIdNotRegistered(id))
+        );
+        assert_eq!(
+            world.remove_system(id).err(),
+            Some(RegisteredSystemError::SystemIdNotRegistered(id))
+        );
+        assert!(world.get_entity(entity).is_some());
+    }
+
+    #[test]
+    fn recursive_run_system() {
+        #[derive(Resource)]
+        struct Recursion(SystemId, Option<RegisteredSystemError>);
+
+        fn recurse(world: &mut World) {
+            let id = world.resource::<Recursion>().0;
+            world.resource_mut::<Recursion>().1 = world.run_system(id).err();
+        }
+
+        let mut world = World::new();
+        let id = world.register_system(recurse);
+        world.insert_resource(Recursion(id, None));
+
+        let mut queue = CommandQueue::default();
+        Commands::new(&mut queue, &world).run_system(id);
+        queue.apply(&mut world);
+
+        assert_eq!(
+            world.resource::<Recursion>().1,
+            Some(RegisteredSystemError::Recursive(id))
+        );
+    }
+}
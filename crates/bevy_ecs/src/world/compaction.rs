@@ -0,0 +1,353 @@
+use fixedbitset::FixedBitSet;
+
+use crate::{self as bevy_ecs, system::Resource, world::World};
+
+impl World {
+    /// Releases the memory that the storages of this world keep for entities that no longer exist.
+    ///
+    /// The capacity of every [`Table`](crate::storage::Table), sparse set and
+    /// [`Archetype`](crate::archetype::Archetype) is shrunk down to the number of entities it
+    /// stores, so empty tables don't hold any component memory afterwards.
+    ///
+    /// The tables and archetypes themselves are kept, see [`World::reclaim_empty_archetypes`]
+    /// to remove the empty ones.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #[derive(Component)]
+    /// struct Bullet;
+    ///
+    /// let mut world = World::new();
+    /// let bullets: Vec<_> = world.spawn_batch((0..1000).map(|_| Bullet)).collect();
+    /// for bullet in bullets {
+    ///     world.despawn(bullet);
+    /// }
+    /// world.compact();
+    /// ```
+    pub fn compact(&mut self) {
+        self.flush();
+        self.storages.tables.shrink_to_fit();
+        self.storages.sparse_sets.shrink_to_fit();
+        self.archetypes.shrink_to_fit();
+    }
+
+    /// Removes the empty [`Archetype`](crate::archetype::Archetype)s of this world, along with the
+    /// [`Table`](crate::storage::Table)s only they were using, and returns the number of removed archetypes.
+    ///
+    /// The remaining archetypes and tables get new ids. The [`QueryState`](crate::query::QueryState)s
+    /// and systems that cached the previous ids notice the change the next time they update their
+    /// archetypes, and match all archetypes again.
+    /// Removed archetypes are created again if an entity needs them.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #[derive(Component)]
+    /// struct Shield;
+    ///
+    /// let mut world = World::new();
+    /// let mut query = world.query::<&Shield>();
+    /// let player = world.spawn_empty().id();
+    /// world.entity_mut(player).insert(Shield);
+    /// world.entity_mut(player).remove::<Shield>();
+    ///
+    /// assert_eq!(world.reclaim_empty_archetypes(), 1);
+    /// assert_eq!(query.iter(&world).count(), 0);
+    /// ```
+    pub fn reclaim_empty_archetypes(&mut self) -> usize {
+        self.flush();
+        let Some(new_archetype_ids) = self.archetypes.remove_empty() else {
+            return 0;
+        };
+
+        let mut used_tables = FixedBitSet::with_capacity(self.storages.tables.len());
+        for archetype in self.archetypes.iter() {
+            used_tables.insert(archetype.table_id().index());
+        }
+        let new_table_ids = self
+            .storages
+            .tables
+            .remove_unused(|table_id| used_tables.contains(table_id.index()));
+        self.archetypes.remap_tables(&new_table_ids);
+
+        self.entities.update_locations(|location| {
+            // entities are only stored in non-empty archetypes and their tables, which are kept
+            location.archetype_id = new_archetype_ids[location.archetype_id.index()]
+                .expect("the archetype of an entity should not be removed");
+            location.table_id = new_table_ids[location.table_id.index()]
+                .expect("the table of an entity should not be removed");
+        });
+
+        new_archetype_ids.iter().filter(|id| id.is_none()).count()
+    }
+}
+
+/// How often the [`compact_world`] system compacts the [`World`] it runs in.
+///
+/// Without this resource, [`compact_world`] does nothing.
+#[derive(Resource, Debug, Clone)]
+pub struct CompactionPolicy {
+    /// The number of runs of [`compact_world`] between two compactions.
+    pub interval: u32,
+    /// Whether to also remove the empty archetypes with [`World::reclaim_empty_archetypes`].
+    pub reclaim_empty_archetypes: bool,
+    runs: u32,
+}
+
+impl CompactionPolicy {
+    /// Compacts the world every `interval` runs of [`compact_world`], without reclaiming empty archetypes.
+    pub fn every(interval: u32) -> Self {
+        Self {
+            interval,
+            reclaim_empty_archetypes: false,
+            runs: 0,
+        }
+    }
+
+    /// Also removes the empty archetypes when compacting.
+    pub fn reclaiming_empty_archetypes(mut self) -> Self {
+        self.reclaim_empty_archetypes = true;
+        self
+    }
+}
+
+/// Compacts the world with [`World::compact`], as often as requested by the [`CompactionPolicy`] resource.
+pub fn compact_world(world: &mut World) {
+    let Some(mut policy) = world.get_resource_mut::<CompactionPolicy>() else {
+        return;
+    };
+    policy.runs += 1;
+    if policy.runs < policy.interval {
+        return;
+    }
+    policy.runs = 0;
+
+    if policy.reclaim_empty_archetypes {
+        world.reclaim_empty_archetypes();
+    }
+    world.compact();
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        self as bevy_ecs,
+        prelude::*,
+        world::{compact_world, CompactionPolicy},
+    };
+
+    #[derive(Component, Debug, PartialEq)]
+    struct A(u32);
+
+    #[derive(Component, Debug, PartialEq)]
+    struct B(u32);
+
+    #[derive(Component, Debug, PartialEq)]
+    #[component(storage = "SparseSet")]
+    struct S(u32);
+
+    #[test]
+    fn compact_keeps_components() {
+        let mut world = World::new();
+        let entities: Vec<_> = world.spawn_batch((0..100).map(|i| (A(i), S(i)))).collect();
+        for entity in &entities[1..] {
+            world.despawn(*entity);
+        }
+
+        world.compact();
+
+        let table = world.storages().tables.iter().last().unwrap();
+        assert_eq!(table.entity_count(), 1);
+        assert_eq!(table.entity_capacity(), 1);
+        assert_eq!(world.get::<A>(entities[0]), Some(&A(0)));
+        assert_eq!(world.get::<S>(entities[0]), Some(&S(0)));
+        world.spawn((A(1), S(1)));
+        assert_eq!(world.query::<(&A, &S)>().iter(&world).count(), 2);
+    }
+
+    #[test]
+    fn reclaim_empty_archetypes() {
+        let mut world = World::new();
+        let mut query = world.query::<(Entity, &A)>();
+        let mut system = IntoSystem::into_system(|query: Query<&A>| query.iter().count());
+        system.initialize(&mut world);
+
+        let a = world.spawn(A(0)).id();
+        let ab = world.spawn((A(1), B(1))).id();
+        let abs = world.spawn((A(2), B(2), S(2))).id();
+        let empty = world.spawn(A(3)).id();
+        world.entity_mut(empty).remove::<A>();
+        // caches the archetypes
+        assert_eq!(query.iter(&world).count(), 3);
+        assert_eq!(system.run((), &mut world), 3);
+
+        world.despawn(ab);
+        let archetypes = world.archetypes().len();
+        let tables = world.storages().tables.len();
+        assert_eq!(world.reclaim_empty_archetypes(), 1);
+        assert_eq!(world.archetypes().len(), archetypes - 1);
+        assert_eq!(world.storages().tables.len(), tables - 1);
+        assert_eq!(world.reclaim_empty_archetypes(), 0);
+
+        for (id, archetype) in world.archetypes().iter().enumerate() {
+            assert_eq!(archetype.id().index(), id);
+            for entity in archetype.entities() {
+                let location = world.entities().get(entity.entity()).unwrap();
+                assert_eq!(location.archetype_id, archetype.id());
+                assert_eq!(location.table_id, archetype.table_id());
+            }
+        }
+        let mut found: Vec<_> = query.iter(&world).collect();
+        found.sort_by_key(|(_, a)| a.0);
+        assert_eq!(found, vec![(a, &A(0)), (abs, &A(2))]);
+        assert_eq!(system.run((), &mut world), 2);
+        assert!(world.get_entity(empty).is_some());
+
+        // the removed archetype is created again
+        let ab = world.spawn((A(1), B(1))).id();
+        world.entity_mut(a).insert(B(0));
+        assert_eq!(world.get::<B>(ab), Some(&B(1)));
+        assert_eq!(query.iter(&world).count(), 3);
+        assert_eq!(system.run((), &mut world), 3);
+    }
+
+    #[test]
+    fn reclaim_archetypes_of_wrapped_query() {
+        use crate::{
+            archetype::Archetype,
+            component::Tick,
+            query::QueryState,
+            system::{SystemMeta, SystemParam},
+            world::unsafe_world_cell::UnsafeWorldCell,
+        };
+
+        /// Wraps a query without forwarding `forget_archetypes`.
+        struct Wrapped<'w, 's>(Query<'w, 's, &'static A>);
+
+        type Inner = Query<'static, 'static, &'static A>;
+
+        // SAFETY: the access of the wrapped query is registered by its `init_state` and `new_archetype`.
+        unsafe impl SystemParam for Wrapped<'_, '_> {
+            type State = QueryState<&'static A>;
+            type Item<'w, 's> = Wrapped<'w, 's>;
+
+            fn init_state(world: &mut World, system_meta: &mut SystemMeta) -> Self::State {
+                Inner::init_state(world, system_meta)
+            }
+
+            fn new_archetype(
+                state: &mut Self::State,
+                archetype: &Archetype,
+                system_meta: &mut SystemMeta,
+            ) {
+                Inner::new_archetype(state, archetype, system_meta);
+            }
+
+            unsafe fn get_param<'w, 's>(
+                state: &'s mut Self::State,
+                system_meta: &SystemMeta,
+                world: UnsafeWorldCell<'w>,
+                change_tick: Tick,
+            ) -> Self::Item<'w, 's> {
+                // SAFETY: forwarded from the caller.
+                Wrapped(unsafe { Inner::get_param(state, system_meta, world, change_tick) })
+            }
+        }
+
+        let mut world = World::new();
+        let mut system =
+            IntoSystem::into_system(|wrapped: Wrapped| wrapped.0.iter().map(|a| a.0).sum::<u32>());
+        system.initialize(&mut world);
+
+        #[derive(Component)]
+        struct C;
+
+        world.spawn(A(1));
+        let ab = world.spawn((A(2), B(2))).id();
+        world.spawn((A(4), C));
+        assert_eq!(system.run((), &mut world), 7);
+
+        world.despawn(ab);
+        assert_eq!(world.reclaim_empty_archetypes(), 1);
+        assert_eq!(system.run((), &mut world), 5);
+        world.spawn((A(8), B(8)));
+        assert_eq!(system.run((), &mut world), 13);
+    }
+
+    #[test]
+    #[should_panic = "after empty archetypes were reclaimed"]
+    fn reclaim_archetypes_of_manual_query() {
+        #[derive(Component)]
+        struct C(u8);
+
+        let mut world = World::new();
+        let b = world.spawn(B(0)).id();
+        world.spawn(A(1));
+        world.spawn(C(2));
+        let query = world.query::<&A>();
+        assert_eq!(query.iter_manual(&world).count(), 1);
+
+        // the table of `A` now has the id the table of `C` had
+        world.despawn(b);
+        assert_eq!(world.reclaim_empty_archetypes(), 1);
+        query.iter_manual(&world).for_each(drop);
+    }
+
+    #[test]
+    fn reclaim_archetypes_of_manual_methods() {
+        use crate::system::SystemState;
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+
+        #[derive(Component)]
+        struct C(u8);
+
+        let mut world = World::new();
+        let b = world.spawn(B(0)).id();
+        let a = world.spawn(A(1)).id();
+        world.spawn(C(2));
+        let mut query = world.query::<&A>();
+        let mut system_state = SystemState::<Query<&A>>::new(&mut world);
+        system_state.update_archetypes(&world);
+        assert_eq!(system_state.get_manual(&world).iter().count(), 1);
+
+        world.despawn(b);
+        assert_eq!(world.reclaim_empty_archetypes(), 1);
+        let panics = |f: &dyn Fn()| catch_unwind(AssertUnwindSafe(f)).is_err();
+        assert!(panics(&|| drop(query.get_manual(&world, a))));
+        assert!(panics(&|| query
+            .iter_many_manual(&world, [a])
+            .for_each(drop)));
+
+        // the archetypes are matched again once updated
+        query.update_archetypes(&world);
+        assert_eq!(query.get_manual(&world, a), Ok(&A(1)));
+        assert_eq!(query.iter_manual(&world).collect::<Vec<_>>(), [&A(1)]);
+        // system states match the archetypes again by themselves
+        assert_eq!(
+            system_state.get_manual(&world).iter().collect::<Vec<_>>(),
+            [&A(1)]
+        );
+        assert_eq!(
+            system_state
+                .get_manual_mut(&mut world)
+                .iter()
+                .collect::<Vec<_>>(),
+            [&A(1)]
+        );
+    }
+
+    #[test]
+    fn compaction_policy() {
+        let mut world = World::new();
+        world.insert_resource(CompactionPolicy::every(2).reclaiming_empty_archetypes());
+        let entity = world.spawn(A(0)).id();
+        world.despawn(entity);
+        let archetypes = world.archetypes().len();
+
+        let mut schedule = Schedule::new();
+        schedule.add_systems(compact_world);
+        schedule.run(&mut world);
+        assert_eq!(world.archetypes().len(), archetypes);
+        schedule.run(&mut world);
+        assert_eq!(world.archetypes().len(), archetypes - 1);
+    }
+}
@@ -0,0 +1,92 @@
+use std::ops::Deref;
+
+use crate::{
+    change_detection::{Mut, MutUntyped},
+    component::{Component, ComponentId},
+    entity::Entity,
+    event::Event,
+    system::{Commands, Resource},
+    world::World,
+};
+
+/// A [`World`] reference that disallows structural ECS changes.
+///
+/// This is handed to [component hooks](crate::component::ComponentHooks): data can be read and
+/// mutated freely, but spawning or despawning entities, inserting or removing components and
+/// inserting or removing resources must go through [`DeferredWorld::commands`].
+/// Those commands are applied as soon as the [`World`] operation that ran the hook completes.
+pub struct DeferredWorld<'w> {
+    world: &'w mut World,
+}
+
+impl<'w> Deref for DeferredWorld<'w> {
+    type Target = World;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.world
+    }
+}
+
+impl<'w> From<&'w mut World> for DeferredWorld<'w> {
+    fn from(world: &'w mut World) -> Self {
+        DeferredWorld { world }
+    }
+}
+
+impl<'w> DeferredWorld<'w> {
+    /// Creates a [`Commands`] instance that pushes to the world's command queue.
+    ///
+    /// The queued commands are applied once the operation that triggered the current hook completes.
+    #[inline]
+    pub fn commands(&mut self) -> Commands<'_, '_> {
+        Commands::new_from_entities(&mut self.world.command_queue, &self.world.entities)
+    }
+
+    /// Retrieves a mutable reference to the given `entity`'s [`Component`] of the given type.
+    /// Returns `None` if the `entity` does not have a [`Component`] of the given type.
+    #[inline]
+    pub fn get_mut<T: Component>(&mut self, entity: Entity) -> Option<Mut<'_, T>> {
+        self.world.get_mut(entity)
+    }
+
+    /// Gets a mutable reference to the resource of the given type.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the resource does not exist.
+    /// Use [`get_resource_mut`](DeferredWorld::get_resource_mut) instead if you want to handle this case.
+    #[inline]
+    #[track_caller]
+    pub fn resource_mut<R: Resource>(&mut self) -> Mut<'_, R> {
+        self.world.resource_mut()
+    }
+
+    /// Gets a mutable reference to the resource of the given type if it exists.
+    #[inline]
+    pub fn get_resource_mut<R: Resource>(&mut self) -> Option<Mut<'_, R>> {
+        self.world.get_resource_mut()
+    }
+
+    /// Gets a mutable reference to the resource with the given [`ComponentId`] if it exists.
+    #[inline]
+    pub fn get_resource_mut_by_id(&mut self, component_id: ComponentId) -> Option<MutUntyped<'_>> {
+        self.world.get_resource_mut_by_id(component_id)
+    }
+
+    /// Gets a mutable reference to the non-send resource of the given type, if it exists.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if it isn't called from the same thread that the resource was inserted from.
+    #[inline]
+    pub fn get_non_send_resource_mut<R: 'static>(&mut self) -> Option<Mut<'_, R>> {
+        self.world.get_non_send_resource_mut()
+    }
+
+    /// Sends an [`Event`].
+    #[inline]
+    pub fn send_event<E: Event>(&mut self, event: E) {
+        self.world.send_event(event);
+    }
+}
@@ -1,9 +1,10 @@
 use crate::{
     archetype::{Archetype, ArchetypeId, Archetypes},
-    bundle::{Bundle, BundleInfo, BundleInserter, DynamicBundle},
+    bundle::{Bundle, BundleId, BundleInfo, BundleInserter, DynamicBundle},
     change_detection::MutUntyped,
     component::{Component, ComponentId, ComponentTicks, Components, StorageType},
     entity::{Entities, Entity, EntityLocation},
+    query::DebugCheckedUnwrap,
     removal_detection::RemovedComponentEvents,
     storage::Storages,
     world::{Mut, World},
@@ -12,6 +13,9 @@ use bevy_ptr::{OwningPtr, Ptr};
 use bevy_utils::tracing::debug;
 use std::any::TypeId;
 
+#[cfg(feature = "track_change_detection")]
+use crate::change_detection::{ChangedBy, DetectChanges};
+
 use super::{unsafe_world_cell::UnsafeEntityCell, Ref};
 
 /// A read-only reference to a particular [`Entity`] and all of its components
@@ -139,6 +143,16 @@ impl<'w> EntityRef<'w> {
         unsafe { self.as_unsafe_world_cell_readonly().get_change_ticks::<T>() }
     }
 
+    /// Returns where the component of type `T` was most recently changed, if it was changed
+    /// through change detection since it was added.
+    #[cfg(feature = "track_change_detection")]
+    #[inline]
+    pub fn get_changed_by<T: Component>(&self) -> Option<ChangedBy> {
+        // SAFETY: &self implies shared access
+        let component = unsafe { self.as_unsafe_world_cell_readonly().get_ref::<T>() }?;
+        component.changed_by().cloned()
+    }
+
     /// Retrieves the change ticks for the given [`ComponentId`]. This can be useful for implementing change
     /// detection in custom runtimes.
     ///
@@ -173,6 +187,7 @@ impl<'w> EntityRef<'w> {
 
 impl<'w> From<EntityMut<'w>> for EntityRef<'w> {
     fn from(entity_mut: EntityMut<'w>) -> EntityRef<'w> {
+        entity_mut.assert_not_despawned();
         // SAFETY: the safety invariants on EntityMut and EntityRef are identical
         // and EntityMut is promised to be valid by construction.
         unsafe { EntityRef::new(entity_mut.world, entity_mut.entity, entity_mut.location) }
@@ -188,6 +203,7 @@ pub struct EntityMut<'w> {
 
 impl<'w> EntityMut<'w> {
     fn as_unsafe_world_cell_readonly(&self) -> UnsafeEntityCell<'_> {
+        self.assert_not_despawned();
         UnsafeEntityCell::new(
             self.world.as_unsafe_world_cell_readonly(),
             self.entity,
@@ -195,6 +211,7 @@ impl<'w> EntityMut<'w> {
         )
     }
     fn as_unsafe_world_cell(&mut self) -> UnsafeEntityCell<'_> {
+        self.assert_not_despawned();
         UnsafeEntityCell::new(
             self.world.as_unsafe_world_cell(),
             self.entity,
@@ -234,12 +251,14 @@ impl<'w> EntityMut<'w> {
     /// Gets metadata indicating the location where the current entity is stored.
     #[inline]
     pub fn location(&self) -> EntityLocation {
+        self.assert_not_despawned();
         self.location
     }
 
     /// Returns the archetype that the current entity belongs to.
     #[inline]
     pub fn archetype(&self) -> &Archetype {
+        self.assert_not_despawned();
         &self.world.archetypes[self.location.archetype_id]
     }
 
@@ -306,6 +325,16 @@ impl<'w> EntityMut<'w> {
         unsafe { self.as_unsafe_world_cell_readonly().get_change_ticks::<T>() }
     }
 
+    /// Returns where the component of type `T` was most recently changed, if it was changed
+    /// through change detection since it was added.
+    #[cfg(feature = "track_change_detection")]
+    #[inline]
+    pub fn get_changed_by<T: Component>(&self) -> Option<ChangedBy> {
+        // SAFETY: &self implies shared access
+        let component = unsafe { self.as_unsafe_world_cell_readonly().get_ref::<T>() }?;
+        component.changed_by().cloned()
+    }
+
     /// Retrieves the change ticks for the given [`ComponentId`]. This can be useful for implementing change
     /// detection in custom runtimes.
     ///
@@ -325,6 +354,7 @@ impl<'w> EntityMut<'w> {
     ///
     /// This will overwrite any previous value(s) of the same component type.
     pub fn insert<T: Bundle>(&mut self, bundle: T) -> &mut Self {
+        self.assert_not_despawned();
         let change_tick = self.world.change_tick();
         let bundle_info = self
             .world
@@ -338,10 +368,13 @@ impl<'w> EntityMut<'w> {
             self.location.archetype_id,
             change_tick,
         );
+        let bundle_id = bundle_info.id();
+        let old_archetype_id = self.location.archetype_id;
         // SAFETY: location matches current entity. `T` matches `bundle_info`
         unsafe {
             self.location = bundle_inserter.insert(self.entity, self.location, bundle);
         }
+        self.after_insert(bundle_id, Some(old_archetype_id));
 
         self
     }
@@ -361,12 +394,15 @@ impl<'w> EntityMut<'w> {
         component_id: ComponentId,
         component: OwningPtr<'_>,
     ) -> &mut Self {
+        self.assert_not_despawned();
         let change_tick = self.world.change_tick();
 
         let bundles = &mut self.world.bundles;
         let components = &mut self.world.components;
 
         let (bundle_info, storage_type) = bundles.init_component_info(components, component_id);
+        let bundle_id = bundle_info.id();
+        let old_archetype_id = self.location.archetype_id;
         let bundle_inserter = bundle_info.get_bundle_inserter(
             &mut self.world.entities,
             &mut self.world.archetypes,
@@ -383,6 +419,7 @@ impl<'w> EntityMut<'w> {
             Some(component).into_iter(),
             Some(storage_type).into_iter(),
         );
+        self.after_insert(bundle_id, Some(old_archetype_id));
 
         self
     }
@@ -404,12 +441,15 @@ impl<'w> EntityMut<'w> {
         component_ids: &[ComponentId],
         iter_components: I,
     ) -> &mut Self {
+        self.assert_not_despawned();
         let change_tick = self.world.change_tick();
 
         let bundles = &mut self.world.bundles;
         let components = &mut self.world.components;
 
         let (bundle_info, storage_types) = bundles.init_dynamic_info(components, component_ids);
+        let bundle_id = bundle_info.id();
+        let old_archetype_id = self.location.archetype_id;
         let bundle_inserter = bundle_info.get_bundle_inserter(
             &mut self.world.entities,
             &mut self.world.archetypes,
@@ -426,6 +466,7 @@ impl<'w> EntityMut<'w> {
             iter_components,
             storage_types.iter().cloned(),
         );
+        self.after_insert(bundle_id, Some(old_archetype_id));
 
         self
     }
@@ -437,6 +478,24 @@ impl<'w> EntityMut<'w> {
     // TODO: BundleRemover?
     #[must_use]
     pub fn take<T: Bundle>(&mut self) -> Option<T> {
+        self.assert_not_despawned();
+        let bundle_id = self
+            .world
+            .bundles
+            .init_info::<T>(&mut self.world.components, &mut self.world.storages)
+            .id();
+        let archetype = &self.world.archetypes[self.location.archetype_id];
+        let bundle_info = self.world.bundles.get(bundle_id).unwrap();
+        if !bundle_info
+            .components()
+            .iter()
+            .all(|&id| archetype.contains(id))
+        {
+            return None;
+        }
+        self.world
+            .trigger_remove_hooks(self.entity, self.location.archetype_id, Some(bundle_id));
+
         let archetypes = &mut self.world.archetypes;
         let storages = &mut self.world.storages;
         let components = &mut self.world.components;
@@ -497,6 +556,7 @@ impl<'w> EntityMut<'w> {
                 new_archetype_id,
             );
         }
+        self.flush_commands();
 
         Some(result)
     }
@@ -585,13 +645,44 @@ impl<'w> EntityMut<'w> {
     /// Removes any components in the [`Bundle`] from the entity.
     // TODO: BundleRemover?
     pub fn remove<T: Bundle>(&mut self) -> &mut Self {
+        let bundle_id = self
+            .world
+            .bundles
+            .init_info::<T>(&mut self.world.components, &mut self.world.storages)
+            .id();
+        self.remove_bundle(bundle_id)
+    }
+
+    /// Removes the component with the given [`ComponentId`] from the entity, if it has it.
+    ///
+    /// You should prefer to use the typed API [`EntityMut::remove`] where possible.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the provided [`ComponentId`] does not exist in the [`World`].
+    pub fn remove_by_id(&mut self, component_id: ComponentId) -> &mut Self {
+        let bundle_id = self
+            .world
+            .bundles
+            .init_component_info(&mut self.world.components, component_id)
+            .0
+            .id();
+        self.remove_bundle(bundle_id)
+    }
+
+    fn remove_bundle(&mut self, bundle_id: BundleId) -> &mut Self {
+        self.assert_not_despawned();
+        self.world
+            .trigger_remove_hooks(self.entity, self.location.archetype_id, Some(bundle_id));
+
         let archetypes = &mut self.world.archetypes;
         let storages = &mut self.world.storages;
         let components = &mut self.world.components;
         let entities = &mut self.world.entities;
         let removed_components = &mut self.world.removed_components;
 
-        let bundle_info = self.world.bundles.init_info::<T>(components, storages);
+        // SAFETY: the caller initialized the bundle with this id.
+        let bundle_info = unsafe { self.world.bundles.get(bundle_id).debug_checked_unwrap() };
         let old_location = self.location;
 
         // SAFETY: `archetype_id` exists because it is referenced in the old `EntityLocation` which is valid,
@@ -643,15 +734,23 @@ impl<'w> EntityMut<'w> {
                 new_archetype_id,
             );
         }
+        self.flush_commands();
 
         self
     }
 
     /// Despawns the current entity.
+    ///
+    /// Does nothing if the entity was already despawned by a component hook.
     pub fn despawn(self) {
+        if self.location == EntityLocation::INVALID {
+            return;
+        }
         debug!("Despawning entity {:?}", self.entity);
         let world = self.world;
         world.flush();
+        let archetype_id = world.entities.get(self.entity).unwrap().archetype_id;
+        world.trigger_remove_hooks(self.entity, archetype_id, None);
         let location = world
             .entities
             .free(self.entity)
@@ -711,6 +810,7 @@ impl<'w> EntityMut<'w> {
             world.archetypes[moved_location.archetype_id]
                 .set_entity_table_row(moved_location.archetype_row, table_row);
         }
+        world.flush_commands();
     }
 
     /// Gets read-only access to the world that the current entity belongs to.
@@ -787,7 +887,50 @@ impl<'w> EntityMut<'w> {
     /// This is *only* required when using the unsafe function [`EntityMut::world_mut`],
     /// which enables the location to change.
     pub fn update_location(&mut self) {
-        self.location = self.world.entities().get(self.entity).unwrap();
+        self.location = self
+            .world
+            .entities()
+            .get(self.entity)
+            .unwrap_or(EntityLocation::INVALID);
+    }
+
+    /// Runs the insertion hooks for the bundle that was just inserted, then applies the commands they queued.
+    ///
+    /// `old_archetype_id` is `None` if the entity was just spawned with the bundle.
+    pub(crate) fn after_insert(
+        &mut self,
+        bundle_id: BundleId,
+        old_archetype_id: Option<ArchetypeId>,
+    ) {
+        self.world
+            .trigger_insert_hooks(self.entity, bundle_id, old_archetype_id);
+        self.flush_commands();
+    }
+
+    /// Applies the commands queued by component hooks, which may move the current entity.
+    ///
+    /// If one of those commands despawned the current entity, its location is invalidated and the
+    /// methods of this [`EntityMut`] accessing the entity panic.
+    fn flush_commands(&mut self) {
+        self.world.flush_commands();
+        self.location = self
+            .world
+            .entities()
+            .get(self.entity)
+            .unwrap_or(EntityLocation::INVALID);
+    }
+
+    /// # Panics
+    ///
+    /// If the entity was despawned by the commands queued by a component hook.
+    #[inline]
+    #[track_caller]
+    fn assert_not_despawned(&self) {
+        assert!(
+            self.location != EntityLocation::INVALID,
+            "Entity {:?} was despawned by the commands of a component hook",
+            self.entity
+        );
     }
 }
 
@@ -0,0 +1,411 @@
+#[cfg(feature = "bevy_reflect")]
+use bevy_reflect::{Reflect, TypeRegistry};
+
+use crate::{
+    archetype::ArchetypeId,
+    component::{ComponentId, ComponentInfo},
+    storage::{Column, TableId},
+    world::{EntityRef, World},
+};
+#[cfg(feature = "bevy_reflect")]
+use crate::{
+    change_detection::Mut,
+    entity::Entity,
+    reflect::{ReflectComponent, ReflectResource},
+};
+
+/// A summary of an [`Archetype`](crate::archetype::Archetype), returned by [`World::archetype_summaries`].
+#[derive(Debug, Clone)]
+pub struct ArchetypeSummary<'w> {
+    /// The id of the archetype.
+    pub id: ArchetypeId,
+    /// The id of the [`Table`](crate::storage::Table) storing the table components of the archetype.
+    pub table_id: TableId,
+    /// The number of entities in the archetype.
+    pub entity_count: usize,
+    /// The components of the entities in the archetype.
+    pub components: Vec<&'w ComponentInfo>,
+}
+
+/// The memory used by the values of a component in the [`Storages`](crate::storage::Storages)
+/// of a world, returned by [`World::component_memory_usage`].
+#[derive(Debug, Clone)]
+pub struct ComponentMemoryUsage<'w> {
+    /// The component.
+    pub info: &'w ComponentInfo,
+    /// The number of values of the component in the world.
+    pub count: usize,
+    /// The number of bytes used by the values of the component and their change ticks.
+    pub used_bytes: usize,
+    /// The number of bytes allocated for the values of the component and their change ticks,
+    /// including the unused capacity of their storages.
+    pub allocated_bytes: usize,
+}
+
+/// A component or resource, with its value as a [`Reflect`] if its type is registered with
+/// [`ReflectComponent`] or [`ReflectResource`].
+#[cfg(feature = "bevy_reflect")]
+pub struct ReflectedComponent<'w> {
+    /// The component or resource.
+    pub info: &'w ComponentInfo,
+    /// The value of the component or resource, or `None` if it can't be reflected.
+    pub value: Option<&'w dyn Reflect>,
+}
+
+/// A component or resource, with its value as a mutable [`Reflect`] if its type is registered
+/// with [`ReflectComponent`] or [`ReflectResource`].
+#[cfg(feature = "bevy_reflect")]
+pub struct ReflectedComponentMut<'w> {
+    /// The component or resource.
+    pub info: &'w ComponentInfo,
+    /// The value of the component or resource, or `None` if it can't be reflected.
+    pub value: Option<Mut<'w, dyn Reflect>>,
+}
+
+impl World {
+    /// Returns an iterator over all entities of the world in the order of their index,
+    /// which stays the same as long as no entity is spawned or despawned.
+    ///
+    /// Unlike [`World::iter_entities`], the order doesn't depend on the archetypes of the entities.
+    pub fn iter_entities_ordered(&self) -> impl Iterator<Item = EntityRef<'_>> + '_ {
+        (0..self.entities.total_count() as u32).filter_map(|index| {
+            let entity = self.entities.resolve_from_id(index)?;
+            self.get_entity(entity)
+        })
+    }
+
+    /// Returns a summary of each [`Archetype`](crate::archetype::Archetype) of the world.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #[derive(Component)]
+    /// struct Health(u32);
+    ///
+    /// let mut world = World::new();
+    /// world.spawn(Health(100));
+    /// world.spawn(Health(50));
+    ///
+    /// let summary = world
+    ///     .archetype_summaries()
+    ///     .find(|summary| summary.entity_count > 0)
+    ///     .unwrap();
+    /// assert_eq!(summary.entity_count, 2);
+    /// assert!(summary.components[0].name().ends_with("Health"));
+    /// ```
+    pub fn archetype_summaries(&self) -> impl Iterator<Item = ArchetypeSummary<'_>> + '_ {
+        self.archetypes.iter().map(|archetype| ArchetypeSummary {
+            id: archetype.id(),
+            table_id: archetype.table_id(),
+            entity_count: archetype.len(),
+            components: archetype
+                .components()
+                .filter_map(|id| self.components.get_info(id))
+                .collect(),
+        })
+    }
+
+    /// Returns the memory used by the values of each component stored in the tables and sparse
+    /// sets of the world, ordered by [`ComponentId`].
+    ///
+    /// Components that were never stored in the world are skipped. Resources aren't included.
+    pub fn component_memory_usage(&self) -> Vec<ComponentMemoryUsage<'_>> {
+        let mut usages: Vec<Option<ComponentMemoryUsage>> = Vec::new();
+        let mut add = |id: ComponentId, column: &Column| {
+            if usages.len() <= id.index() {
+                usages.resize_with(id.index() + 1, || None);
+            }
+            let usage = usages[id.index()].get_or_insert_with(|| ComponentMemoryUsage {
+                info: self.components.get_info(id).unwrap(),
+                count: 0,
+                used_bytes: 0,
+                allocated_bytes: 0,
+            });
+            usage.count += column.len();
+            usage.used_bytes += column.used_bytes();
+            usage.allocated_bytes += column.allocated_bytes();
+        };
+
+        for table in self.storages.tables.iter() {
+            for (id, column) in table.iter_with_ids() {
+                add(id, column);
+            }
+        }
+        for (id, sparse_set) in self.storages.sparse_sets.iter() {
+            add(id, sparse_set.dense());
+        }
+        usages.into_iter().flatten().collect()
+    }
+
+    /// Returns the components of `entity`, with the value of those whose type is registered with
+    /// [`ReflectComponent`] in `registry`, or `None` if the entity doesn't exist.
+    ///
+    /// This is the reflection-aware version of [`World::inspect_entity`].
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_ecs::reflect::ReflectComponent;
+    /// # use bevy_reflect::{Reflect, TypeRegistry};
+    /// #[derive(Component, Reflect, Default)]
+    /// #[reflect(Component)]
+    /// struct Health(u32);
+    ///
+    /// let mut registry = TypeRegistry::default();
+    /// registry.register::<Health>();
+    ///
+    /// let mut world = World::new();
+    /// let entity = world.spawn(Health(100)).id();
+    ///
+    /// for component in world.reflect_entity(entity, &registry).unwrap() {
+    ///     println!("{}: {:?}", component.info.name(), component.value);
+    /// }
+    /// ```
+    #[cfg(feature = "bevy_reflect")]
+    pub fn reflect_entity(
+        &self,
+        entity: Entity,
+        registry: &TypeRegistry,
+    ) -> Option<Vec<ReflectedComponent<'_>>> {
+        let entity = self.get_entity(entity)?;
+        let archetype = entity.archetype();
+        Some(
+            archetype
+                .components()
+                .filter_map(|id| self.components.get_info(id))
+                .map(|info| ReflectedComponent {
+                    info,
+                    value: reflect_data::<ReflectComponent>(info, registry)
+                        .and_then(|reflect| reflect.reflect(entity)),
+                })
+                .collect(),
+        )
+    }
+
+    /// Returns the components of `entity`, with a mutable reference to the value of those whose
+    /// type is registered with [`ReflectComponent`] in `registry`, or `None` if the entity doesn't exist.
+    ///
+    /// See [`World::reflect_entity`] for the read-only version.
+    #[cfg(feature = "bevy_reflect")]
+    pub fn reflect_entity_mut(
+        &mut self,
+        entity: Entity,
+        registry: &TypeRegistry,
+    ) -> Option<Vec<ReflectedComponentMut<'_>>> {
+        let world = self.as_unsafe_world_cell();
+        let entity = world.get_entity(entity)?;
+        Some(
+            entity
+                .archetype()
+                .components()
+                .filter_map(|id| world.components().get_info(id))
+                .map(|info| ReflectedComponentMut {
+                    info,
+                    value: reflect_data::<ReflectComponent>(info, registry).and_then(|reflect| {
+                        // SAFETY: the world is borrowed mutably, and each component
+                        // of the entity is only borrowed once.
+                        unsafe { reflect.reflect_unchecked_mut(entity) }
+                    }),
+                })
+                .collect(),
+        )
+    }
+
+    /// Returns the resources of the world, with the value of those whose type is registered with
+    /// [`ReflectResource`] in `registry`, ordered by [`ComponentId`].
+    ///
+    /// `!Send` resources aren't included.
+    #[cfg(feature = "bevy_reflect")]
+    pub fn reflect_resources(&self, registry: &TypeRegistry) -> Vec<ReflectedComponent<'_>> {
+        self.storages
+            .resources
+            .iter()
+            .filter(|(_, data)| data.is_present())
+            .filter_map(|(id, _)| self.components.get_info(id))
+            .map(|info| ReflectedComponent {
+                info,
+                value: reflect_data::<ReflectResource>(info, registry)
+                    .and_then(|reflect| reflect.reflect(self)),
+            })
+            .collect()
+    }
+
+    /// Returns the resources of the world, with a mutable reference to the value of those whose
+    /// type is registered with [`ReflectResource`] in `registry`, ordered by [`ComponentId`].
+    ///
+    /// See [`World::reflect_resources`] for the read-only version.
+    #[cfg(feature = "bevy_reflect")]
+    pub fn reflect_resources_mut(
+        &mut self,
+        registry: &TypeRegistry,
+    ) -> Vec<ReflectedComponentMut<'_>> {
+        let resources: Vec<ComponentId> = self
+            .storages
+            .resources
+            .iter()
+            .filter(|(_, data)| data.is_present())
+            .map(|(id, _)| id)
+            .collect();
+        let world = self.as_unsafe_world_cell();
+        resources
+            .into_iter()
+            .filter_map(|id| world.components().get_info(id))
+            .map(|info| ReflectedComponentMut {
+                info,
+                value: reflect_data::<ReflectResource>(info, registry).and_then(|reflect| {
+                    // SAFETY: the world is borrowed mutably, and each resource is only borrowed once.
+                    unsafe { reflect.reflect_unchecked_mut(world) }
+                }),
+            })
+            .collect()
+    }
+}
+
+/// Returns the type data `T` registered in `registry` for the type of the component `info`.
+#[cfg(feature = "bevy_reflect")]
+fn reflect_data<'r, T: bevy_reflect::TypeData>(
+    info: &ComponentInfo,
+    registry: &'r TypeRegistry,
+) -> Option<&'r T> {
+    registry.get_type_data::<T>(info.type_id()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{self as bevy_ecs, prelude::*};
+
+    #[derive(Component, Debug, PartialEq)]
+    struct A(u64);
+
+    #[derive(Component)]
+    #[component(storage = "SparseSet")]
+    struct S;
+
+    #[test]
+    fn iter_entities_ordered() {
+        let mut world = World::new();
+        let a = world.spawn(A(0)).id();
+        let b = world.spawn(S).id();
+        let c = world.spawn((A(1), S)).id();
+        let d = world.spawn(A(2)).id();
+        world.despawn(b);
+        let e = world.spawn(S).id();
+
+        let entities: Vec<_> = world.iter_entities_ordered().map(|e| e.id()).collect();
+        assert_eq!(entities, vec![a, e, c, d]);
+    }
+
+    #[test]
+    fn archetype_summaries() {
+        let mut world = World::new();
+        world.spawn((A(0), S));
+        world.spawn((A(1), S));
+        let a = world.init_component::<A>();
+        let s = world.init_component::<S>();
+
+        let summary = world
+            .archetype_summaries()
+            .find(|summary| summary.entity_count > 0)
+            .unwrap();
+        assert_eq!(summary.entity_count, 2);
+        let mut components: Vec<_> = summary.components.iter().map(|info| info.id()).collect();
+        components.sort();
+        assert_eq!(components, vec![a, s]);
+    }
+
+    #[test]
+    fn component_memory_usage() {
+        let mut world = World::new();
+        world.spawn_batch((0..10).map(A));
+        world.spawn((A(10), S));
+        let a = world.init_component::<A>();
+        let s = world.init_component::<S>();
+
+        let usages = world.component_memory_usage();
+        assert_eq!(usages.len(), 2);
+        let usage_a = usages.iter().find(|usage| usage.info.id() == a).unwrap();
+        assert_eq!(usage_a.count, 11);
+        assert!(usage_a.used_bytes >= 11 * std::mem::size_of::<A>());
+        assert!(usage_a.allocated_bytes >= usage_a.used_bytes);
+        let usage_s = usages.iter().find(|usage| usage.info.id() == s).unwrap();
+        assert_eq!(usage_s.count, 1);
+
+        world.clear_entities();
+        world.compact();
+        let usages = world.component_memory_usage();
+        assert!(usages.iter().all(|usage| usage.allocated_bytes == 0));
+    }
+
+    #[cfg(feature = "bevy_reflect")]
+    mod reflect {
+        use bevy_reflect::{Reflect, TypeRegistry};
+
+        use crate::{
+            self as bevy_ecs,
+            prelude::*,
+            reflect::{ReflectComponent, ReflectResource},
+        };
+
+        #[derive(Component, Reflect, Default, Debug, PartialEq)]
+        #[reflect(Component)]
+        struct Health(u32);
+
+        #[derive(Component)]
+        struct Unregistered;
+
+        #[derive(Resource, Reflect, Default, Debug, PartialEq)]
+        #[reflect(Resource)]
+        struct Score(u32);
+
+        fn registry() -> TypeRegistry {
+            let mut registry = TypeRegistry::default();
+            registry.register::<Health>();
+            registry.register::<Score>();
+            registry
+        }
+
+        #[test]
+        fn reflect_entity() {
+            let registry = registry();
+            let mut world = World::new();
+            let entity = world.spawn((Health(100), Unregistered)).id();
+
+            let components = world.reflect_entity(entity, &registry).unwrap();
+            assert_eq!(components.len(), 2);
+            for component in &components {
+                if component.info.name().ends_with("Health") {
+                    let health = component.value.unwrap().downcast_ref::<Health>();
+                    assert_eq!(health, Some(&Health(100)));
+                } else {
+                    assert!(component.value.is_none());
+                }
+            }
+
+            for component in world.reflect_entity_mut(entity, &registry).unwrap() {
+                if let Some(mut value) = component.value {
+                    value.apply(&Health(50));
+                }
+            }
+            assert_eq!(world.get::<Health>(entity), Some(&Health(50)));
+
+            world.despawn(entity);
+            assert!(world.reflect_entity(entity, &registry).is_none());
+        }
+
+        #[test]
+        fn reflect_resources() {
+            let registry = registry();
+            let mut world = World::new();
+            world.insert_resource(Score(1));
+
+            let resources = world.reflect_resources(&registry);
+            assert_eq!(resources.len(), 1);
+            let score = resources[0].value.unwrap().downcast_ref::<Score>();
+            assert_eq!(score, Some(&Score(1)));
+
+            for resource in world.reflect_resources_mut(&registry) {
+                resource.value.unwrap().apply(&Score(2));
+            }
+            assert_eq!(world.resource::<Score>(), &Score(2));
+            assert!(world.is_resource_changed::<Score>());
+        }
+    }
+}
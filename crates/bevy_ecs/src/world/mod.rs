@@ -1,28 +1,44 @@
 //! Defines the [`World`] and APIs for accessing it directly.
 
+mod compaction;
+mod deferred_world;
 mod entity_ref;
 pub mod error;
+mod inspection;
+mod snapshot;
 mod spawn_batch;
 pub mod unsafe_world_cell;
 mod world_cell;
 
 pub use crate::change_detection::{Mut, Ref, CHECK_TICK_THRESHOLD};
+pub use compaction::{compact_world, CompactionPolicy};
+pub use deferred_world::DeferredWorld;
 pub use entity_ref::{EntityMut, EntityRef};
+pub use inspection::{ArchetypeSummary, ComponentMemoryUsage};
+#[cfg(feature = "bevy_reflect")]
+pub use inspection::{ReflectedComponent, ReflectedComponentMut};
+pub use snapshot::WorldSnapshot;
 pub use spawn_batch::*;
 pub use world_cell::*;
 
 use crate::{
     archetype::{ArchetypeComponentId, ArchetypeId, ArchetypeRow, Archetypes},
-    bundle::{Bundle, BundleInserter, BundleSpawner, Bundles},
+    bundle::{Bundle, BundleId, BundleInserter, BundleSpawner, Bundles},
     change_detection::{MutUntyped, TicksMut},
-    component::{Component, ComponentDescriptor, ComponentId, ComponentInfo, Components, Tick},
-    entity::{AllocAtWithoutReplacement, Entities, Entity, EntityLocation},
+    component::{
+        Component, ComponentDescriptor, ComponentHooks, ComponentId, ComponentInfo, Components,
+        Tick,
+    },
+    entity::{
+        AllocAtWithoutReplacement, Entities, Entity, EntityCloner, EntityLocation, MapEntities,
+    },
     event::{Event, Events},
     query::{DebugCheckedUnwrap, QueryState, ReadOnlyWorldQuery, WorldQuery},
+    relation::{self, RelationKind},
     removal_detection::RemovedComponentEvents,
     schedule::{Schedule, ScheduleLabel, Schedules},
     storage::{ResourceData, Storages},
-    system::Resource,
+    system::{CommandQueue, Resource},
     world::error::TryRunScheduleError,
 };
 use bevy_ptr::{OwningPtr, Ptr};
@@ -32,6 +48,10 @@ use std::{
     fmt,
     sync::atomic::{AtomicU32, Ordering},
 };
+
+#[cfg(feature = "track_change_detection")]
+use crate::change_detection::ChangedBy;
+
 mod identifier;
 
 pub use identifier::WorldId;
@@ -65,6 +85,8 @@ pub struct World {
     pub(crate) storages: Storages,
     pub(crate) bundles: Bundles,
     pub(crate) removed_components: RemovedComponentEvents,
+    /// Commands queued by [component hooks](ComponentHooks) through a [`DeferredWorld`].
+    pub(crate) command_queue: CommandQueue,
     /// Access cache used by [`WorldCell`]. Is only accessed in the `Drop` impl of `WorldCell`.
     pub(crate) archetype_component_access: ArchetypeComponentAccess,
     pub(crate) change_tick: AtomicU32,
@@ -82,6 +104,7 @@ impl Default for World {
             storages: Default::default(),
             bundles: Default::default(),
             removed_components: Default::default(),
+            command_queue: Default::default(),
             archetype_component_access: Default::default(),
             // Default value is `1`, and `last_change_tick`s default to `0`, such that changes
             // are detected on first system runs and for direct world queries.
@@ -180,6 +203,26 @@ impl World {
         self.components.init_component::<T>(&mut self.storages)
     }
 
+    /// Returns a mutable reference to the [`ComponentHooks`] of [`Component`] type `T`,
+    /// initializing the component if needed.
+    ///
+    /// See [`ComponentHooks`] for more details and an example.
+    pub fn register_component_hooks<T: Component>(&mut self) -> &mut ComponentHooks {
+        let id = self.init_component::<T>();
+        self.register_component_hooks_by_id(id).unwrap()
+    }
+
+    /// Returns a mutable reference to the [`ComponentHooks`] of the component with the given
+    /// [`ComponentId`], or `None` if it is not registered in this world.
+    pub fn register_component_hooks_by_id(
+        &mut self,
+        id: ComponentId,
+    ) -> Option<&mut ComponentHooks> {
+        self.components
+            .get_info_mut(id)
+            .map(ComponentInfo::hooks_mut)
+    }
+
     /// Initializes a new [`Component`] type and returns the [`ComponentId`] created for it.
     ///
     /// This method differs from [`World::init_component`] in that it uses a [`ComponentDescriptor`]
@@ -512,7 +555,7 @@ impl World {
         self.flush();
         let change_tick = self.change_tick();
         let entity = self.entities.alloc();
-        let entity_location = {
+        let (bundle_id, entity_location) = {
             let bundle_info = self
                 .bundles
                 .init_info::<B>(&mut self.components, &mut self.storages);
@@ -525,11 +568,15 @@ impl World {
             );
 
             // SAFETY: bundle's type matches `bundle_info`, entity is allocated but non-existent
-            unsafe { spawner.spawn_non_existent(entity, bundle) }
+            let location = unsafe { spawner.spawn_non_existent(entity, bundle) };
+            (bundle_info.id(), location)
         };
 
         // SAFETY: entity and location are valid, as they were just created above
-        unsafe { EntityMut::new(self, entity, entity_location) }
+        let mut entity_mut = unsafe { EntityMut::new(self, entity, entity_location) };
+        // the hooks may move or despawn the entity
+        entity_mut.after_insert(bundle_id, None);
+        entity_mut
     }
 
     /// # Safety
@@ -651,6 +698,150 @@ impl World {
         }
     }
 
+    /// Registers the [`Clone`] implementation of the component `T`, which allows it to be copied by
+    /// [`World::snapshot`]. Returns the [`ComponentId`] of `T`.
+    pub fn register_component_clone<T: Component + Clone>(&mut self) -> ComponentId {
+        let component_id = self.init_component::<T>();
+        self.components.register_clone::<T>(component_id);
+        component_id
+    }
+
+    /// Registers the [`MapEntities`] implementation of the component `T`, which allows its entity references
+    /// to be remapped when it's cloned by an [`EntityCloner`]. Returns the [`ComponentId`] of `T`.
+    pub fn register_component_map_entities<T: Component + MapEntities>(&mut self) -> ComponentId {
+        let component_id = self.init_component::<T>();
+        self.components.register_map_entities::<T>(component_id);
+        component_id
+    }
+
+    /// Registers the [`Clone`] implementation of the resource `R`, which allows it to be copied by
+    /// [`World::snapshot`]. Returns the [`ComponentId`] of `R`.
+    pub fn register_resource_clone<R: Resource + Clone>(&mut self) -> ComponentId {
+        let component_id = self.components.init_resource::<R>();
+        self.components.register_clone::<R>(component_id);
+        component_id
+    }
+
+    /// Takes a [`WorldSnapshot`] of the given components and resources, which can later be restored
+    /// with [`World::restore`].
+    ///
+    /// The snapshot also records which entities are alive and the state of the entity allocator.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #[derive(Component, Clone)]
+    /// struct Position(f32);
+    ///
+    /// let mut world = World::new();
+    /// let position = world.register_component_clone::<Position>();
+    /// let entity = world.spawn(Position(0.0)).id();
+    ///
+    /// let snapshot = world.snapshot(&[position], &[]);
+    /// world.get_mut::<Position>(entity).unwrap().0 = 1.0;
+    /// let bullet = world.spawn(Position(5.0)).id();
+    ///
+    /// world.restore(&snapshot);
+    /// assert_eq!(world.get::<Position>(entity).unwrap().0, 0.0);
+    /// assert!(world.get_entity(bullet).is_none());
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if any of the components or resources doesn't have a clone function registered,
+    /// for example with [`World::register_component_clone`], or isn't [`Send`] and [`Sync`].
+    pub fn snapshot(
+        &mut self,
+        component_ids: &[ComponentId],
+        resource_ids: &[ComponentId],
+    ) -> WorldSnapshot {
+        WorldSnapshot::take(self, component_ids, resource_ids)
+    }
+
+    /// Restores a [`WorldSnapshot`] taken from this world.
+    ///
+    /// Entities spawned since the snapshot was taken are despawned, and entities despawned since
+    /// then are spawned again with the same id. The components and resources copied by the snapshot
+    /// are then restored to their copied values and change ticks, and the entity allocator is rewound
+    /// so that the same entities are allocated again. Other components and resources are left untouched.
+    ///
+    /// Component hooks run as components are removed and inserted.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the snapshot was taken from another world.
+    pub fn restore(&mut self, snapshot: &WorldSnapshot) {
+        snapshot.restore(self);
+    }
+
+    /// Spawns a clone of `source` with all of its components that have a clone function registered,
+    /// for example with [`World::register_component_clone`], and returns it.
+    ///
+    /// Use an [`EntityCloner`] to choose which components are cloned, or to clone several entities at once.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #[derive(Component, Clone)]
+    /// struct Health(u32);
+    ///
+    /// let mut world = World::new();
+    /// world.register_component_clone::<Health>();
+    /// let entity = world.spawn(Health(10)).id();
+    ///
+    /// let clone = world.clone_entity(entity);
+    /// assert_eq!(world.get::<Health>(clone).unwrap().0, 10);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `source` doesn't exist.
+    pub fn clone_entity(&mut self, source: Entity) -> Entity {
+        EntityCloner::default().clone_entity(self, source)
+    }
+
+    /// Relates `source` to `target` with a [relation](crate::relation) of kind `R`.
+    ///
+    /// This adds `target` to the [`Relation<R>`](crate::relation::Relation) of `source`, and `source`
+    /// to the [`RelatedBy<R>`](crate::relation::RelatedBy) of `target`, inserting these components if needed.
+    /// Returns `false` if `source` was already related to `target`.
+    ///
+    /// ```
+    /// use bevy_ecs::{prelude::*, relation::{Relation, RelatedBy, RelationKind}};
+    ///
+    /// struct Owns;
+    /// impl RelationKind for Owns {}
+    ///
+    /// let mut world = World::new();
+    /// let player = world.spawn_empty().id();
+    /// let sword = world.spawn_empty().id();
+    /// world.relate::<Owns>(player, sword);
+    ///
+    /// assert!(world.get::<Relation<Owns>>(player).unwrap().contains(sword));
+    /// assert!(world.get::<RelatedBy<Owns>>(sword).unwrap().contains(player));
+    ///
+    /// // Relations are cleaned up when either of their endpoints is despawned.
+    /// world.despawn(sword);
+    /// assert!(world.get::<Relation<Owns>>(player).is_none());
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if either `source` or `target` does not exist.
+    pub fn relate<R: RelationKind>(&mut self, source: Entity, target: Entity) -> bool {
+        relation::relate::<R>(self, source, target)
+    }
+
+    /// Removes the [relation](crate::relation) of kind `R` from `source` to `target`.
+    ///
+    /// Returns `false` if `source` was not related to `target`.
+    pub fn unrelate<R: RelationKind>(&mut self, source: Entity, target: Entity) -> bool {
+        relation::unrelate::<R>(self, source, target)
+    }
+
+    /// Removes all the [relations](crate::relation) of kind `R` that have `source` as their source.
+    pub fn unrelate_all<R: RelationKind>(&mut self, source: Entity) {
+        relation::unrelate_all::<R>(self, source);
+    }
+
     /// Clears the internal component tracker state.
     ///
     /// The world maintains some internal state about changed and removed components. This state
@@ -983,6 +1174,20 @@ impl World {
             .unwrap_or(false)
     }
 
+    /// Returns where the resource of type `R` was most recently changed, if it exists and was
+    /// changed through change detection since it was inserted.
+    #[cfg(feature = "track_change_detection")]
+    pub fn get_resource_changed_by<R: Resource>(&self) -> Option<ChangedBy> {
+        let component_id = self.components.get_resource_id(TypeId::of::<R>())?;
+        let (_, ticks) = self
+            .storages
+            .resources
+            .get(component_id)?
+            .get_with_ticks()?;
+        // SAFETY: &self implies shared access to the resource
+        unsafe { bevy_ptr::UnsafeCellDeref::deref(ticks.changed_by) }.clone()
+    }
+
     /// Gets a reference to the resource of the given type
     ///
     /// # Panics
@@ -1211,6 +1416,13 @@ impl World {
         let bundle_info = self
             .bundles
             .init_info::<B>(&mut self.components, &mut self.storages);
+        let bundle_id = bundle_info.id();
+        // Hooks can't run while the storages are borrowed, so they are run once the whole batch is written.
+        let has_hooks = bundle_info
+            .components()
+            .iter()
+            .any(|&id| !self.components.get_info(id).unwrap().hooks().is_empty());
+        let mut hooked_entities = Vec::new();
         enum SpawnOrInsert<'a, 'b> {
             Spawn(BundleSpawner<'a, 'b>),
             Insert(BundleInserter<'a, 'b>, ArchetypeId),
@@ -1239,6 +1451,9 @@ impl World {
                 .alloc_at_without_replacement(entity)
             {
                 AllocAtWithoutReplacement::Exists(location) => {
+                    if has_hooks {
+                        hooked_entities.push((entity, Some(location.archetype_id)));
+                    }
                     match spawn_or_insert {
                         SpawnOrInsert::Insert(ref mut inserter, archetype)
                             if location.archetype_id == archetype =>
@@ -1263,6 +1478,9 @@ impl World {
                     };
                 }
                 AllocAtWithoutReplacement::DidNotExist => {
+                    if has_hooks {
+                        hooked_entities.push((entity, None));
+                    }
                     if let SpawnOrInsert::Spawn(ref mut spawner) = spawn_or_insert {
                         // SAFETY: `entity` is allocated (but non existent), bundle matches inserter
                         unsafe { spawner.spawn_non_existent(entity, bundle) };
@@ -1285,6 +1503,11 @@ impl World {
             }
         }
 
+        for (entity, old_archetype_id) in hooked_entities {
+            self.trigger_insert_hooks(entity, bundle_id, old_archetype_id);
+        }
+        self.flush_commands();
+
         if invalid_entities.is_empty() {
             Ok(())
         } else {
@@ -1337,6 +1560,8 @@ impl World {
             ticks: TicksMut {
                 added: &mut ticks.added,
                 changed: &mut ticks.changed,
+                #[cfg(feature = "track_change_detection")]
+                changed_by: None,
                 last_run: last_change_tick,
                 this_run: change_tick,
             },
@@ -1498,6 +1723,88 @@ impl World {
         }
     }
 
+    /// Applies the commands queued by [component hooks](ComponentHooks) through [`DeferredWorld::commands`].
+    ///
+    /// This is done automatically at the end of every [`World`] operation that can trigger hooks,
+    /// so calling it manually is rarely needed.
+    pub fn flush_commands(&mut self) {
+        while !self.command_queue.is_empty() {
+            let mut queue = std::mem::take(&mut self.command_queue);
+            queue.apply(self);
+        }
+    }
+
+    /// Runs the `on_add` and `on_insert` [hooks](ComponentHooks) for the components of the bundle
+    /// that was just inserted on `entity`.
+    ///
+    /// `old_archetype_id` is the archetype `entity` belonged to before the insertion, or `None`
+    /// if it was just spawned. It is used to tell newly added components apart from overwritten ones.
+    pub(crate) fn trigger_insert_hooks(
+        &mut self,
+        entity: Entity,
+        bundle_id: BundleId,
+        old_archetype_id: Option<ArchetypeId>,
+    ) {
+        let bundle_info = self.bundles.get(bundle_id).unwrap();
+        let hooks: Vec<(ComponentId, bool, ComponentHooks)> = bundle_info
+            .components()
+            .iter()
+            .filter_map(|&id| {
+                // SAFETY: bundle components are always registered in the world they belong to
+                let hooks = *unsafe { self.components.get_info_unchecked(id) }.hooks();
+                (hooks.on_add.is_some() || hooks.on_insert.is_some()).then(|| {
+                    let added = !old_archetype_id
+                        .is_some_and(|archetype_id| self.archetypes[archetype_id].contains(id));
+                    (id, added, hooks)
+                })
+            })
+            .collect();
+
+        for (id, added, hooks) in &hooks {
+            if let (true, Some(on_add)) = (added, hooks.on_add) {
+                on_add(DeferredWorld::from(&mut *self), entity, *id);
+            }
+        }
+        for (id, _, hooks) in &hooks {
+            if let Some(on_insert) = hooks.on_insert {
+                on_insert(DeferredWorld::from(&mut *self), entity, *id);
+            }
+        }
+    }
+
+    /// Runs the `on_remove` [hooks](ComponentHooks) for the components of `entity` that are about to
+    /// be removed: the components of the given bundle that are in `archetype_id`, or all of them if
+    /// `bundle_id` is `None`.
+    pub(crate) fn trigger_remove_hooks(
+        &mut self,
+        entity: Entity,
+        archetype_id: ArchetypeId,
+        bundle_id: Option<BundleId>,
+    ) {
+        let archetype = &self.archetypes[archetype_id];
+        let on_remove = |id: ComponentId| {
+            // SAFETY: archetype components are always registered in the world they belong to
+            let hooks = unsafe { self.components.get_info_unchecked(id) }.hooks();
+            hooks.on_remove.map(|hook| (id, hook))
+        };
+        let hooks: Vec<_> = match bundle_id {
+            Some(bundle_id) => self
+                .bundles
+                .get(bundle_id)
+                .unwrap()
+                .components()
+                .iter()
+                .filter(|&&id| archetype.contains(id))
+                .filter_map(|&id| on_remove(id))
+                .collect(),
+            None => archetype.components().filter_map(on_remove).collect(),
+        };
+
+        for (id, on_remove) in hooks {
+            on_remove(DeferredWorld::from(&mut *self), entity, id);
+        }
+    }
+
     /// Increments the world's current change tick and returns the old value.
     #[inline]
     pub fn increment_change_tick(&self) -> Tick {
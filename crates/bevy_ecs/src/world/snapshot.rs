@@ -0,0 +1,617 @@
+use bevy_utils::HashSet;
+
+use crate::{
+    component::{ComponentCloneFn, ComponentId, ComponentInfo, ComponentTicks, StorageType},
+    entity::{EntitiesSnapshot, Entity},
+    query::DebugCheckedUnwrap,
+    storage::{BlobVec, Column, TableId, TableRow},
+    world::{World, WorldId},
+};
+
+/// A copy of part of a [`World`], taken with [`World::snapshot`] and restored with [`World::restore`].
+///
+/// A snapshot holds:
+/// - the set of alive entities and the state of the entity allocator, so that entities spawned
+///   after restoring get the same ids they got after the snapshot was taken,
+/// - the values and change ticks of the chosen components, for every entity,
+/// - the values and change ticks of the chosen resources.
+///
+/// Components and resources that weren't chosen are left untouched by [`World::restore`],
+/// except on entities that get despawned because they didn't exist in the snapshot.
+///
+/// Component values are copied table by table and sparse set by sparse set. When restoring, the
+/// values of the entities that still have the component are written back in place, without
+/// running component hooks. Only the entities that gained or lost a component since the snapshot
+/// was taken go through [`EntityMut::insert_by_id`](super::EntityMut::insert_by_id) and
+/// [`EntityMut::remove_by_id`](super::EntityMut::remove_by_id).
+pub struct WorldSnapshot {
+    world_id: WorldId,
+    entities: EntitiesSnapshot,
+    alive: Vec<Entity>,
+    component_ids: Vec<ComponentId>,
+    tables: Vec<TableSnapshot>,
+    sparse_sets: Vec<SparseSetSnapshot>,
+    resources: Vec<ResourceSnapshot>,
+}
+
+// SAFETY: only `Send + Sync` components and resources can be snapshotted, so the values
+// stored in the `BlobVec`s can be accessed from any thread.
+unsafe impl Send for WorldSnapshot {}
+// SAFETY: see above.
+unsafe impl Sync for WorldSnapshot {}
+
+/// The chosen table components of the entities of a table, in the order of its rows.
+struct TableSnapshot {
+    table_id: TableId,
+    entities: Vec<Entity>,
+    columns: Vec<ColumnSnapshot>,
+}
+
+/// The values of a chosen sparse set component, in the order of `entities`.
+struct SparseSetSnapshot {
+    entities: Vec<Entity>,
+    column: ColumnSnapshot,
+}
+
+struct ColumnSnapshot {
+    component_id: ComponentId,
+    clone: ComponentCloneFn,
+    ticks: Vec<ComponentTicks>,
+    values: BlobVec,
+}
+
+struct ResourceSnapshot {
+    component_id: ComponentId,
+    clone: ComponentCloneFn,
+    // `None` if the resource didn't exist.
+    ticks: Option<ComponentTicks>,
+    // Holds the value of the resource if it existed.
+    value: BlobVec,
+}
+
+impl ColumnSnapshot {
+    /// Copies the values and ticks of the rows of `column`.
+    ///
+    /// # Safety
+    /// `column` must store the values of the component of `clone`.
+    unsafe fn copy<'a>(
+        component_id: ComponentId,
+        clone: ComponentCloneFn,
+        mut values: BlobVec,
+        rows: impl Iterator<Item = (&'a Column, TableRow)>,
+    ) -> Self {
+        let mut ticks = Vec::new();
+        for (column, row) in rows {
+            clone(column.get_data_unchecked(row), &mut |value| {
+                values.push(value)
+            });
+            ticks.push(column.get_ticks_unchecked(row));
+        }
+        ColumnSnapshot {
+            component_id,
+            clone,
+            ticks,
+            values,
+        }
+    }
+
+    /// Overwrites the value and ticks of `row` in `column` with the ones at `index`.
+    ///
+    /// # Safety
+    /// `column` must store the values of the component of this snapshot, `row` must be an
+    /// initialized row of `column`, and `index` must be in range of this snapshot.
+    unsafe fn write(&self, index: usize, column: &mut Column, row: TableRow) {
+        let ticks = self.ticks[index];
+        (self.clone)(self.values.get_unchecked(index), &mut |value| {
+            column.replace(row, value, ticks.changed);
+        });
+        *column.get_added_ticks_unchecked(row).get() = ticks.added;
+    }
+
+    /// Inserts the value at `index` on `entity` through the world, which moves it to a new archetype.
+    ///
+    /// # Safety
+    /// `index` must be in range of this snapshot.
+    unsafe fn insert(&self, index: usize, world: &mut World, entity: Entity) {
+        let Some(mut entity_mut) = world.get_entity_mut(entity) else {
+            // A hook despawned the entity.
+            return;
+        };
+        (self.clone)(self.values.get_unchecked(index), &mut |value| {
+            entity_mut.insert_by_id(self.component_id, value);
+        });
+        if let Some(value) = entity_mut.get_mut_by_id(self.component_id) {
+            *value.ticks.added = self.ticks[index].added;
+            *value.ticks.changed = self.ticks[index].changed;
+        }
+    }
+}
+
+impl WorldSnapshot {
+    /// Returns the id of the [`World`] this snapshot was taken from.
+    #[inline]
+    pub fn world_id(&self) -> WorldId {
+        self.world_id
+    }
+
+    /// Returns the entities that were alive when this snapshot was taken.
+    #[inline]
+    pub fn entities(&self) -> &[Entity] {
+        &self.alive
+    }
+
+    /// Returns the ids of the components copied by this snapshot.
+    pub fn component_ids(&self) -> impl Iterator<Item = ComponentId> + '_ {
+        self.component_ids.iter().copied()
+    }
+
+    /// Returns the ids of the resources copied by this snapshot.
+    pub fn resource_ids(&self) -> impl Iterator<Item = ComponentId> + '_ {
+        self.resources.iter().map(|snapshot| snapshot.component_id)
+    }
+
+    pub(crate) fn take(
+        world: &mut World,
+        component_ids: &[ComponentId],
+        resource_ids: &[ComponentId],
+    ) -> Self {
+        world.flush();
+
+        let alive = world
+            .archetypes
+            .iter()
+            .flat_map(|archetype| archetype.entities())
+            .map(|archetype_entity| archetype_entity.entity())
+            .collect();
+
+        let mut table_components = Vec::new();
+        let mut sparse_sets = Vec::new();
+        for &component_id in component_ids {
+            let (clone, values) = snapshot_storage(world, component_id);
+            let info = world.components.get_info(component_id).unwrap();
+            if info.storage_type() == StorageType::Table {
+                table_components.push(component_id);
+                continue;
+            }
+            let Some(sparse_set) = world.storages.sparse_sets.get(component_id) else {
+                continue;
+            };
+            let entities: Vec<Entity> = world
+                .archetypes
+                .iter()
+                .filter(|archetype| archetype.contains(component_id))
+                .flat_map(|archetype| archetype.entities())
+                .map(|archetype_entity| archetype_entity.entity())
+                .collect();
+            // SAFETY: the dense column of the sparse set stores the values of the component, and
+            // the entities are in archetypes that contain it, so they have a row in the column.
+            let column = unsafe {
+                ColumnSnapshot::copy(
+                    component_id,
+                    clone,
+                    values,
+                    entities.iter().map(|&entity| {
+                        let row = sparse_set.dense_row(entity).debug_checked_unwrap();
+                        (sparse_set.dense(), row)
+                    }),
+                )
+            };
+            sparse_sets.push(SparseSetSnapshot { entities, column });
+        }
+
+        let tables = world
+            .storages
+            .tables
+            .iter()
+            .enumerate()
+            .filter(|(_, table)| !table.is_empty())
+            .filter_map(|(index, table)| {
+                let columns: Vec<_> = table_components
+                    .iter()
+                    .filter_map(|&component_id| {
+                        let column = table.get_column(component_id)?;
+                        let (clone, values) = snapshot_storage(world, component_id);
+                        let rows =
+                            (0..table.entity_count()).map(|row| (column, TableRow::new(row)));
+                        // SAFETY: the column stores the values of the component.
+                        Some(unsafe { ColumnSnapshot::copy(component_id, clone, values, rows) })
+                    })
+                    .collect();
+                (!columns.is_empty()).then(|| TableSnapshot {
+                    table_id: TableId::new(index),
+                    entities: table.entities().to_vec(),
+                    columns,
+                })
+            })
+            .collect();
+
+        let resources = resource_ids
+            .iter()
+            .map(|&component_id| {
+                let (clone, mut value) = snapshot_storage(world, component_id);
+                let ticks = world
+                    .storages
+                    .resources
+                    .get(component_id)
+                    .and_then(|resource| resource.get_with_ticks())
+                    .map(|(ptr, ticks)| {
+                        // SAFETY: `clone` and `value` both belong to the resource, and `&World`
+                        // ensures there are no mutable references to the ticks.
+                        unsafe {
+                            clone(ptr, &mut |ptr| value.push(ptr));
+                            ticks.read()
+                        }
+                    });
+                ResourceSnapshot {
+                    component_id,
+                    clone,
+                    ticks,
+                    value,
+                }
+            })
+            .collect();
+
+        WorldSnapshot {
+            world_id: world.id(),
+            entities: world.entities.snapshot(),
+            alive,
+            component_ids: component_ids.to_vec(),
+            tables,
+            sparse_sets,
+            resources,
+        }
+    }
+
+    pub(crate) fn restore(&self, world: &mut World) {
+        assert_eq!(
+            self.world_id,
+            world.id(),
+            "A WorldSnapshot can only be restored to the World it was taken from."
+        );
+        world.flush();
+
+        let alive: HashSet<Entity> = self.alive.iter().copied().collect();
+        despawn_unknown_entities(world, &alive);
+        world.entities.restore(&self.entities);
+        for &entity in &self.alive {
+            if world.entities.get(entity).is_none() {
+                // SAFETY: the entity was allocated by restoring the entity allocator, and isn't
+                // in any archetype.
+                unsafe { world.spawn_at_empty_internal(entity) };
+            }
+        }
+
+        for &component_id in &self.component_ids {
+            self.remove_stale_components(world, component_id);
+        }
+
+        // The values of the entities that lost a component since the snapshot was taken are
+        // inserted back once the other values are restored in place.
+        let mut missing = Vec::new();
+        for snapshot in &self.tables {
+            let tables = &mut world.storages.tables;
+            let mut table =
+                (snapshot.table_id.index() < tables.len()).then(|| &mut tables[snapshot.table_id]);
+            for column_snapshot in &snapshot.columns {
+                let mut column = table
+                    .as_deref_mut()
+                    .and_then(|table| table.get_column_mut(column_snapshot.component_id));
+                for (index, &entity) in snapshot.entities.iter().enumerate() {
+                    match (world.entities.get(entity), column.as_deref_mut()) {
+                        (Some(location), Some(column))
+                            if location.table_id == snapshot.table_id =>
+                        {
+                            // SAFETY: the entity is in the table, so `table_row` is an initialized
+                            // row of the column of the component.
+                            unsafe { column_snapshot.write(index, column, location.table_row) };
+                        }
+                        _ => missing.push((column_snapshot, index, entity)),
+                    }
+                }
+            }
+        }
+        for snapshot in &self.sparse_sets {
+            let column_snapshot = &snapshot.column;
+            let mut sparse_set = world
+                .storages
+                .sparse_sets
+                .get_mut(column_snapshot.component_id);
+            for (index, &entity) in snapshot.entities.iter().enumerate() {
+                let row = sparse_set
+                    .as_deref()
+                    .filter(|_| world.entities.get(entity).is_some())
+                    .and_then(|sparse_set| sparse_set.dense_row(entity));
+                match (row, sparse_set.as_deref_mut()) {
+                    (Some(row), Some(sparse_set)) => {
+                        // SAFETY: the entity has the component, so `row` is an initialized row of
+                        // the dense column of the sparse set.
+                        unsafe { column_snapshot.write(index, sparse_set.dense_mut(), row) };
+                    }
+                    _ => missing.push((column_snapshot, index, entity)),
+                }
+            }
+        }
+        for (column_snapshot, index, entity) in missing {
+            // SAFETY: `index` is the index of `entity` in the snapshot.
+            unsafe { column_snapshot.insert(index, world, entity) };
+        }
+
+        for snapshot in &self.resources {
+            let component_id = snapshot.component_id;
+            let Some(ticks) = snapshot.ticks else {
+                world.remove_resource_by_id(component_id);
+                continue;
+            };
+            // SAFETY: `value` holds the value of the resource, and `clone` belongs to it.
+            unsafe {
+                let value = snapshot.value.get_unchecked(0);
+                (snapshot.clone)(value, &mut |value| {
+                    world.insert_resource_by_id(component_id, value);
+                });
+            }
+            if let Some(value) = world.get_resource_mut_by_id(component_id) {
+                *value.ticks.added = ticks.added;
+                *value.ticks.changed = ticks.changed;
+            }
+        }
+
+        // Hooks may have spawned entities while the components were restored.
+        despawn_unknown_entities(world, &alive);
+        world.entities.restore(&self.entities);
+    }
+
+    /// Removes the component from the entities that didn't have it when the snapshot was taken.
+    fn remove_stale_components(&self, world: &mut World, component_id: ComponentId) {
+        let table_owners = self
+            .tables
+            .iter()
+            .filter(|snapshot| {
+                snapshot
+                    .columns
+                    .iter()
+                    .any(|column| column.component_id == component_id)
+            })
+            .flat_map(|snapshot| &snapshot.entities);
+        let sparse_set_owners = self
+            .sparse_sets
+            .iter()
+            .filter(|snapshot| snapshot.column.component_id == component_id)
+            .flat_map(|snapshot| &snapshot.entities);
+        let owners: HashSet<Entity> = table_owners.chain(sparse_set_owners).copied().collect();
+
+        let stale: Vec<Entity> = world
+            .archetypes
+            .iter()
+            .filter(|archetype| archetype.contains(component_id))
+            .flat_map(|archetype| archetype.entities())
+            .map(|archetype_entity| archetype_entity.entity())
+            .filter(|entity| !owners.contains(entity))
+            .collect();
+        for entity in stale {
+            if let Some(mut entity_mut) = world.get_entity_mut(entity) {
+                entity_mut.remove_by_id(component_id);
+            }
+        }
+    }
+}
+
+/// Returns the clone function of the component or resource, and an empty [`BlobVec`] for its values.
+fn snapshot_storage(world: &World, component_id: ComponentId) -> (ComponentCloneFn, BlobVec) {
+    let info: &ComponentInfo = world
+        .components
+        .get_info(component_id)
+        .unwrap_or_else(|| panic!("{component_id:?} does not exist in this World"));
+    assert!(
+        info.is_send_and_sync(),
+        "Cannot snapshot {}: only Send + Sync components and resources can be snapshotted.",
+        info.name()
+    );
+    let clone = info.clone_fn().unwrap_or_else(|| {
+        panic!(
+            "Cannot snapshot {}: it doesn't have a clone function. Register one with `World::register_component_clone` or `World::register_resource_clone`.",
+            info.name()
+        )
+    });
+    // SAFETY: the drop function matches the layout of the component.
+    let values = unsafe { BlobVec::new(info.layout(), info.drop(), 0) };
+    (clone, values)
+}
+
+/// Despawns every entity that isn't in `alive`, including the ones spawned by hooks while doing so.
+fn despawn_unknown_entities(world: &mut World, alive: &HashSet<Entity>) {
+    loop {
+        let unknown: Vec<Entity> = world
+            .archetypes
+            .iter()
+            .flat_map(|archetype| archetype.entities())
+            .map(|archetype_entity| archetype_entity.entity())
+            .filter(|entity| !alive.contains(entity))
+            .collect();
+        if unknown.is_empty() {
+            return;
+        }
+        for entity in unknown {
+            world.despawn(entity);
+        }
+        world.flush();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{self as bevy_ecs, prelude::*};
+
+    #[derive(Component, Clone, Debug, PartialEq)]
+    struct A(usize);
+
+    #[derive(Component, Clone, Debug, PartialEq)]
+    #[component(storage = "SparseSet")]
+    struct B(String);
+
+    #[derive(Component, Debug, PartialEq)]
+    struct NotSnapshotted(usize);
+
+    #[derive(Resource, Clone, Debug, PartialEq)]
+    struct Frame(usize);
+
+    #[test]
+    fn restore_components_and_entities() {
+        let mut world = World::new();
+        let a = world.register_component_clone::<A>();
+        let b = world.register_component_clone::<B>();
+
+        let e1 = world.spawn((A(1), B("one".to_string()))).id();
+        let e2 = world.spawn(A(2)).id();
+        let despawned = world.spawn(A(3)).id();
+        world.despawn(despawned);
+
+        let snapshot = world.snapshot(&[a, b], &[]);
+
+        let spawned = world.spawn(A(4)).id();
+        world.get_mut::<A>(e1).unwrap().0 = 10;
+        world.entity_mut(e1).remove::<B>();
+        world.entity_mut(e2).insert(B("two".to_string()));
+        world.despawn(e2);
+        world.spawn(A(5));
+
+        world.restore(&snapshot);
+
+        assert_eq!(world.get::<A>(e1), Some(&A(1)));
+        assert_eq!(world.get::<B>(e1), Some(&B("one".to_string())));
+        assert_eq!(world.get::<A>(e2), Some(&A(2)));
+        assert_eq!(world.get::<B>(e2), None);
+        assert!(world.get_entity(spawned).is_none());
+        assert_eq!(world.entities().len(), 2);
+
+        // Entities are allocated exactly like they were after the snapshot was taken.
+        assert_eq!(world.spawn_empty().id(), spawned);
+    }
+
+    #[test]
+    fn restore_moved_entities() {
+        let mut world = World::new();
+        let a = world.register_component_clone::<A>();
+        let b = world.register_component_clone::<B>();
+
+        let e1 = world.spawn((A(1), B("one".to_string()))).id();
+        let e2 = world.spawn((A(2), B("two".to_string()))).id();
+        let e3 = world.spawn((A(3), B("three".to_string()))).id();
+        let snapshot = world.snapshot(&[a, b], &[]);
+
+        // e3 takes the row of e1 in the table, and e2 moves to another table
+        world.despawn(e1);
+        world.get_mut::<A>(e3).unwrap().0 = 30;
+        world.entity_mut(e3).remove::<B>();
+        world.entity_mut(e2).insert(NotSnapshotted(2));
+        world.get_mut::<B>(e2).unwrap().0.push('!');
+
+        world.restore(&snapshot);
+
+        for (entity, a, b) in [(e1, 1, "one"), (e2, 2, "two"), (e3, 3, "three")] {
+            assert_eq!(world.get::<A>(entity), Some(&A(a)));
+            assert_eq!(world.get::<B>(entity), Some(&B(b.to_string())));
+        }
+        assert_eq!(world.get::<NotSnapshotted>(e2), Some(&NotSnapshotted(2)));
+    }
+
+    #[test]
+    fn restore_in_place_skips_hooks() {
+        #[derive(Resource, Default)]
+        struct Inserted(usize);
+
+        let mut world = World::new();
+        world.init_resource::<Inserted>();
+        world
+            .register_component_hooks::<A>()
+            .on_insert(|mut world, _, _| world.resource_mut::<Inserted>().0 += 1);
+        let a = world.register_component_clone::<A>();
+        let entity = world.spawn(A(0)).id();
+        let snapshot = world.snapshot(&[a], &[]);
+
+        world.get_mut::<A>(entity).unwrap().0 = 1;
+        world.restore(&snapshot);
+        assert_eq!(world.get::<A>(entity), Some(&A(0)));
+        assert_eq!(world.resource::<Inserted>().0, 1);
+
+        // values that were removed are inserted back
+        world.entity_mut(entity).remove::<A>();
+        world.restore(&snapshot);
+        assert_eq!(world.get::<A>(entity), Some(&A(0)));
+        assert_eq!(world.resource::<Inserted>().0, 2);
+    }
+
+    #[test]
+    fn restore_is_repeatable() {
+        let mut world = World::new();
+        let a = world.register_component_clone::<A>();
+        let entity = world.spawn(A(0)).id();
+        let snapshot = world.snapshot(&[a], &[]);
+
+        let mut spawned = Vec::new();
+        for _ in 0..3 {
+            world.get_mut::<A>(entity).unwrap().0 += 1;
+            spawned.push(world.spawn(A(1)).id());
+            world.restore(&snapshot);
+            assert_eq!(world.get::<A>(entity), Some(&A(0)));
+        }
+        assert!(spawned.windows(2).all(|ids| ids[0] == ids[1]));
+    }
+
+    #[test]
+    fn restore_change_ticks() {
+        let mut world = World::new();
+        let a = world.register_component_clone::<A>();
+        let entity = world.spawn(A(0)).id();
+        world.clear_trackers();
+        let ticks = world.entity(entity).get_change_ticks::<A>().unwrap();
+        let snapshot = world.snapshot(&[a], &[]);
+
+        world.clear_trackers();
+        world.get_mut::<A>(entity).unwrap().0 = 1;
+        world.restore(&snapshot);
+
+        let restored = world.entity(entity).get_change_ticks::<A>().unwrap();
+        assert_eq!(restored.added.get(), ticks.added.get());
+        assert_eq!(restored.changed.get(), ticks.changed.get());
+    }
+
+    #[test]
+    fn restore_resources() {
+        let mut world = World::new();
+        let frame = world.register_resource_clone::<Frame>();
+        world.insert_resource(Frame(1));
+        let with_frame = world.snapshot(&[], &[frame]);
+
+        world.remove_resource::<Frame>();
+        let without_frame = world.snapshot(&[], &[frame]);
+
+        world.restore(&with_frame);
+        assert_eq!(world.get_resource::<Frame>(), Some(&Frame(1)));
+        world.resource_mut::<Frame>().0 = 2;
+        world.restore(&without_frame);
+        assert_eq!(world.get_resource::<Frame>(), None);
+    }
+
+    #[test]
+    fn untouched_components() {
+        let mut world = World::new();
+        let a = world.register_component_clone::<A>();
+        let entity = world.spawn((A(0), NotSnapshotted(0))).id();
+        let snapshot = world.snapshot(&[a], &[]);
+
+        world.get_mut::<NotSnapshotted>(entity).unwrap().0 = 1;
+        world.restore(&snapshot);
+        assert_eq!(
+            world.get::<NotSnapshotted>(entity),
+            Some(&NotSnapshotted(1))
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn no_clone_fn() {
+        let mut world = World::new();
+        let id = world.init_component::<NotSnapshotted>();
+        world.snapshot(&[id], &[]);
+    }
+}
@@ -15,7 +15,15 @@ where
     I::Item: Bundle,
 {
     inner: I,
-    spawner: BundleSpawner<'w, 'w>,
+    spawner: BatchSpawner<'w>,
+}
+
+enum BatchSpawner<'w> {
+    /// Spawns entities with a single, cached [`BundleSpawner`].
+    Cached(BundleSpawner<'w, 'w>),
+    /// Spawns entities one at a time through [`World::spawn`], so that the
+    /// [component hooks](crate::component::ComponentHooks) of the bundle run for each of them.
+    Hooked(&'w mut World),
 }
 
 impl<'w, I> SpawnBatchIter<'w, I>
@@ -34,10 +42,24 @@ where
         let (lower, upper) = iter.size_hint();
         let length = upper.unwrap_or(lower);
 
-        let bundle_info = world
+        let bundle_id = world
             .bundles
-            .init_info::<I::Item>(&mut world.components, &mut world.storages);
+            .init_info::<I::Item>(&mut world.components, &mut world.storages)
+            .id();
         world.entities.reserve(length as u32);
+        let bundle_info = world.bundles.get(bundle_id).unwrap();
+        let has_hooks = bundle_info
+            .components()
+            .iter()
+            .any(|&id| !world.components.get_info(id).unwrap().hooks().is_empty());
+        if has_hooks {
+            return Self {
+                inner: iter,
+                spawner: BatchSpawner::Hooked(world),
+            };
+        }
+
+        let bundle_info = world.bundles.get(bundle_id).unwrap();
         let mut spawner = bundle_info.get_bundle_spawner(
             &mut world.entities,
             &mut world.archetypes,
@@ -49,7 +71,7 @@ where
 
         Self {
             inner: iter,
-            spawner,
+            spawner: BatchSpawner::Cached(spawner),
         }
     }
 }
@@ -73,8 +95,11 @@ where
 
     fn next(&mut self) -> Option<Entity> {
         let bundle = self.inner.next()?;
-        // SAFETY: bundle matches spawner type
-        unsafe { Some(self.spawner.spawn(bundle)) }
+        match &mut self.spawner {
+            // SAFETY: bundle matches spawner type
+            BatchSpawner::Cached(spawner) => unsafe { Some(spawner.spawn(bundle)) },
+            BatchSpawner::Hooked(world) => Some(world.spawn(bundle).id()),
+        }
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
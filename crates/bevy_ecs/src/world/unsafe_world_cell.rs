@@ -950,6 +950,8 @@ unsafe fn get_component_and_ticks(
                 TickCells {
                     added: components.get_added_ticks_unchecked(location.table_row),
                     changed: components.get_changed_ticks_unchecked(location.table_row),
+                    #[cfg(feature = "track_change_detection")]
+                    changed_by: components.get_changed_by_unchecked(location.table_row),
                 },
             ))
         }
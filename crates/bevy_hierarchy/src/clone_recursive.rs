@@ -0,0 +1,239 @@
+use crate::{
+    child_builder::BuildWorldChildren,
+    components::{Children, Parent},
+};
+use bevy_ecs::{
+    entity::{Entity, EntityCloner, EntityMap},
+    system::{Command, EntityCommands},
+    world::{EntityMut, World},
+};
+
+/// Clones the given entity and all its descendants into `target`
+#[derive(Debug)]
+pub struct CloneRecursive {
+    /// Entity to clone
+    pub source: Entity,
+    /// Entity receiving the clone of `source`
+    pub target: Entity,
+    /// Chooses which components are cloned
+    pub cloner: EntityCloner,
+}
+
+/// Function for cloning an entity and all its descendants.
+///
+/// The components of `source` are cloned into `target`, and each descendant is cloned into a new entity.
+/// The clones form the same hierarchy as the originals, and if `source` has a [`Parent`], `target` is added
+/// as its last child. References between the cloned entities are remapped as described in [`EntityCloner`].
+///
+/// [`Parent`] and [`Children`] are never cloned by `cloner`: they are rebuilt for the clones instead.
+pub fn clone_with_children_recursive(
+    world: &mut World,
+    source: Entity,
+    target: Entity,
+    cloner: &EntityCloner,
+) {
+    let mut map = EntityMap::default();
+    map.insert(source, target);
+    let mut sources = vec![source];
+    let mut index = 0;
+    while let Some(&entity) = sources.get(index) {
+        if let Some(children) = world.get::<Children>(entity) {
+            let children = children.to_vec();
+            for &child in &children {
+                map.insert(child, world.spawn_empty().id());
+            }
+            sources.extend(children);
+        }
+        index += 1;
+    }
+
+    cloner
+        .clone()
+        .deny::<Parent>()
+        .deny::<Children>()
+        .clone_mapped(world, &mut map);
+
+    for &entity in &sources {
+        let Some(children) = world.get::<Children>(entity) else {
+            continue;
+        };
+        let children: Vec<Entity> = children
+            .iter()
+            .map(|child| map.get(*child).unwrap())
+            .collect();
+        let parent = map.get(entity).unwrap();
+        world.entity_mut(parent).push_children(&children);
+    }
+    if let Some(parent) = world.get::<Parent>(source).map(|parent| parent.get()) {
+        world.entity_mut(parent).add_child(target);
+    }
+}
+
+impl Command for CloneRecursive {
+    fn apply(self, world: &mut World) {
+        #[cfg(feature = "trace")]
+        let _span = bevy_utils::tracing::info_span!(
+            "command",
+            name = "CloneRecursive",
+            entity = bevy_utils::tracing::field::debug(self.source)
+        )
+        .entered();
+        clone_with_children_recursive(world, self.source, self.target, &self.cloner);
+    }
+}
+
+/// Trait that holds functions for cloning recursively down the transform hierarchy
+pub trait CloneRecursiveExt {
+    /// Clones the provided entity alongside all descendants, and returns the cloned entity.
+    ///
+    /// See [`clone_with_children_recursive`] for more details.
+    fn clone_recursive(&mut self) -> Entity;
+
+    /// Clones the provided entity alongside all descendants using the given [`EntityCloner`],
+    /// and returns the cloned entity.
+    fn clone_recursive_with(&mut self, cloner: EntityCloner) -> Entity;
+}
+
+impl<'w, 's, 'a> CloneRecursiveExt for EntityCommands<'w, 's, 'a> {
+    fn clone_recursive(&mut self) -> Entity {
+        self.clone_recursive_with(EntityCloner::default())
+    }
+
+    fn clone_recursive_with(&mut self, cloner: EntityCloner) -> Entity {
+        let source = self.id();
+        let target = self.commands().spawn_empty().id();
+        self.commands().add(CloneRecursive {
+            source,
+            target,
+            cloner,
+        });
+        target
+    }
+}
+
+impl<'w> CloneRecursiveExt for EntityMut<'w> {
+    fn clone_recursive(&mut self) -> Entity {
+        self.clone_recursive_with(EntityCloner::default())
+    }
+
+    fn clone_recursive_with(&mut self, cloner: EntityCloner) -> Entity {
+        let source = self.id();
+
+        #[cfg(feature = "trace")]
+        let _span = bevy_utils::tracing::info_span!(
+            "clone_recursive",
+            entity = bevy_utils::tracing::field::debug(source)
+        )
+        .entered();
+
+        self.world_scope(|world| {
+            let target = world.spawn_empty().id();
+            clone_with_children_recursive(world, source, target, &cloner);
+            target
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::{
+        component::Component,
+        entity::{Entity, EntityCloner, EntityMapper, MapEntities},
+        system::{CommandQueue, Commands},
+        world::World,
+    };
+
+    use super::CloneRecursiveExt;
+    use crate::{
+        child_builder::BuildWorldChildren,
+        components::{Children, Parent},
+    };
+
+    #[derive(Component, Clone, Copy, PartialEq, Eq, Debug)]
+    struct Idx(u32);
+
+    #[derive(Component, Clone)]
+    struct Sibling(Entity);
+
+    impl MapEntities for Sibling {
+        fn map_entities(&mut self, entity_mapper: &mut EntityMapper) {
+            self.0 = entity_mapper.get_or_reserve(self.0);
+        }
+    }
+
+    fn world() -> World {
+        let mut world = World::default();
+        world.register_component_clone::<Idx>();
+        world.register_component_clone::<Sibling>();
+        world.register_component_map_entities::<Sibling>();
+        world
+    }
+
+    fn children(world: &World, entity: Entity) -> Vec<Entity> {
+        world
+            .get::<Children>(entity)
+            .map(|children| children.to_vec())
+            .unwrap_or_default()
+    }
+
+    fn idx(world: &World, entity: Entity) -> u32 {
+        world.get::<Idx>(entity).unwrap().0
+    }
+
+    #[test]
+    fn clone_recursive() {
+        let mut world = world();
+        let root = world.spawn(Idx(0)).id();
+        let mut first = Entity::PLACEHOLDER;
+        world.entity_mut(root).with_children(|parent| {
+            first = parent
+                .spawn(Idx(1))
+                .with_children(|parent| {
+                    parent.spawn(Idx(3));
+                })
+                .id();
+            parent.spawn((Idx(2), Sibling(first)));
+        });
+
+        let clone = world.entity_mut(root).clone_recursive();
+
+        assert_eq!(idx(&world, clone), 0);
+        assert!(world.get::<Parent>(clone).is_none());
+        let cloned_children = children(&world, clone);
+        assert_eq!(cloned_children.len(), 2);
+        assert!(!children(&world, root).contains(&cloned_children[0]));
+        assert_eq!(idx(&world, cloned_children[0]), 1);
+        assert_eq!(idx(&world, cloned_children[1]), 2);
+        for &child in &cloned_children {
+            assert_eq!(world.get::<Parent>(child).unwrap().get(), clone);
+        }
+        assert_eq!(
+            world.get::<Sibling>(cloned_children[1]).unwrap().0,
+            cloned_children[0]
+        );
+
+        let grandchildren = children(&world, cloned_children[0]);
+        assert_eq!(grandchildren.len(), 1);
+        assert_eq!(idx(&world, grandchildren[0]), 3);
+        assert_eq!(children(&world, first).len(), 1);
+        assert_ne!(children(&world, first), grandchildren);
+    }
+
+    #[test]
+    fn clone_recursive_child() {
+        let mut world = world();
+        let parent = world.spawn(Idx(0)).id();
+        let child = world.spawn(Idx(1)).id();
+        world.entity_mut(parent).add_child(child);
+        let mut queue = CommandQueue::default();
+
+        let clone = Commands::new(&mut queue, &world)
+            .entity(child)
+            .clone_recursive_with(EntityCloner::default().deny::<Idx>());
+        queue.apply(&mut world);
+
+        assert!(world.get::<Idx>(clone).is_none());
+        assert_eq!(world.get::<Parent>(clone).unwrap().get(), parent);
+        assert_eq!(children(&world, parent), vec![child, clone]);
+    }
+}
@@ -0,0 +1,316 @@
+use std::{marker::PhantomData, ops::Deref};
+
+use bevy_app::{App, Plugin, PostUpdate};
+use bevy_ecs::{
+    event::ManualEventReader,
+    prelude::*,
+    system::{BoxedSystem, IntoSystem},
+};
+
+use crate::Parent;
+
+/// An [`Event`] addressed to an entity, which can be observed by an [`On<E>`] component on that
+/// entity, and bubbles up to the [`On<E>`] components of its ancestors.
+///
+/// Entity events are delivered to their listeners by [`EntityEventPlugin<E>`].
+///
+/// # Example
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_hierarchy::prelude::*;
+/// #[derive(Event, Clone)]
+/// struct Damage {
+///     target: Entity,
+///     amount: u32,
+/// }
+///
+/// impl EntityEvent for Damage {
+///     fn target(&self) -> Entity {
+///         self.target
+///     }
+/// }
+/// ```
+pub trait EntityEvent: Event + Clone {
+    /// Returns the entity the event is addressed to.
+    fn target(&self) -> Entity;
+
+    /// Returns `true` if the event propagates to the ancestors of its target,
+    /// following the [`Parent`] chain. Defaults to `true`.
+    fn can_bubble(&self) -> bool {
+        true
+    }
+}
+
+/// A component running a system whenever an [`EntityEvent`] of type `E` reaches its entity,
+/// either because it is the target of the event, or because the event bubbled up from a descendant.
+///
+/// The system can read the event with the [`Listener<E>`] resource, and stop it from reaching
+/// the ancestors of this entity with [`Listener::stop_propagation`].
+///
+/// # Example
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_hierarchy::prelude::*;
+/// # #[derive(Event, Clone)]
+/// # struct Click(Entity);
+/// # impl EntityEvent for Click {
+/// #     fn target(&self) -> Entity {
+/// #         self.0
+/// #     }
+/// # }
+/// fn spawn_button(mut commands: Commands) {
+///     commands.spawn(On::<Click>::run(|mut click: ResMut<Listener<Click>>| {
+///         println!("{:?} was clicked", click.target());
+///         click.stop_propagation();
+///     }));
+/// }
+/// # bevy_ecs::system::assert_is_system(spawn_button);
+/// ```
+#[derive(Component)]
+pub struct On<E: EntityEvent> {
+    /// `None` while the system is running.
+    system: Option<BoxedSystem>,
+    initialized: bool,
+    _marker: PhantomData<fn(E)>,
+}
+
+impl<E: EntityEvent> On<E> {
+    /// Creates a listener running `system` for each event of type `E` reaching its entity.
+    pub fn run<M>(system: impl IntoSystem<(), (), M>) -> Self {
+        Self {
+            system: Some(Box::new(IntoSystem::into_system(system))),
+            initialized: false,
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// The [`EntityEvent`] being delivered to an [`On<E>`] listener, available as a resource
+/// to the listener's system while it runs.
+#[derive(Resource)]
+pub struct Listener<E: EntityEvent> {
+    event: E,
+    listener: Entity,
+    propagate: bool,
+}
+
+impl<E: EntityEvent> Listener<E> {
+    /// Returns the event.
+    pub fn event(&self) -> &E {
+        &self.event
+    }
+
+    /// Returns the entity the event is addressed to.
+    pub fn target(&self) -> Entity {
+        self.event.target()
+    }
+
+    /// Returns the entity of the [`On<E>`] listener currently running,
+    /// which is the target or one of its ancestors.
+    pub fn listener(&self) -> Entity {
+        self.listener
+    }
+
+    /// Stops the event from reaching the ancestors of the current listener.
+    pub fn stop_propagation(&mut self) {
+        self.propagate = false;
+    }
+}
+
+impl<E: EntityEvent> Deref for Listener<E> {
+    type Target = E;
+
+    fn deref(&self) -> &Self::Target {
+        &self.event
+    }
+}
+
+/// Delivers each [`EntityEvent`] of type `E` sent since the last run to the [`On<E>`] listeners
+/// of its target, then of the target's ancestors until the event stops propagating.
+pub fn dispatch_entity_events<E: EntityEvent>(
+    world: &mut World,
+    mut reader: Local<ManualEventReader<E>>,
+) {
+    let Some(events) = world.get_resource::<Events<E>>() else {
+        return;
+    };
+    let events: Vec<E> = reader.iter(events).cloned().collect();
+
+    for event in events {
+        let mut entity = Some(event.target());
+        while let Some(listener) = entity {
+            if !run_listener(world, listener, &event) || !event.can_bubble() {
+                break;
+            }
+            entity = world.get::<Parent>(listener).map(Parent::get);
+        }
+    }
+}
+
+/// Runs the [`On<E>`] listener of `entity` if it has one, and returns `true` if the event keeps propagating.
+fn run_listener<E: EntityEvent>(world: &mut World, entity: Entity, event: &E) -> bool {
+    // take the system out of the world, so that it can run with exclusive access to the world
+    let Some((mut system, initialized)) = world
+        .get_mut::<On<E>>(entity)
+        .and_then(|mut on| Some((on.system.take()?, on.initialized)))
+    else {
+        return true;
+    };
+    if !initialized {
+        system.initialize(world);
+    }
+
+    world.insert_resource(Listener {
+        event: event.clone(),
+        listener: entity,
+        propagate: true,
+    });
+    system.run((), world);
+    system.apply_deferred(world);
+    let propagate = match world.remove_resource::<Listener<E>>() {
+        Some(listener) => listener.propagate,
+        None => true,
+    };
+
+    // the listener may have been removed while running
+    if let Some(mut on) = world.get_mut::<On<E>>(entity) {
+        if on.system.is_none() {
+            on.system = Some(system);
+            on.initialized = true;
+        }
+    }
+    propagate
+}
+
+/// Adds the [`EntityEvent`] `E` and delivers it to its [`On<E>`] listeners
+/// with [`dispatch_entity_events`] in [`PostUpdate`].
+pub struct EntityEventPlugin<E: EntityEvent>(PhantomData<fn() -> E>);
+
+impl<E: EntityEvent> Default for EntityEventPlugin<E> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<E: EntityEvent> Plugin for EntityEventPlugin<E> {
+    fn build(&self, app: &mut App) {
+        app.add_event::<E>()
+            .add_systems(PostUpdate, dispatch_entity_events::<E>);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_app::App;
+    use bevy_ecs::prelude::*;
+
+    use super::{EntityEvent, EntityEventPlugin, Listener, On};
+    use crate::BuildWorldChildren;
+
+    #[derive(Event, Clone)]
+    struct Click {
+        target: Entity,
+        bubbles: bool,
+    }
+
+    impl EntityEvent for Click {
+        fn target(&self) -> Entity {
+            self.target
+        }
+
+        fn can_bubble(&self) -> bool {
+            self.bubbles
+        }
+    }
+
+    #[derive(Resource, Default)]
+    struct Clicked(Vec<(&'static str, Entity, Entity)>);
+
+    fn log(name: &'static str, stop: bool) -> On<Click> {
+        On::run(
+            move |mut click: ResMut<Listener<Click>>, mut clicked: ResMut<Clicked>| {
+                clicked.0.push((name, click.target(), click.listener()));
+                if stop {
+                    click.stop_propagation();
+                }
+            },
+        )
+    }
+
+    fn click(app: &mut App, target: Entity, bubbles: bool) -> Vec<(&'static str, Entity, Entity)> {
+        app.world.send_event(Click { target, bubbles });
+        app.update();
+        std::mem::take(&mut app.world.resource_mut::<Clicked>().0)
+    }
+
+    #[test]
+    fn bubbling() {
+        let mut app = App::new();
+        app.add_plugins(EntityEventPlugin::<Click>::default())
+            .init_resource::<Clicked>();
+
+        let root = app.world.spawn(log("root", false)).id();
+        let panel = app.world.spawn(log("panel", true)).set_parent(root).id();
+        let container = app.world.spawn_empty().set_parent(panel).id();
+        let button = app
+            .world
+            .spawn(log("button", false))
+            .set_parent(container)
+            .id();
+
+        assert_eq!(
+            click(&mut app, button, true),
+            vec![("button", button, button), ("panel", button, panel)]
+        );
+        assert_eq!(
+            click(&mut app, button, false),
+            vec![("button", button, button)]
+        );
+        assert_eq!(
+            click(&mut app, container, true),
+            vec![("panel", container, panel)]
+        );
+
+        app.world.entity_mut(panel).remove::<On<Click>>();
+        assert_eq!(
+            click(&mut app, button, true),
+            vec![("button", button, button), ("root", button, root)]
+        );
+    }
+
+    #[test]
+    fn listener_state() {
+        #[derive(Resource, Default)]
+        struct Runs(Vec<usize>);
+
+        let mut app = App::new();
+        app.add_plugins(EntityEventPlugin::<Click>::default())
+            .init_resource::<Runs>();
+        let target = app
+            .world
+            .spawn(On::<Click>::run(
+                |mut runs: ResMut<Runs>, mut count: Local<usize>, mut commands: Commands| {
+                    *count += 1;
+                    runs.0.push(*count);
+                    commands.spawn_empty();
+                },
+            ))
+            .id();
+
+        let entities = app.world.entities().len();
+        app.world.send_event(Click {
+            target,
+            bubbles: true,
+        });
+        app.world.send_event(Click {
+            target,
+            bubbles: true,
+        });
+        app.update();
+
+        assert_eq!(app.world.resource::<Runs>().0, vec![1, 2]);
+        assert_eq!(app.world.entities().len(), entities + 2);
+    }
+}
@@ -14,9 +14,15 @@ pub use hierarchy::*;
 mod child_builder;
 pub use child_builder::*;
 
+mod clone_recursive;
+pub use clone_recursive::*;
+
 mod events;
 pub use events::*;
 
+mod entity_event;
+pub use entity_event::*;
+
 mod valid_parent_check_plugin;
 pub use valid_parent_check_plugin::*;
 
@@ -27,8 +33,8 @@ pub use query_extension::*;
 pub mod prelude {
     #[doc(hidden)]
     pub use crate::{
-        child_builder::*, components::*, hierarchy::*, query_extension::*, HierarchyPlugin,
-        ValidParentCheckPlugin,
+        child_builder::*, clone_recursive::*, components::*, entity_event::*, hierarchy::*,
+        query_extension::*, HierarchyPlugin, ValidParentCheckPlugin,
     };
 }
 
@@ -7,6 +7,7 @@ pub mod gamepad;
 mod input;
 pub mod keyboard;
 pub mod mouse;
+pub mod testing;
 pub mod touch;
 pub mod touchpad;
 
@@ -0,0 +1,143 @@
+//! Input injection for apps driven by a [`TestApp`].
+
+use bevy_app::testing::TestApp;
+use bevy_ecs::entity::Entity;
+
+use crate::{
+    gamepad::{
+        Gamepad, GamepadAxisChangedEvent, GamepadAxisType, GamepadButtonChangedEvent,
+        GamepadButtonType, GamepadConnection, GamepadConnectionEvent, GamepadEvent, GamepadInfo,
+    },
+    keyboard::{KeyCode, KeyboardInput},
+    mouse::{MouseButton, MouseButtonInput},
+    ButtonState,
+};
+
+/// Methods of a [`TestApp`] sending the input events read by the [`InputPlugin`](crate::InputPlugin),
+/// as if they came from a device.
+///
+/// The events are read during the next frame, and are attributed to [`Entity::PLACEHOLDER`]
+/// when they need a window.
+///
+/// ```
+/// # use bevy_app::{prelude::*, testing::TestApp};
+/// # use bevy_input::{prelude::*, testing::InjectInput, InputPlugin};
+/// let mut app = App::new();
+/// app.add_plugins(InputPlugin);
+/// let mut app = TestApp::new(app);
+///
+/// app.press_key(KeyCode::Space).update();
+/// assert!(app.world.resource::<Input<KeyCode>>().just_pressed(KeyCode::Space));
+/// ```
+pub trait InjectInput {
+    /// Presses a key of the keyboard.
+    fn press_key(&mut self, key_code: KeyCode) -> &mut Self;
+
+    /// Releases a key of the keyboard.
+    fn release_key(&mut self, key_code: KeyCode) -> &mut Self;
+
+    /// Presses a button of the mouse.
+    fn press_mouse_button(&mut self, button: MouseButton) -> &mut Self;
+
+    /// Releases a button of the mouse.
+    fn release_mouse_button(&mut self, button: MouseButton) -> &mut Self;
+
+    /// Connects `gamepad`, which is needed for its buttons and axes to be taken into account.
+    fn connect_gamepad(&mut self, gamepad: Gamepad) -> &mut Self;
+
+    /// Disconnects `gamepad`.
+    fn disconnect_gamepad(&mut self, gamepad: Gamepad) -> &mut Self;
+
+    /// Sets the value of a button of `gamepad`, between `0.0` when it's released and `1.0`
+    /// when it's fully pressed.
+    fn set_gamepad_button(
+        &mut self,
+        gamepad: Gamepad,
+        button_type: GamepadButtonType,
+        value: f32,
+    ) -> &mut Self;
+
+    /// Sets the value of an axis of `gamepad`, between `-1.0` and `1.0`.
+    fn set_gamepad_axis(
+        &mut self,
+        gamepad: Gamepad,
+        axis_type: GamepadAxisType,
+        value: f32,
+    ) -> &mut Self;
+}
+
+impl InjectInput for TestApp {
+    fn press_key(&mut self, key_code: KeyCode) -> &mut Self {
+        self.send_event(keyboard_input(key_code, ButtonState::Pressed))
+    }
+
+    fn release_key(&mut self, key_code: KeyCode) -> &mut Self {
+        self.send_event(keyboard_input(key_code, ButtonState::Released))
+    }
+
+    fn press_mouse_button(&mut self, button: MouseButton) -> &mut Self {
+        self.send_event(MouseButtonInput {
+            button,
+            state: ButtonState::Pressed,
+            window: Entity::PLACEHOLDER,
+        })
+    }
+
+    fn release_mouse_button(&mut self, button: MouseButton) -> &mut Self {
+        self.send_event(MouseButtonInput {
+            button,
+            state: ButtonState::Released,
+            window: Entity::PLACEHOLDER,
+        })
+    }
+
+    fn connect_gamepad(&mut self, gamepad: Gamepad) -> &mut Self {
+        let info = GamepadInfo {
+            name: format!("Test gamepad {}", gamepad.id),
+        };
+        self.send_event(GamepadEvent::Connection(GamepadConnectionEvent::new(
+            gamepad,
+            GamepadConnection::Connected(info),
+        )))
+    }
+
+    fn disconnect_gamepad(&mut self, gamepad: Gamepad) -> &mut Self {
+        self.send_event(GamepadEvent::Connection(GamepadConnectionEvent::new(
+            gamepad,
+            GamepadConnection::Disconnected,
+        )))
+    }
+
+    fn set_gamepad_button(
+        &mut self,
+        gamepad: Gamepad,
+        button_type: GamepadButtonType,
+        value: f32,
+    ) -> &mut Self {
+        self.send_event(GamepadEvent::Button(GamepadButtonChangedEvent::new(
+            gamepad,
+            button_type,
+            value,
+        )))
+    }
+
+    fn set_gamepad_axis(
+        &mut self,
+        gamepad: Gamepad,
+        axis_type: GamepadAxisType,
+        value: f32,
+    ) -> &mut Self {
+        self.send_event(GamepadEvent::Axis(GamepadAxisChangedEvent::new(
+            gamepad, axis_type, value,
+        )))
+    }
+}
+
+fn keyboard_input(key_code: KeyCode, state: ButtonState) -> KeyboardInput {
+    KeyboardInput {
+        scan_code: 0,
+        key_code: Some(key_code),
+        state,
+        window: Entity::PLACEHOLDER,
+    }
+}
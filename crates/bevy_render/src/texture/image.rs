@@ -16,6 +16,7 @@ use bevy_derive::{Deref, DerefMut};
 use bevy_ecs::system::{lifetimeless::SRes, Resource, SystemParamItem};
 use bevy_math::Vec2;
 use bevy_reflect::{FromReflect, Reflect, TypeUuid};
+use serde::{Deserialize, Serialize};
 
 use std::hash::Hash;
 use thiserror::Error;
@@ -26,7 +27,7 @@ pub const SAMPLER_ASSET_INDEX: u64 = 1;
 pub const DEFAULT_IMAGE_HANDLE: HandleUntyped =
     HandleUntyped::weak_from_u64(Image::TYPE_UUID, 13148262314052771789);
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ImageFormat {
     Avif,
     Basis,
@@ -449,6 +450,8 @@ pub enum ImageType<'a> {
     MimeType(&'a str),
     /// The extension of an image file, for example `"png"`.
     Extension(&'a str),
+    /// The format of an image.
+    Format(ImageFormat),
 }
 
 impl<'a> ImageType<'a> {
@@ -458,6 +461,7 @@ impl<'a> ImageType<'a> {
                 .ok_or_else(|| TextureError::InvalidImageMimeType(mime_type.to_string())),
             ImageType::Extension(extension) => ImageFormat::from_extension(extension)
                 .ok_or_else(|| TextureError::InvalidImageExtension(extension.to_string())),
+            ImageType::Format(format) => Ok(*format),
         }
     }
 }
@@ -2,11 +2,12 @@ use anyhow::Result;
 use bevy_asset::{AssetLoader, LoadContext, LoadedAsset};
 use bevy_ecs::prelude::{FromWorld, World};
 use bevy_utils::BoxedFuture;
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 use crate::{
     renderer::RenderDevice,
-    texture::{Image, ImageType, TextureError},
+    texture::{Image, ImageFormat, ImageSampler, ImageType, TextureError},
 };
 
 use super::CompressedImageFormats;
@@ -17,6 +18,48 @@ pub struct ImageTextureLoader {
     supported_compressed_formats: CompressedImageFormats,
 }
 
+/// The sampler of an image loaded by the [`ImageTextureLoader`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ImageTextureSampler {
+    /// The default sampler of the [`ImagePlugin`](super::ImagePlugin).
+    #[default]
+    Default,
+    /// A sampler with [`Linear`](crate::render_resource::FilterMode::Linear) filters.
+    Linear,
+    /// A sampler with [`Nearest`](crate::render_resource::FilterMode::Nearest) filters.
+    Nearest,
+}
+
+/// The settings of the [`ImageTextureLoader`], read from the `.meta` file of an image or given
+/// to [`AssetServer::load_with_settings`](bevy_asset::AssetServer::load_with_settings).
+///
+/// ```ron
+/// (
+///     settings: (is_srgb: false, sampler: Nearest),
+/// )
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ImageTextureSettings {
+    /// The format of the image, or `None` to use the format of the image's extension.
+    pub format: Option<ImageFormat>,
+    /// Whether the image is in the sRGB color space. Images containing data rather than colors,
+    /// such as normal maps, should set this to `false`.
+    pub is_srgb: bool,
+    /// The sampler of the image.
+    pub sampler: ImageTextureSampler,
+}
+
+impl Default for ImageTextureSettings {
+    fn default() -> Self {
+        Self {
+            format: None,
+            is_srgb: true,
+            sampler: ImageTextureSampler::Default,
+        }
+    }
+}
+
 const FILE_EXTENSIONS: &[&str] = &[
     #[cfg(feature = "basis-universal")]
     "basis",
@@ -53,20 +96,33 @@ impl AssetLoader for ImageTextureLoader {
         load_context: &'a mut LoadContext,
     ) -> BoxedFuture<'a, Result<()>> {
         Box::pin(async move {
-            // use the file extension for the image type
-            let ext = load_context.path().extension().unwrap().to_str().unwrap();
+            let settings = load_context.settings::<ImageTextureSettings>()?;
 
-            let dyn_img = Image::from_buffer(
+            // use the file extension for the image type, unless the settings have a format
+            let image_type = match settings.format {
+                Some(format) => ImageType::Format(format),
+                None => {
+                    ImageType::Extension(load_context.path().extension().unwrap().to_str().unwrap())
+                }
+            };
+
+            let mut dyn_img = Image::from_buffer(
                 bytes,
-                ImageType::Extension(ext),
+                image_type,
                 self.supported_compressed_formats,
-                true,
+                settings.is_srgb,
             )
             .map_err(|err| FileTextureError {
                 error: err,
                 path: format!("{}", load_context.path().display()),
             })?;
 
+            dyn_img.sampler_descriptor = match settings.sampler {
+                ImageTextureSampler::Default => ImageSampler::Default,
+                ImageTextureSampler::Linear => ImageSampler::linear(),
+                ImageTextureSampler::Nearest => ImageSampler::nearest(),
+            };
+
             load_context.set_default_asset(LoadedAsset::new(dyn_img));
             Ok(())
         })
@@ -0,0 +1,28 @@
+use anyhow::Result;
+use bevy_asset::{AssetSaver, SaveContext};
+use image::ImageOutputFormat;
+
+use crate::texture::Image;
+
+/// Saver for images in the PNG format.
+///
+/// Only the formats supported by [`Image::try_into_dynamic`] can be saved.
+#[derive(Clone, Default)]
+pub struct ImageTextureSaver;
+
+impl AssetSaver for ImageTextureSaver {
+    type Asset = Image;
+
+    fn save(&self, image: &Image, _: &SaveContext) -> Result<Vec<u8>> {
+        let mut bytes = Vec::new();
+        image.clone().try_into_dynamic()?.write_to(
+            &mut std::io::Cursor::new(&mut bytes),
+            ImageOutputFormat::Png,
+        )?;
+        Ok(bytes)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["png"]
+    }
+}
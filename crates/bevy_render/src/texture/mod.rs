@@ -10,6 +10,8 @@ mod hdr_texture_loader;
 #[allow(clippy::module_inception)]
 mod image;
 mod image_texture_loader;
+#[cfg(feature = "png")]
+mod image_texture_saver;
 #[cfg(feature = "ktx2")]
 mod ktx2;
 mod texture_cache;
@@ -28,6 +30,8 @@ pub use hdr_texture_loader::*;
 
 pub use fallback_image::*;
 pub use image_texture_loader::*;
+#[cfg(feature = "png")]
+pub use image_texture_saver::*;
 pub use texture_cache::*;
 
 use crate::{
@@ -83,6 +87,11 @@ impl Plugin for ImagePlugin {
             app.init_asset_loader::<ImageTextureLoader>();
         }
 
+        #[cfg(feature = "png")]
+        {
+            app.add_asset_saver(ImageTextureSaver);
+        }
+
         #[cfg(feature = "exr")]
         {
             app.init_asset_loader::<ExrTextureLoader>();
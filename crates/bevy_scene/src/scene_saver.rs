@@ -0,0 +1,39 @@
+#[cfg(feature = "serialize")]
+use anyhow::Result;
+#[cfg(feature = "serialize")]
+use bevy_asset::{AssetSaver, SaveContext};
+use bevy_ecs::reflect::AppTypeRegistry;
+use bevy_ecs::world::{FromWorld, World};
+use bevy_reflect::TypeRegistryArc;
+
+#[cfg(feature = "serialize")]
+use crate::DynamicScene;
+
+/// Saver for [`DynamicScene`](crate::DynamicScene)s in the format read by the
+/// [`SceneLoader`](crate::SceneLoader).
+#[derive(Debug)]
+pub struct SceneSaver {
+    type_registry: TypeRegistryArc,
+}
+
+impl FromWorld for SceneSaver {
+    fn from_world(world: &mut World) -> Self {
+        let type_registry = world.resource::<AppTypeRegistry>();
+        SceneSaver {
+            type_registry: type_registry.0.clone(),
+        }
+    }
+}
+
+#[cfg(feature = "serialize")]
+impl AssetSaver for SceneSaver {
+    type Asset = DynamicScene;
+
+    fn save(&self, scene: &DynamicScene, _: &SaveContext) -> Result<Vec<u8>> {
+        Ok(scene.serialize_ron(&self.type_registry)?.into_bytes())
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["scn", "scn.ron"]
+    }
+}
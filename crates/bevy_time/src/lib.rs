@@ -4,6 +4,7 @@
 pub mod common_conditions;
 pub mod fixed_timestep;
 mod stopwatch;
+pub mod testing;
 #[allow(clippy::module_inception)]
 mod time;
 mod timer;
@@ -0,0 +1,82 @@
+//! Manual control of [`Time`] for apps driven by a [`TestApp`].
+
+use bevy_app::testing::TestApp;
+use bevy_utils::{Duration, Instant};
+
+#[cfg(doc)]
+use crate::Time;
+use crate::TimeUpdateStrategy;
+
+/// Methods of a [`TestApp`] controlling the [`Time`] of its frames through the
+/// [`TimeUpdateStrategy`] resource, instead of the real elapsed time.
+///
+/// ```
+/// # use bevy_app::{prelude::*, testing::TestApp};
+/// # use bevy_time::{testing::ControlTime, Time, TimePlugin};
+/// # use bevy_utils::Duration;
+/// let mut app = App::new();
+/// app.add_plugins(TimePlugin);
+/// let mut app = TestApp::new(app);
+///
+/// app.set_frame_time(Duration::from_millis(100)).update_frames(3);
+/// assert_eq!(app.world.resource::<Time>().delta(), Duration::from_millis(100));
+/// ```
+pub trait ControlTime {
+    /// Advances the time by `duration` on each of the next frames.
+    fn set_frame_time(&mut self, duration: Duration) -> &mut Self;
+
+    /// Sets the time to `instant` on each of the next frames.
+    fn set_time(&mut self, instant: Instant) -> &mut Self;
+
+    /// Advances the app by one frame, during which the time advances by `duration`.
+    ///
+    /// The time keeps advancing by `duration` on the next frames.
+    fn update_by(&mut self, duration: Duration) -> &mut Self;
+}
+
+impl ControlTime for TestApp {
+    fn set_frame_time(&mut self, duration: Duration) -> &mut Self {
+        self.insert_resource(TimeUpdateStrategy::ManualDuration(duration));
+        self
+    }
+
+    fn set_time(&mut self, instant: Instant) -> &mut Self {
+        self.insert_resource(TimeUpdateStrategy::ManualInstant(instant));
+        self
+    }
+
+    fn update_by(&mut self, duration: Duration) -> &mut Self {
+        self.set_frame_time(duration).update()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_app::{testing::TestApp, App};
+    use bevy_utils::Duration;
+
+    use super::ControlTime;
+    use crate::{Time, TimePlugin};
+
+    #[test]
+    fn control_time() {
+        let mut app = App::new();
+        app.add_plugins(TimePlugin);
+        let mut app = TestApp::new(app);
+        app.update();
+        let elapsed = app.world.resource::<Time>().elapsed();
+
+        app.update_by(Duration::from_secs(2))
+            .update_by(Duration::from_secs(1));
+        let time = app.world.resource::<Time>();
+        assert_eq!(time.delta(), Duration::from_secs(1));
+        assert_eq!(time.elapsed() - elapsed, Duration::from_secs(3));
+
+        let startup = time.startup();
+        app.set_time(startup + Duration::from_secs(10)).update();
+        assert_eq!(
+            app.world.resource::<Time>().elapsed(),
+            Duration::from_secs(10)
+        );
+    }
+}